@@ -0,0 +1,309 @@
+/*
+    Appellation: check <autodiff>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+//! # Gradient Checking
+//!
+//! Verifies analytic gradients, such as those produced by a [Tape](super::Tape), against
+//! those approximated by central differences. Each element of every parameter tensor is
+//! perturbed by `±eps` in turn, and the objective re-evaluated, so the check is only suitable
+//! for small models.
+use crate::func::loss::Loss;
+use crate::nn::optim::Optimizable;
+use crate::rust::Vec;
+use crate::{Forward, Predict, PredictError};
+use core::convert::Infallible;
+use nd::prelude::*;
+use nd::{Data, Zip};
+use num::traits::Float;
+
+/// [GradCheck] compares the gradients of a model's parameters with those approximated by
+/// central differences, `(f(x + eps) - f(x - eps)) / 2eps`.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct GradCheck<A = f64> {
+    pub eps: A,
+}
+
+/// [GradError] reports the discrepancy between the analytic and numeric gradients of a single
+/// parameter tensor.
+///
+/// The relative error of each element is `|a - n| / max(|a|, |n|)`, which is taken to be zero
+/// should both gradients vanish.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct GradError<A = f64> {
+    pub analytic: ArrayD<A>,
+    pub numeric: ArrayD<A>,
+    pub max_abs: A,
+    pub max_rel: A,
+}
+
+/// [Objective] reduces the output of a [Loss] to the scalar being differentiated; unreduced
+/// losses are summed, matching the convention of [LossGrad](crate::func::loss::LossGrad).
+pub trait Objective<A> {
+    fn objective(self) -> A;
+}
+
+/// Replaces the element at `index` of the `tensor`-th parameter visited by the model.
+fn replace<A, M, G>(model: &mut M, grads: &G, tensor: usize, index: &IxDyn, value: A)
+where
+    A: Copy,
+    M: Optimizable<A, G>,
+    G: ?Sized,
+{
+    let mut visited = 0;
+    model.zip_grad(grads, &mut |mut params, _| {
+        if visited == tensor {
+            if let Some(x) = params.get_mut(index.clone()) {
+                *x = value;
+            }
+        }
+        visited += 1;
+    });
+}
+
+/// Pairs the analytic gradients visited by the model with the given numeric ones.
+fn compare<A, M, G>(model: &mut M, grads: &G, numeric: Vec<ArrayD<A>>) -> Vec<GradError<A>>
+where
+    A: Float,
+    M: Optimizable<A, G>,
+    G: ?Sized,
+{
+    let mut analytic = Vec::new();
+    model.zip_grad(grads, &mut |_, grad| analytic.push(grad.to_owned()));
+    analytic
+        .into_iter()
+        .zip(numeric)
+        .map(|(analytic, numeric)| GradError::new(analytic, numeric))
+        .collect()
+}
+
+/*
+ ************* Implementations *************
+*/
+impl<A> GradCheck<A>
+where
+    A: Float,
+{
+    pub fn new(eps: A) -> Self {
+        Self { eps }
+    }
+
+    /// Sets the step of the central differences.
+    pub fn eps(mut self, eps: A) -> Self {
+        self.eps = eps;
+        self
+    }
+    /// Approximates the gradient of the objective `f` with respect to each of the parameters
+    /// visited by the model; the parameters are restored once perturbed.
+    pub fn numeric<M, G, F>(&self, model: &mut M, grads: &G, mut f: F) -> Vec<ArrayD<A>>
+    where
+        M: Optimizable<A, G>,
+        G: ?Sized,
+        F: FnMut(&M) -> A,
+    {
+        match self.try_numeric(model, grads, |m| Ok::<A, Infallible>(f(m))) {
+            Ok(res) => res,
+            Err(err) => match err {},
+        }
+    }
+    /// [Approximates](GradCheck::numeric) the gradients of a fallible objective, stopping at
+    /// the first error; the parameters are restored regardless.
+    pub fn try_numeric<M, G, F, E>(
+        &self,
+        model: &mut M,
+        grads: &G,
+        mut f: F,
+    ) -> Result<Vec<ArrayD<A>>, E>
+    where
+        M: Optimizable<A, G>,
+        G: ?Sized,
+        F: FnMut(&M) -> Result<A, E>,
+    {
+        // read each parameter once, such that every perturbation is relative to its value
+        let mut params = Vec::new();
+        model.zip_grad(grads, &mut |p, _| params.push(p.to_owned()));
+        let two = A::one() + A::one();
+        let mut res = Vec::with_capacity(params.len());
+        for (tensor, values) in params.into_iter().enumerate() {
+            let mut grad = ArrayD::zeros(values.raw_dim());
+            for (index, &x) in values.indexed_iter() {
+                replace(model, grads, tensor, &index, x + self.eps);
+                let fp = f(model);
+                replace(model, grads, tensor, &index, x - self.eps);
+                let fm = f(model);
+                replace(model, grads, tensor, &index, x);
+                grad[index] = (fp? - fm?) / (two * self.eps);
+            }
+            res.push(grad);
+        }
+        Ok(res)
+    }
+    /// Compares the analytic gradients, `grads`, of the objective `f` against those
+    /// approximated numerically, reporting the error of each parameter tensor in the order
+    /// they are visited by the model.
+    pub fn check<M, G, F>(&self, model: &mut M, grads: &G, f: F) -> Vec<GradError<A>>
+    where
+        M: Optimizable<A, G>,
+        G: ?Sized,
+        F: FnMut(&M) -> A,
+    {
+        let numeric = self.numeric(model, grads, f);
+        compare(model, grads, numeric)
+    }
+    /// [Checks](GradCheck::check) the gradients of a fallible objective, stopping at the
+    /// first error.
+    pub fn try_check<M, G, F, E>(
+        &self,
+        model: &mut M,
+        grads: &G,
+        f: F,
+    ) -> Result<Vec<GradError<A>>, E>
+    where
+        M: Optimizable<A, G>,
+        G: ?Sized,
+        F: FnMut(&M) -> Result<A, E>,
+    {
+        let numeric = self.try_numeric(model, grads, f)?;
+        Ok(compare(model, grads, numeric))
+    }
+    /// [Checks](GradCheck::check) the gradients of the given loss of the model's prediction
+    /// for `input` with respect to `target`.
+    pub fn check_loss<M, G, X, Y, L>(
+        &self,
+        model: &mut M,
+        grads: &G,
+        input: &X,
+        target: &Y,
+        loss: &L,
+    ) -> Vec<GradError<A>>
+    where
+        M: Forward<X> + Optimizable<A, G>,
+        G: ?Sized,
+        L: Loss<M::Output, Y>,
+        L::Output: Objective<A>,
+    {
+        self.check(model, grads, |m| {
+            loss.loss(&m.forward(input), target).objective()
+        })
+    }
+    /// [Checks](GradCheck::check_loss) the gradients of the given loss of a [Predict]ion,
+    /// returning the first error raised by the model rather than panicking.
+    pub fn check_predict<M, G, X, Y, L>(
+        &self,
+        model: &mut M,
+        grads: &G,
+        input: &X,
+        target: &Y,
+        loss: &L,
+    ) -> Result<Vec<GradError<A>>, PredictError>
+    where
+        M: Predict<X> + Optimizable<A, G>,
+        G: ?Sized,
+        L: Loss<M::Output, Y>,
+        L::Output: Objective<A>,
+    {
+        self.try_check(model, grads, |m| {
+            let pred = m.predict(input)?;
+            Ok(loss.loss(&pred, target).objective())
+        })
+    }
+}
+
+impl<A> Default for GradCheck<A>
+where
+    A: Float,
+{
+    fn default() -> Self {
+        Self::new(A::from(1e-6).unwrap())
+    }
+}
+
+impl<A> GradError<A>
+where
+    A: Float,
+{
+    pub fn new(analytic: ArrayD<A>, numeric: ArrayD<A>) -> Self {
+        let (mut max_abs, mut max_rel) = (A::zero(), A::zero());
+        Zip::from(&analytic).and(&numeric).for_each(|&a, &n| {
+            let err = (a - n).abs();
+            let scale = a.abs().max(n.abs());
+            max_abs = max_abs.max(err);
+            if scale > A::zero() {
+                max_rel = max_rel.max(err / scale);
+            }
+        });
+        Self {
+            analytic,
+            numeric,
+            max_abs,
+            max_rel,
+        }
+    }
+    /// Returns true if every element satisfies either the absolute or the relative tolerance.
+    pub fn is_close(&self, abs: A, rel: A) -> bool {
+        Zip::from(&self.analytic).and(&self.numeric).all(|&a, &n| {
+            let err = (a - n).abs();
+            err <= abs || err <= rel * a.abs().max(n.abs())
+        })
+    }
+}
+
+#[cfg(feature = "approx")]
+impl<A> GradError<A>
+where
+    A: approx::RelativeEq<Epsilon = A> + Float,
+{
+    /// Compares the gradients using [approx::AbsDiffEq].
+    pub fn abs_diff_eq(&self, epsilon: A) -> bool {
+        use approx::AbsDiffEq;
+        self.analytic.abs_diff_eq(&self.numeric, epsilon)
+    }
+    /// Compares the gradients using [approx::RelativeEq].
+    pub fn relative_eq(&self, epsilon: A, max_relative: A) -> bool {
+        use approx::RelativeEq;
+        self.analytic
+            .relative_eq(&self.numeric, epsilon, max_relative)
+    }
+}
+
+macro_rules! impl_objective {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl Objective<$t> for $t {
+                fn objective(self) -> $t {
+                    self
+                }
+            }
+
+            impl Objective<$t> for Option<$t> {
+                /// An undefined loss, e.g. the mean of no values, is treated as NaN.
+                fn objective(self) -> $t {
+                    self.unwrap_or(<$t>::NAN)
+                }
+            }
+        )*
+    };
+}
+
+impl_objective!(f32, f64);
+
+impl<A, S, D> Objective<A> for ArrayBase<S, D>
+where
+    A: Float,
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    fn objective(self) -> A {
+        self.sum()
+    }
+}
@@ -0,0 +1,47 @@
+/*
+    Appellation: grad <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::Var;
+use crate::rust::Vec;
+use nd::prelude::*;
+use num::traits::Zero;
+
+/// [Gradients] stores the result of backpropagating through a [Tape](super::Tape), mapping
+/// each recorded variable onto its gradient.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Gradients<A = f64> {
+    store: Vec<Option<ArrayD<A>>>,
+}
+
+impl<A> Gradients<A> {
+    pub(crate) fn from_vec(store: Vec<Option<ArrayD<A>>>) -> Self {
+        Self { store }
+    }
+    /// Returns the gradient of the variable with the given index, if any.
+    pub fn get(&self, id: usize) -> Option<&ArrayD<A>> {
+        self.store.get(id).and_then(|g| g.as_ref())
+    }
+    /// Returns the gradient with respect to the given variable, if it contributed to the result.
+    pub fn wrt(&self, var: &Var<'_, A>) -> Option<&ArrayD<A>> {
+        self.get(var.id())
+    }
+    /// Returns the gradient with respect to the given variable converted into the dimension `D`;
+    /// variables that did not contribute to the result receive a gradient of zeros.
+    pub fn wrt_as<D>(&self, var: &Var<'_, A>) -> Array<A, D>
+    where
+        A: Clone + Zero,
+        D: Dimension,
+    {
+        let grad = match self.wrt(var) {
+            Some(g) => g.clone(),
+            None => ArrayD::zeros(var.shape()),
+        };
+        grad.into_dimensionality::<D>()
+            .expect("the gradient does not match the requested dimension")
+    }
+    /// Removes and returns the gradient of the variable with the given index.
+    pub fn remove(&mut self, id: usize) -> Option<ArrayD<A>> {
+        self.store.get_mut(id).and_then(|g| g.take())
+    }
+}
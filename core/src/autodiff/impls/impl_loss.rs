@@ -0,0 +1,237 @@
+/*
+    Appellation: impl_loss <impls>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use crate::autodiff::{TraceLoss, Var};
+use crate::func::loss::*;
+use nd::prelude::*;
+use nd::{Data, ScalarOperand};
+use num::traits::Float;
+
+/// Returns the value of the variable in the given dimensionality.
+fn value<A, D>(var: &Var<'_, A>) -> Array<A, D>
+where
+    A: Clone,
+    D: Dimension,
+{
+    var.value()
+        .into_dimensionality::<D>()
+        .expect("the prediction has an unexpected number of dimensions")
+}
+
+/// Scales the unreduced losses, indexed by sample along the first axis, and their gradient
+/// by the weight of each sample before reducing them into a scalar variable; returns [None]
+/// if there is not a weight for each sample.
+fn weighted<'t, A>(
+    pred: Var<'t, A>,
+    losses: ArrayD<A>,
+    mut grad: ArrayD<A>,
+    weights: ArrayView1<'_, A>,
+    reduction: Reduction,
+) -> Option<Var<'t, A>>
+where
+    A: Float + ScalarOperand,
+{
+    if losses.ndim() == 0 || weights.len() != losses.len_of(Axis(0)) {
+        return None;
+    }
+    let per_sample = A::from(losses.len() / losses.len_of(Axis(0)).max(1)).unwrap();
+    let scale = reduction.grad_scale(weights.sum() * per_sample);
+    let loss = losses
+        .outer_iter()
+        .zip(weights)
+        .fold(A::zero(), |acc, (l, &w)| acc + l.sum() * w);
+    for (mut g, &w) in grad.outer_iter_mut().zip(weights) {
+        g.mapv_inplace(|x| x * w * scale);
+    }
+    Some(pred.reduce_with(loss * scale, grad))
+}
+
+/// Computes the weighted mean of the given losses, normalized by the total weight; returns
+/// [None] if there is not a weight for each index along the first axis of the losses.
+fn weighted_mean<'t, A>(losses: Var<'t, A>, weights: ArrayView1<'_, A>) -> Option<Var<'t, A>>
+where
+    A: Float + ScalarOperand,
+{
+    if losses.shape().slice().first() != Some(&weights.len()) {
+        return None;
+    }
+    let per_sample = losses.shape().size() / weights.len().max(1);
+    let norm = weights.sum() * A::from(per_sample).unwrap();
+    // align the weights with the first axis of the losses
+    let mut weights = weights.into_dyn();
+    while weights.ndim() < losses.ndim() {
+        let axis = Axis(weights.ndim());
+        weights = weights.insert_axis(axis);
+    }
+    let loss = (losses * losses.tape().var(&weights)).sum();
+    Some(loss.scale(norm.recip()))
+}
+
+/// Implements [TraceLoss] for losses implementing both [Loss] and [LossGrad], recording the
+/// loss as a single node whose gradient is given by the latter; `$unweighted` yields a copy
+/// of the loss without any per-sample weights. The `entropy` arms cover the losses whose
+/// reduced value and gradient are optional, i.e. the cross-entropy family, whose unreduced
+/// values are given by their `losses`.
+macro_rules! trace_loss {
+    (@impl $D:ty, |$this:ident| $unweighted:expr) => {
+        fn trace_loss<'t>(
+            &self,
+            pred: Var<'t, A>,
+            target: &ArrayBase<S, $D>,
+        ) -> Option<Var<'t, A>> {
+            let p = value::<A, $D>(&pred);
+            let loss = Loss::loss(self, &p, target)?.sum();
+            let grad = LossGrad::loss_grad(self, &p, target)?;
+            Some(pred.reduce_with(loss, grad.into_dyn()))
+        }
+
+        fn trace_weighted<'t>(
+            &self,
+            pred: Var<'t, A>,
+            target: &ArrayBase<S, $D>,
+            weights: ArrayView1<'_, A>,
+        ) -> Option<Var<'t, A>> {
+            let p = value::<A, $D>(&pred);
+            let $this = self;
+            let unreduced = $unweighted.with_reduction(Reduction::None);
+            let losses = Loss::loss(&unreduced, &p, target)?;
+            let grad = LossGrad::loss_grad(&unreduced, &p, target)?;
+            weighted(pred, losses, grad.into_dyn(), weights, self.reduction)
+        }
+    };
+    (@entropy $D:ty) => {
+        fn trace_loss<'t>(
+            &self,
+            pred: Var<'t, A>,
+            target: &ArrayBase<S, $D>,
+        ) -> Option<Var<'t, A>> {
+            let p = value::<A, $D>(&pred);
+            let loss = Loss::loss(self, &p, target)?.sum();
+            let grad = LossGrad::loss_grad(self, &p, target)?;
+            Some(pred.reduce_with(loss, grad.into_dyn()))
+        }
+
+        fn trace_weighted<'t>(
+            &self,
+            pred: Var<'t, A>,
+            target: &ArrayBase<S, $D>,
+            weights: ArrayView1<'_, A>,
+        ) -> Option<Var<'t, A>> {
+            let p = value::<A, $D>(&pred);
+            let unreduced = self.clone().with_reduction(Reduction::None);
+            let losses = unreduced.losses(&p, target)?;
+            let grad = LossGrad::loss_grad(&unreduced, &p, target)?;
+            weighted(pred, losses.into_dyn(), grad.into_dyn(), weights, self.reduction)
+        }
+    };
+    (elementwise |$this:ident| $unweighted:expr => $($name:ident),* $(,)?) => {
+        $(
+            impl<A, S, D> TraceLoss<A, ArrayBase<S, D>> for $name<A>
+            where
+                A: Float + ScalarOperand,
+                D: Dimension,
+                S: Data<Elem = A>,
+            {
+                trace_loss!(@impl D, |$this| $unweighted);
+            }
+        )*
+    };
+    (categorical |$this:ident| $unweighted:expr => $($name:ident),* $(,)?) => {
+        $(
+            impl<A, S> TraceLoss<A, ArrayBase<S, Ix2>> for $name<A>
+            where
+                A: Float + ScalarOperand,
+                S: Data<Elem = A>,
+            {
+                trace_loss!(@impl Ix2, |$this| $unweighted);
+            }
+        )*
+    };
+    (elementwise entropy => $($name:ident),* $(,)?) => {
+        $(
+            impl<A, S, D> TraceLoss<A, ArrayBase<S, D>> for $name<A>
+            where
+                A: Float + ScalarOperand,
+                D: Dimension,
+                S: Data<Elem = A>,
+            {
+                trace_loss!(@entropy D);
+            }
+        )*
+    };
+    (categorical entropy => $($name:ident),* $(,)?) => {
+        $(
+            impl<A, S> TraceLoss<A, ArrayBase<S, Ix2>> for $name<A>
+            where
+                A: Float + ScalarOperand,
+                S: Data<Elem = A>,
+            {
+                trace_loss!(@entropy Ix2);
+            }
+        )*
+    };
+}
+
+trace_loss! {
+    elementwise |this| Self { weights: None, ..this.clone() } =>
+        Huber, SmoothL1, LogCosh, Quantile, MSLE
+}
+
+trace_loss! {
+    elementwise entropy => BinaryCrossEntropy, BinaryCrossEntropyWithLogits
+}
+
+trace_loss! {
+    categorical entropy => CrossEntropy, CrossEntropyWithLogits, NLLLoss, FocalLoss
+}
+
+trace_loss! {
+    categorical |this| this.clone() => Hinge, SquaredHinge, KLDivergence, JSDivergence
+}
+
+impl<A, S, D> TraceLoss<A, ArrayBase<S, D>> for MAE
+where
+    A: Float + ScalarOperand,
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    fn trace_loss<'t>(&self, pred: Var<'t, A>, target: &ArrayBase<S, D>) -> Option<Var<'t, A>> {
+        let target = pred.tape().var(target);
+        Some((pred - target).abs().mean())
+    }
+
+    fn trace_weighted<'t>(
+        &self,
+        pred: Var<'t, A>,
+        target: &ArrayBase<S, D>,
+        weights: ArrayView1<'_, A>,
+    ) -> Option<Var<'t, A>> {
+        let target = pred.tape().var(target);
+        weighted_mean((pred - target).abs(), weights)
+    }
+}
+
+impl<A, S, D> TraceLoss<A, ArrayBase<S, D>> for MSE
+where
+    A: Float + ScalarOperand,
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    fn trace_loss<'t>(&self, pred: Var<'t, A>, target: &ArrayBase<S, D>) -> Option<Var<'t, A>> {
+        let target = pred.tape().var(target);
+        let diff = pred - target;
+        Some((diff * diff).mean())
+    }
+
+    fn trace_weighted<'t>(
+        &self,
+        pred: Var<'t, A>,
+        target: &ArrayBase<S, D>,
+        weights: ArrayView1<'_, A>,
+    ) -> Option<Var<'t, A>> {
+        let target = pred.tape().var(target);
+        let diff = pred - target;
+        weighted_mean(diff * diff, weights)
+    }
+}
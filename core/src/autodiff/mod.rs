@@ -0,0 +1,74 @@
+/*
+    Appellation: autodiff <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+//! # Automatic Differentiation
+//!
+//! This module implements reverse-mode automatic differentiation over [ndarray](https://docs.rs/ndarray).
+//! Operations are recorded onto a [Tape] as they are evaluated; calling [backward](crate::Backward::backward)
+//! on the resulting [Var] walks the tape in reverse, accumulating the gradient of every recorded
+//! node into a [Gradients] store.
+//!
+//! Models participate by implementing the [Trace] trait, which records their forward pass onto
+//! a tape and later reassembles the gradients of their parameters into their native shape.
+pub use self::check::{GradCheck, GradError, Objective};
+pub use self::{grad::Gradients, tape::Tape, var::Var};
+
+use nd::ArrayView1;
+
+pub(crate) mod grad;
+pub(crate) mod tape;
+pub(crate) mod var;
+
+pub mod check;
+
+mod impls {
+    mod impl_loss;
+}
+
+pub(crate) mod prelude {
+    pub use super::check::{GradCheck, GradError};
+    pub use super::grad::Gradients;
+    pub use super::tape::Tape;
+    pub use super::var::Var;
+    pub use super::{Trace, TraceLoss};
+}
+
+/// [Trace] describes a model capable of recording its forward pass onto a [Tape].
+///
+/// Implementors register their learnable parameters as leaves of the tape, returning
+/// handles to them (`Vars`) alongside the output; after backpropagating, [Trace::grad]
+/// gathers the gradients of those leaves into the model's own parameter layout.
+pub trait Trace<'t, A, X>
+where
+    A: 't,
+{
+    type Output;
+    type Vars;
+    type Grad;
+
+    fn trace(&self, tape: &'t Tape<A>, input: X) -> (Self::Output, Self::Vars);
+
+    fn grad(&self, vars: &Self::Vars, grads: &Gradients<A>) -> Self::Grad;
+}
+
+/// [TraceLoss] describes a loss function capable of recording itself onto the tape of the
+/// prediction, reducing the prediction and the target to a scalar variable.
+///
+/// Every loss of the [loss](crate::func::loss) module is supported; the categorical losses
+/// expect two-dimensional predictions. Losses left unreduced are recorded as the sum of their
+/// values. Either method returns [None] whenever the loss itself would, e.g. when the weights
+/// of the loss do not match the prediction.
+pub trait TraceLoss<A, T> {
+    fn trace_loss<'t>(&self, pred: Var<'t, A>, target: &T) -> Option<Var<'t, A>>;
+    /// Records the loss with the losses of each sample, i.e. each index along the first axis of
+    /// the prediction, scaled by the corresponding weight; the mean is normalized by the total
+    /// weight. Any per-sample weights configured on the loss are replaced by those given, which
+    /// must hold a weight for each sample.
+    fn trace_weighted<'t>(
+        &self,
+        pred: Var<'t, A>,
+        target: &T,
+        weights: ArrayView1<'_, A>,
+    ) -> Option<Var<'t, A>>;
+}
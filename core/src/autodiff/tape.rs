@@ -0,0 +1,237 @@
+/*
+    Appellation: tape <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::{Gradients, Var};
+use crate::rust::Vec;
+use core::cell::RefCell;
+use nd::prelude::*;
+use nd::{Data, ScalarOperand};
+use num::traits::Float;
+
+/// The operations capable of being recorded onto a [Tape]; each variant stores the
+/// indices of its operands along with anything required to compute the local gradient.
+pub(crate) enum Op<A> {
+    Leaf,
+    Add(usize, usize),
+    Sub(usize, usize),
+    Mul(usize, usize),
+    Div(usize, usize),
+    Neg(usize),
+    Scale(usize, A),
+    Dot(usize, usize),
+    Transpose(usize),
+    /// An element-wise map storing the derivative evaluated at each element
+    Map(usize, ArrayD<A>),
+    Sum(usize),
+    Mean(usize),
+    SumAxis(usize),
+    MeanAxis(usize, Axis),
+    /// Softmax along the given axis; the local gradient is derived from the output
+    Softmax(usize, Axis),
+    /// Log-softmax along the given axis; the local gradient is derived from the output
+    LogSoftmax(usize, Axis),
+    /// A scalar evaluated outside of the tape, storing its gradient with respect to the operand
+    Reduce(usize, ArrayD<A>),
+}
+
+pub(crate) struct Node<A> {
+    pub(crate) op: Op<A>,
+    pub(crate) value: ArrayD<A>,
+}
+
+/// A [Tape] records the operations performed on its variables, enabling the gradient of
+/// any recorded node to be computed with respect to every node preceding it.
+pub struct Tape<A = f64> {
+    pub(crate) nodes: RefCell<Vec<Node<A>>>,
+}
+
+impl<A> Tape<A> {
+    pub fn new() -> Self {
+        Self {
+            nodes: RefCell::new(Vec::new()),
+        }
+    }
+    /// Clears every recorded node, invalidating any outstanding variables.
+    pub fn clear(&mut self) {
+        self.nodes.get_mut().clear();
+    }
+    /// Returns true if nothing has been recorded onto the tape.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.borrow().is_empty()
+    }
+    /// Returns the number of nodes recorded onto the tape.
+    pub fn len(&self) -> usize {
+        self.nodes.borrow().len()
+    }
+    /// Registers the given array as a leaf of the tape.
+    pub fn var<S, D>(&self, value: &ArrayBase<S, D>) -> Var<'_, A>
+    where
+        A: Clone,
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        self.push(Op::Leaf, value.to_owned().into_dyn())
+    }
+
+    pub(crate) fn push(&self, op: Op<A>, value: ArrayD<A>) -> Var<'_, A> {
+        let mut nodes = self.nodes.borrow_mut();
+        nodes.push(Node { op, value });
+        Var::new(self, nodes.len() - 1)
+    }
+}
+
+impl<A> Tape<A>
+where
+    A: Float + ScalarOperand,
+{
+    /// Backpropagates the `seed` from the node at `root`, returning the gradient of every
+    /// node reachable from it.
+    pub(crate) fn backward(&self, root: usize, seed: ArrayD<A>) -> Gradients<A> {
+        let nodes = self.nodes.borrow();
+        let mut grads: Vec<Option<ArrayD<A>>> = (0..nodes.len()).map(|_| None).collect();
+        grads[root] = Some(seed);
+        // nodes are always recorded after their operands; walking backwards is a topological order
+        for idx in (0..=root).rev() {
+            let grad = match grads[idx].take() {
+                Some(g) => g,
+                None => continue,
+            };
+            let node = &nodes[idx];
+            let value = |i: usize| &nodes[i].value;
+            match &node.op {
+                Op::Leaf => {}
+                Op::Add(a, b) => {
+                    accumulate(&mut grads, *a, unbroadcast(&grad, value(*a).shape()));
+                    accumulate(&mut grads, *b, unbroadcast(&grad, value(*b).shape()));
+                }
+                Op::Sub(a, b) => {
+                    accumulate(&mut grads, *a, unbroadcast(&grad, value(*a).shape()));
+                    let gb = grad.mapv(|g| -g);
+                    accumulate(&mut grads, *b, unbroadcast(&gb, value(*b).shape()));
+                }
+                Op::Mul(a, b) => {
+                    let ga = &grad * value(*b);
+                    let gb = &grad * value(*a);
+                    accumulate(&mut grads, *a, unbroadcast(&ga, value(*a).shape()));
+                    accumulate(&mut grads, *b, unbroadcast(&gb, value(*b).shape()));
+                }
+                Op::Div(a, b) => {
+                    let (x, y) = (value(*a), value(*b));
+                    let ga = &grad / y;
+                    let gb = -(&grad * x) / (y * y);
+                    accumulate(&mut grads, *a, unbroadcast(&ga, x.shape()));
+                    accumulate(&mut grads, *b, unbroadcast(&gb, y.shape()));
+                }
+                Op::Neg(a) => accumulate(&mut grads, *a, grad.mapv(|g| -g)),
+                Op::Scale(a, k) => accumulate(&mut grads, *a, &grad * *k),
+                Op::Dot(a, b) => {
+                    let (x, y) = (value(*a), value(*b));
+                    let g = as_matrix(&grad, x.ndim() == 1, y.ndim() == 1);
+                    let (xm, ym) = (lhs_matrix(x), rhs_matrix(y));
+                    let ga = g.dot(&ym.t()).into_shape(x.raw_dim()).unwrap();
+                    let gb = xm.t().dot(&g).into_shape(y.raw_dim()).unwrap();
+                    accumulate(&mut grads, *a, ga);
+                    accumulate(&mut grads, *b, gb);
+                }
+                Op::Transpose(a) => accumulate(&mut grads, *a, grad.t().to_owned()),
+                Op::Map(a, df) => accumulate(&mut grads, *a, &grad * df),
+                Op::Sum(a) => {
+                    let g = grad.first().copied().unwrap_or_else(A::zero);
+                    accumulate(&mut grads, *a, ArrayD::from_elem(value(*a).raw_dim(), g));
+                }
+                Op::Mean(a) => {
+                    let x = value(*a);
+                    let n = A::from(x.len()).unwrap();
+                    let g = grad.first().copied().unwrap_or_else(A::zero) / n;
+                    accumulate(&mut grads, *a, ArrayD::from_elem(x.raw_dim(), g));
+                }
+                Op::SumAxis(a) => {
+                    let g = grad.broadcast(value(*a).raw_dim()).unwrap().to_owned();
+                    accumulate(&mut grads, *a, g);
+                }
+                Op::MeanAxis(a, axis) => {
+                    let x = value(*a);
+                    let n = A::from(x.len_of(*axis)).unwrap();
+                    let g = grad.broadcast(x.raw_dim()).unwrap().mapv(|g| g / n);
+                    accumulate(&mut grads, *a, g);
+                }
+                Op::Softmax(a, axis) => {
+                    let y = &node.value;
+                    let dot = (&grad * y).sum_axis(*axis).insert_axis(*axis);
+                    accumulate(&mut grads, *a, y * &(&grad - &dot));
+                }
+                Op::LogSoftmax(a, axis) => {
+                    let y = node.value.mapv(A::exp);
+                    let sum = grad.sum_axis(*axis).insert_axis(*axis);
+                    accumulate(&mut grads, *a, &grad - &(y * &sum));
+                }
+                Op::Reduce(a, df) => {
+                    let g = grad.first().copied().unwrap_or_else(A::zero);
+                    accumulate(&mut grads, *a, df * g);
+                }
+            }
+            grads[idx] = Some(grad);
+        }
+        Gradients::from_vec(grads)
+    }
+}
+
+impl<A> Default for Tape<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn accumulate<A>(grads: &mut [Option<ArrayD<A>>], idx: usize, grad: ArrayD<A>)
+where
+    A: Float + ScalarOperand,
+{
+    match grads[idx].as_mut() {
+        Some(acc) => *acc = &*acc + &grad,
+        None => grads[idx] = Some(grad),
+    }
+}
+
+/// Reduces a broadcasted gradient back down to the given shape by summing over the
+/// broadcasted axes.
+pub(crate) fn unbroadcast<A>(grad: &ArrayD<A>, shape: &[usize]) -> ArrayD<A>
+where
+    A: Float + ScalarOperand,
+{
+    let mut res = grad.to_owned();
+    while res.ndim() > shape.len() {
+        res = res.sum_axis(Axis(0));
+    }
+    for (i, &dim) in shape.iter().enumerate() {
+        if dim == 1 && res.len_of(Axis(i)) != 1 {
+            res = res.sum_axis(Axis(i)).insert_axis(Axis(i));
+        }
+    }
+    res
+}
+
+fn lhs_matrix<A: Clone>(x: &ArrayD<A>) -> Array2<A> {
+    match x.ndim() {
+        1 => x.view().into_shape((1, x.len())).unwrap().to_owned(),
+        _ => x.view().into_dimensionality::<Ix2>().unwrap().to_owned(),
+    }
+}
+
+fn rhs_matrix<A: Clone>(x: &ArrayD<A>) -> Array2<A> {
+    match x.ndim() {
+        1 => x.view().into_shape((x.len(), 1)).unwrap().to_owned(),
+        _ => x.view().into_dimensionality::<Ix2>().unwrap().to_owned(),
+    }
+}
+
+/// Restores the gradient of a product to a matrix, accounting for any vector operands.
+fn as_matrix<A: Clone>(grad: &ArrayD<A>, lvec: bool, rvec: bool) -> Array2<A> {
+    let shape = match (lvec, rvec) {
+        (true, true) => (1, 1),
+        (true, false) => (1, grad.len()),
+        (false, true) => (grad.len(), 1),
+        (false, false) => return grad.view().into_dimensionality::<Ix2>().unwrap().to_owned(),
+    };
+    grad.view().into_shape(shape).unwrap().to_owned()
+}
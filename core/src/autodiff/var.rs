@@ -0,0 +1,352 @@
+/*
+    Appellation: var <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::tape::{Op, Tape};
+use super::Gradients;
+use crate::func::activate::{heavyside, relu, Activation};
+use crate::Backward;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+use nd::linalg::Dot;
+use nd::prelude::*;
+use nd::ScalarOperand;
+use num::traits::{Float, FloatConst};
+
+/// A [Var] is a handle to a node recorded onto a [Tape]; every operation performed on a
+/// variable is itself recorded, producing a new variable.
+pub struct Var<'t, A = f64> {
+    pub(crate) id: usize,
+    pub(crate) tape: &'t Tape<A>,
+}
+
+impl<'t, A> Var<'t, A> {
+    pub(crate) fn new(tape: &'t Tape<A>, id: usize) -> Self {
+        Self { id, tape }
+    }
+    /// Returns the index of the variable within its tape.
+    pub const fn id(&self) -> usize {
+        self.id
+    }
+    /// Returns a reference to the tape the variable was recorded onto.
+    pub const fn tape(&self) -> &'t Tape<A> {
+        self.tape
+    }
+    /// Returns the number of dimensions of the underlying value.
+    pub fn ndim(&self) -> usize {
+        self.tape.nodes.borrow()[self.id].value.ndim()
+    }
+    /// Returns the shape of the underlying value.
+    pub fn shape(&self) -> IxDyn {
+        self.tape.nodes.borrow()[self.id].value.raw_dim()
+    }
+    /// Returns a copy of the underlying value.
+    pub fn value(&self) -> ArrayD<A>
+    where
+        A: Clone,
+    {
+        self.tape.nodes.borrow()[self.id].value.clone()
+    }
+
+    fn unary<F>(&self, f: F) -> Self
+    where
+        F: FnOnce(&ArrayD<A>) -> (Op<A>, ArrayD<A>),
+    {
+        let (op, value) = f(&self.tape.nodes.borrow()[self.id].value);
+        self.tape.push(op, value)
+    }
+}
+
+impl<'t, A> Var<'t, A>
+where
+    A: Float + ScalarOperand,
+{
+    fn record(
+        &self,
+        rhs: &Self,
+        op: Op<A>,
+        f: impl FnOnce(&ArrayD<A>, &ArrayD<A>) -> ArrayD<A>,
+    ) -> Self {
+        let value = {
+            let nodes = self.tape.nodes.borrow();
+            f(&nodes[self.id].value, &nodes[rhs.id].value)
+        };
+        self.tape.push(op, value)
+    }
+    /// Computes the matrix product of two variables; both operands may be either one or two dimensional.
+    pub fn dot(&self, rhs: &Self) -> Self {
+        self.record(rhs, Op::Dot(self.id, rhs.id), |x, y| {
+            match (x.ndim(), y.ndim()) {
+                (1, 1) => {
+                    let x = x.view().into_dimensionality::<Ix1>().unwrap();
+                    let y = y.view().into_dimensionality::<Ix1>().unwrap();
+                    arr0(x.dot(&y)).into_dyn()
+                }
+                (1, 2) => {
+                    let x = x.view().into_dimensionality::<Ix1>().unwrap();
+                    let y = y.view().into_dimensionality::<Ix2>().unwrap();
+                    x.dot(&y).into_dyn()
+                }
+                (2, 1) => {
+                    let x = x.view().into_dimensionality::<Ix2>().unwrap();
+                    let y = y.view().into_dimensionality::<Ix1>().unwrap();
+                    x.dot(&y).into_dyn()
+                }
+                _ => {
+                    let x = x.view().into_dimensionality::<Ix2>().unwrap();
+                    let y = y.view().into_dimensionality::<Ix2>().unwrap();
+                    Dot::dot(&x, &y).into_dyn()
+                }
+            }
+        })
+    }
+    /// Reverses the axes of the variable; for matrices this is the transpose.
+    pub fn t(&self) -> Self {
+        self.unary(|x| (Op::Transpose(self.id), x.t().to_owned()))
+    }
+    /// Multiplies every element by the scalar `k`.
+    pub fn scale(&self, k: A) -> Self {
+        self.unary(|x| (Op::Scale(self.id, k), x * k))
+    }
+    /// Adds the scalar `k` to every element.
+    pub fn add_scalar(&self, k: A) -> Self {
+        self.map(|x| x + k, |_| A::one())
+    }
+    /// Applies the element-wise function `f`, where `df` is its derivative.
+    pub fn map<F, G>(&self, f: F, df: G) -> Self
+    where
+        F: Fn(A) -> A,
+        G: Fn(A) -> A,
+    {
+        self.unary(|x| (Op::Map(self.id, x.mapv(&df)), x.mapv(&f)))
+    }
+
+    /// Records a scalar evaluated outside of the tape, such as a loss, given its gradient with
+    /// respect to the variable.
+    pub(crate) fn reduce_with(&self, value: A, grad: ArrayD<A>) -> Self {
+        self.unary(|_| (Op::Reduce(self.id, grad), arr0(value).into_dyn()))
+    }
+
+    /// Replaces every element where the `mask` is true with the given value; the masked
+    /// elements no longer depend on the input and so receive no gradient.
+    pub fn masked_fill<S, D>(&self, mask: &ArrayBase<S, D>, value: A) -> Self
+    where
+        D: Dimension,
+        S: nd::Data<Elem = bool>,
+    {
+        self.unary(|x| {
+            let mask = mask
+                .broadcast(x.raw_dim())
+                .expect("the mask must broadcast to the input");
+            let mut res = x.clone();
+            let mut df = ArrayD::ones(x.raw_dim());
+            nd::Zip::from(&mut res)
+                .and(&mut df)
+                .and(&mask)
+                .for_each(|r, d, &m| {
+                    if m {
+                        *r = value;
+                        *d = A::zero();
+                    }
+                });
+            (Op::Map(self.id, df), res)
+        })
+    }
+
+    pub fn abs(&self) -> Self {
+        self.map(A::abs, A::signum)
+    }
+
+    pub fn exp(&self) -> Self {
+        self.map(A::exp, A::exp)
+    }
+
+    pub fn ln(&self) -> Self {
+        self.map(A::ln, A::recip)
+    }
+
+    pub fn powi(&self, n: i32) -> Self {
+        let k = A::from(n).unwrap();
+        self.map(|x| x.powi(n), |x| k * x.powi(n - 1))
+    }
+
+    pub fn sqrt(&self) -> Self {
+        let two = A::one() + A::one();
+        self.map(A::sqrt, |x| (two * x.sqrt()).recip())
+    }
+
+    pub fn relu(&self) -> Self {
+        self.map(relu, heavyside)
+    }
+
+    pub fn sigmoid(&self) -> Self {
+        let sigmoid = |x: A| (A::one() + x.neg().exp()).recip();
+        self.map(sigmoid, |x| {
+            let s = sigmoid(x);
+            s * (A::one() - s)
+        })
+    }
+
+    pub fn tanh(&self) -> Self {
+        self.map(A::tanh, |x| A::one() - x.tanh().powi(2))
+    }
+    /// Computes the softmax along the given axis, subtracting the maximum for numerical stability.
+    pub fn softmax_axis(&self, axis: usize) -> Self {
+        let axis = Axis(axis);
+        self.unary(|x| {
+            let max = x
+                .fold_axis(axis, A::neg_infinity(), |&m, &xi| m.max(xi))
+                .insert_axis(axis);
+            let e = (x - &max).mapv(A::exp);
+            let sum = e.sum_axis(axis).insert_axis(axis);
+            (Op::Softmax(self.id, axis), e / sum)
+        })
+    }
+    /// Computes the log-softmax along the given axis, i.e. `x - logsumexp(x)`, whose gradient
+    /// is given by the softmax rather than the reciprocal of a (possibly vanishing) probability.
+    pub fn log_softmax_axis(&self, axis: usize) -> Self {
+        let axis = Axis(axis);
+        self.unary(|x| {
+            let max = x
+                .fold_axis(axis, A::neg_infinity(), |&m, &xi| m.max(xi))
+                .insert_axis(axis);
+            let shifted = x - &max;
+            let lse = shifted
+                .mapv(A::exp)
+                .sum_axis(axis)
+                .mapv(A::ln)
+                .insert_axis(axis);
+            (Op::LogSoftmax(self.id, axis), shifted - lse)
+        })
+    }
+    /// Applies the given [Activation]; the (log) softmax is taken along its configured axis,
+    /// or the last axis of the variable should none be given. The (log) softmax of a scalar is
+    /// constant, being one (zero) regardless of its value.
+    pub fn activate(&self, rho: Activation) -> Self
+    where
+        A: FloatConst,
+    {
+        match (rho, rho.axis(self.ndim())) {
+            (Activation::Linear, _) => *self,
+            (Activation::Softmax { .. }, Some(_)) if self.ndim() == 0 => {
+                self.map(|_| A::one(), |_| A::zero())
+            }
+            (Activation::LogSoftmax { .. }, Some(_)) if self.ndim() == 0 => {
+                self.map(|_| A::zero(), |_| A::zero())
+            }
+            (Activation::Sigmoid, _) => self.sigmoid(),
+            (Activation::Tanh, _) => self.tanh(),
+            (Activation::Softmax { .. }, Some(axis)) => self.softmax_axis(axis),
+            (Activation::LogSoftmax { .. }, Some(axis)) => self.log_softmax_axis(axis),
+            _ => self.map(|x| rho.eval(x), |x| rho.derivative(x)),
+        }
+    }
+    /// Reduces the variable to the sum of its elements.
+    pub fn sum(&self) -> Self {
+        self.unary(|x| (Op::Sum(self.id), arr0(x.sum()).into_dyn()))
+    }
+    /// Reduces the variable to the mean of its elements.
+    pub fn mean(&self) -> Self {
+        self.unary(|x| {
+            let n = A::from(x.len()).unwrap();
+            (Op::Mean(self.id), arr0(x.sum() / n).into_dyn())
+        })
+    }
+    /// Sums along the given axis; the axis is retained with length one so the result
+    /// broadcasts against the original variable.
+    pub fn sum_axis(&self, axis: usize) -> Self {
+        let axis = Axis(axis);
+        self.unary(|x| (Op::SumAxis(self.id), x.sum_axis(axis).insert_axis(axis)))
+    }
+    /// Averages along the given axis; the axis is retained with length one so the result
+    /// broadcasts against the original variable.
+    pub fn mean_axis(&self, axis: usize) -> Self {
+        let axis = Axis(axis);
+        self.unary(|x| {
+            let n = A::from(x.len_of(axis)).unwrap();
+            let mean = x.sum_axis(axis).insert_axis(axis).mapv(|s| s / n);
+            (Op::MeanAxis(self.id, axis), mean)
+        })
+    }
+    /// Backpropagates the given seed, the gradient of some downstream objective with
+    /// respect to this variable.
+    pub fn backward_with<S, D>(&self, seed: &ArrayBase<S, D>) -> Gradients<A>
+    where
+        D: Dimension,
+        S: nd::Data<Elem = A>,
+    {
+        self.tape.backward(self.id, seed.to_owned().into_dyn())
+    }
+}
+
+/*
+ ************* Implementations *************
+*/
+impl<'t, A> Backward for Var<'t, A>
+where
+    A: Float + ScalarOperand,
+{
+    type Output = Gradients<A>;
+
+    fn backward(&self) -> Self::Output {
+        let seed = ArrayD::ones(self.shape());
+        self.tape.backward(self.id, seed)
+    }
+}
+
+impl<'t, A> Clone for Var<'t, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'t, A> Copy for Var<'t, A> {}
+
+impl<'t, A> core::fmt::Debug for Var<'t, A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Var").field("id", &self.id).finish()
+    }
+}
+
+impl<'t, A> Neg for Var<'t, A>
+where
+    A: Float + ScalarOperand,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.unary(|x| (Op::Neg(self.id), x.mapv(|xi| -xi)))
+    }
+}
+
+macro_rules! impl_binary_op {
+    ($($trait:ident::$call:ident),* $(,)?) => {
+        $(
+            impl_binary_op!(@impl $trait::$call);
+        )*
+    };
+    (@impl $trait:ident::$call:ident) => {
+        impl<'t, A> $trait for Var<'t, A>
+        where
+            A: Float + ScalarOperand,
+        {
+            type Output = Self;
+
+            fn $call(self, rhs: Self) -> Self::Output {
+                self.record(&rhs, Op::$trait(self.id, rhs.id), |x, y| x.$call(y))
+            }
+        }
+
+        impl<'a, 't, A> $trait<&'a Var<'t, A>> for &'a Var<'t, A>
+        where
+            A: Float + ScalarOperand,
+        {
+            type Output = Var<'t, A>;
+
+            fn $call(self, rhs: &'a Var<'t, A>) -> Self::Output {
+                self.record(rhs, Op::$trait(self.id, rhs.id), |x, y| x.$call(y))
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add::add, Div::div, Mul::mul, Sub::sub);
@@ -0,0 +1,298 @@
+/*
+    Appellation: kinds <activate>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::utils::*;
+use super::Activate;
+use nd::prelude::*;
+use nd::{Data, ScalarOperand, Zip};
+use num::traits::{Float, FloatConst};
+use strum::{AsRefStr, Display, EnumCount, EnumIs, EnumIter, EnumString, VariantNames};
+
+/// [Activation] enumerates the activation functions that may be selected by name, enabling
+/// models to be configured from formats such as JSON or TOML.
+///
+/// The names are those of the corresponding functions, e.g. `"relu"` or `"leaky_relu"`.
+/// Parameterized functions use their conventional defaults: a slope of `0.01` for the
+/// [leaky relu](leaky_relu) and an `alpha` of one for the [elu]. Unless an axis is given, the
+/// [softmax](Activation::Softmax) is taken along the last axis of its input; as such, the
+/// (log) softmax may be deserialized from its name alone, e.g. `"softmax"`, or alongside its
+/// parameters, e.g. `{ "softmax": { "axis": 0 } }`.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumCount,
+    EnumIs,
+    EnumIter,
+    EnumString,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    VariantNames,
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "lowercase", try_from = "self::impl_serde::Repr")
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum Activation {
+    #[default]
+    Linear,
+    Heavyside,
+    ReLU,
+    Sigmoid,
+    Tanh,
+    Softmax {
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        axis: Option<usize>,
+    },
+    #[cfg_attr(feature = "serde", serde(rename = "log_softmax"))]
+    #[strum(serialize = "log_softmax")]
+    LogSoftmax {
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        axis: Option<usize>,
+    },
+    ELU,
+    GELU,
+    #[cfg_attr(feature = "serde", serde(rename = "gelu_tanh"))]
+    #[strum(serialize = "gelu_tanh")]
+    GELUTanh,
+    #[cfg_attr(feature = "serde", serde(rename = "hard_sigmoid"))]
+    #[strum(serialize = "hard_sigmoid")]
+    HardSigmoid,
+    #[cfg_attr(feature = "serde", serde(rename = "hard_swish"))]
+    #[strum(serialize = "hard_swish")]
+    HardSwish,
+    #[cfg_attr(feature = "serde", serde(rename = "leaky_relu"))]
+    #[strum(serialize = "leaky_relu")]
+    LeakyReLU,
+    Mish,
+    SELU,
+    SiLU,
+    Softplus,
+    Softsign,
+}
+
+impl Activation {
+    /// Returns the axis along which the (log) softmax of an input with `ndim` dimensions is
+    /// computed, or `None` if the activation is applied elementwise.
+    pub fn axis(&self, ndim: usize) -> Option<usize> {
+        match *self {
+            Activation::Softmax { axis } | Activation::LogSoftmax { axis } => {
+                Some(axis.unwrap_or(ndim.saturating_sub(1)))
+            }
+            _ => None,
+        }
+    }
+    /// The recommended gain of initializers, such as those of the Kaiming scheme, used ahead
+    /// of the activation; the gain compensates for its effect on the variance of the signal.
+    pub fn gain<A>(&self) -> A
+    where
+        A: Float,
+    {
+        let c = |x: f64| A::from(x).unwrap();
+        match self {
+            Activation::ReLU => c(2.0).sqrt(),
+            Activation::LeakyReLU => (c(2.0) / (c(1.0) + c(0.01).powi(2))).sqrt(),
+            Activation::SELU => c(0.75),
+            Activation::Tanh => c(5.0 / 3.0),
+            _ => A::one(),
+        }
+    }
+    /// Returns true if the activation is computed independently for each element.
+    pub fn is_elementwise(&self) -> bool {
+        !matches!(
+            self,
+            Activation::Softmax { .. } | Activation::LogSoftmax { .. }
+        )
+    }
+    /// Applies the activation to a single value; functions which are not computed
+    /// [elementwise](Activation::is_elementwise) leave the value unchanged.
+    pub fn eval<A>(&self, x: A) -> A
+    where
+        A: Float + FloatConst,
+    {
+        let c = |v: f64| A::from(v).unwrap();
+        match self {
+            Activation::Heavyside => heavyside(x),
+            Activation::ReLU => relu(x),
+            Activation::Sigmoid => sigmoid(x),
+            Activation::Tanh => Float::tanh(x),
+            Activation::ELU => elu(x, A::one()),
+            Activation::GELU => gelu(x),
+            Activation::GELUTanh => gelu_tanh(x),
+            Activation::HardSigmoid => hard_sigmoid(x),
+            Activation::HardSwish => hard_swish(x),
+            Activation::LeakyReLU => leaky_relu(x, c(0.01)),
+            Activation::Mish => mish(x),
+            Activation::SELU => selu(x),
+            Activation::SiLU => silu(x),
+            Activation::Softplus => softplus(x),
+            Activation::Softsign => softsign(x),
+            _ => x,
+        }
+    }
+    /// Computes the derivative of an [elementwise](Activation::is_elementwise) activation at
+    /// the given value; see [vjp](Activation::vjp) for the remainder.
+    pub fn derivative<A>(&self, x: A) -> A
+    where
+        A: Float + FloatConst,
+    {
+        let c = |v: f64| A::from(v).unwrap();
+        match self {
+            Activation::Heavyside => heavyside_derivative(x),
+            Activation::ReLU => relu_derivative(x),
+            Activation::Sigmoid => sigmoid_derivative(x),
+            Activation::Tanh => tanh_derivative(x),
+            Activation::ELU => elu_derivative(x, A::one()),
+            Activation::GELU => gelu_derivative(x),
+            Activation::GELUTanh => gelu_tanh_derivative(x),
+            Activation::HardSigmoid => hard_sigmoid_derivative(x),
+            Activation::HardSwish => hard_swish_derivative(x),
+            Activation::LeakyReLU => leaky_relu_derivative(x, c(0.01)),
+            Activation::Mish => mish_derivative(x),
+            Activation::SELU => selu_derivative(x),
+            Activation::SiLU => silu_derivative(x),
+            Activation::Softplus => softplus_derivative(x),
+            Activation::Softsign => softsign_derivative(x),
+            _ => linear_derivative(x),
+        }
+    }
+    /// Applies the activation to the given array.
+    pub fn apply<A, S, D>(&self, args: &ArrayBase<S, D>) -> Array<A, D>
+    where
+        A: Float + FloatConst + ScalarOperand,
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        match (self, self.axis(args.ndim())) {
+            (Activation::Linear, _) => args.to_owned(),
+            (_, Some(_)) if args.ndim() == 0 => match self {
+                Activation::Softmax { .. } => softmax(args),
+                _ => log_softmax(args),
+            },
+            (Activation::Softmax { .. }, Some(axis)) => softmax_axis(args, axis),
+            (Activation::LogSoftmax { .. }, Some(axis)) => log_softmax_axis(args, axis),
+            _ => args.mapv(|x| self.eval(x)),
+        }
+    }
+    /// Computes the vector-Jacobian product of the activation at `args`, i.e. the gradient
+    /// with respect to the input given the gradient `grad` with respect to the output.
+    pub fn vjp<A, S, T, D>(&self, args: &ArrayBase<S, D>, grad: &ArrayBase<T, D>) -> Array<A, D>
+    where
+        A: Float + FloatConst + ScalarOperand,
+        D: Dimension,
+        S: Data<Elem = A>,
+        T: Data<Elem = A>,
+    {
+        match (self, self.axis(args.ndim())) {
+            (Activation::Softmax { .. }, Some(axis)) if args.ndim() > 0 => {
+                vjp_axis(softmax_axis(args, axis), axis, grad)
+            }
+            (Activation::Softmax { .. }, _) => vjp(softmax(args), grad),
+            (Activation::LogSoftmax { .. }, Some(axis)) if args.ndim() > 0 => {
+                // the gradient of x - lse(x) is grad - softmax(x) * sum(grad)
+                let mut res = grad.to_owned();
+                let y = softmax_axis(args, axis);
+                Zip::from(res.lanes_mut(Axis(axis)))
+                    .and(y.lanes(Axis(axis)))
+                    .for_each(|mut g, y| {
+                        let sum = g.sum();
+                        g.zip_mut_with(&y, |g, &y| *g = *g - y * sum);
+                    });
+                res
+            }
+            (Activation::LogSoftmax { .. }, _) => {
+                let sum = grad.sum();
+                let y = softmax(args);
+                Zip::from(&y).and(grad).map_collect(|&y, &g| g - y * sum)
+            }
+            _ => Zip::from(args)
+                .and(grad)
+                .map_collect(|&x, &g| self.derivative(x) * g),
+        }
+    }
+}
+
+/*
+ ************* Implementations *************
+*/
+impl<A, S, D> Activate<ArrayBase<S, D>> for Activation
+where
+    A: Float + FloatConst + ScalarOperand,
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    type Output = Array<A, D>;
+
+    fn activate(&self, args: ArrayBase<S, D>) -> Self::Output {
+        self.apply(&args)
+    }
+}
+
+impl<'a, A, S, D> Activate<&'a ArrayBase<S, D>> for Activation
+where
+    A: Float + FloatConst + ScalarOperand,
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    type Output = Array<A, D>;
+
+    fn activate(&self, args: &'a ArrayBase<S, D>) -> Self::Output {
+        self.apply(args)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod impl_serde {
+    use super::Activation;
+    use crate::rust::String;
+    use core::str::FromStr;
+
+    /// The serialized forms of an [Activation]: either its name or, for those with
+    /// parameters, a map from the name to the parameters.
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    pub(super) enum Repr {
+        Name(String),
+        Params(Params),
+    }
+
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    pub(super) enum Params {
+        Softmax {
+            #[serde(default)]
+            axis: Option<usize>,
+        },
+        LogSoftmax {
+            #[serde(default)]
+            axis: Option<usize>,
+        },
+    }
+
+    impl TryFrom<Repr> for Activation {
+        type Error = strum::ParseError;
+
+        fn try_from(repr: Repr) -> Result<Self, Self::Error> {
+            match repr {
+                Repr::Name(name) => Activation::from_str(&name),
+                Repr::Params(Params::Softmax { axis }) => Ok(Activation::Softmax { axis }),
+                Repr::Params(Params::LogSoftmax { axis }) => Ok(Activation::LogSoftmax { axis }),
+            }
+        }
+    }
+}
@@ -4,18 +4,22 @@
 */
 #[doc(inline)]
 pub use self::utils::*;
-pub use self::{binary::*, linear::*, nonlinear::*};
+pub use self::{binary::*, kinds::Activation, linear::*, nonlinear::*, prelu::PReLU};
 
 pub(crate) mod utils;
 
 pub mod binary;
+pub mod kinds;
 pub mod linear;
 pub mod nonlinear;
+pub mod prelu;
 
 pub(crate) mod prelude {
     pub use super::binary::*;
+    pub use super::kinds::Activation;
     pub use super::linear::*;
     pub use super::nonlinear::*;
+    pub use super::prelu::PReLU;
     pub use super::utils::*;
     pub use super::{Activate, NdActivate};
 }
@@ -23,7 +27,7 @@ pub(crate) mod prelude {
 use nd::prelude::*;
 use nd::{Data, DataMut, RemoveAxis, ScalarOperand};
 use num::complex::ComplexFloat;
-use num::traits::{One, Zero};
+use num::traits::{Float, One, Zero};
 
 /// [Activate] designates a function or structure that can be used
 /// as an activation function for a neural network.
@@ -85,8 +89,7 @@ where
     where
         A: ComplexFloat,
     {
-        let exp = self.activate(ComplexFloat::exp);
-        &exp / exp.sum()
+        softmax(&self.linear())
     }
 
     fn softmax_axis(&self, axis: usize) -> Array<A, D>
@@ -94,9 +97,36 @@ where
         A: ComplexFloat,
         D: RemoveAxis,
     {
-        let exp = self.activate(ComplexFloat::exp);
-        let axis = Axis(axis);
-        &exp / &exp.sum_axis(axis)
+        softmax_axis(&self.linear(), axis)
+    }
+
+    fn log_softmax(&self) -> Array<A, D>
+    where
+        A: ComplexFloat,
+    {
+        log_softmax(&self.linear())
+    }
+
+    fn log_softmax_axis(&self, axis: usize) -> Array<A, D>
+    where
+        A: ComplexFloat,
+    {
+        log_softmax_axis(&self.linear(), axis)
+    }
+
+    fn logsumexp(&self) -> A
+    where
+        A: ComplexFloat,
+    {
+        logsumexp(&self.linear())
+    }
+
+    fn logsumexp_axis(&self, axis: usize) -> Array<A, D::Smaller>
+    where
+        A: ComplexFloat,
+        D: RemoveAxis,
+    {
+        logsumexp_axis(&self.linear(), axis)
     }
 
     fn tanh(&self) -> Array<A, D>
@@ -105,6 +135,143 @@ where
     {
         self.activate(tanh)
     }
+
+    fn elu(&self, alpha: A) -> Array<A, D>
+    where
+        A: Float,
+    {
+        self.activate(|x| elu(x, alpha))
+    }
+
+    fn gelu(&self) -> Array<A, D>
+    where
+        A: Float,
+    {
+        self.activate(gelu)
+    }
+
+    fn gelu_tanh(&self) -> Array<A, D>
+    where
+        A: ComplexFloat,
+    {
+        self.activate(gelu_tanh)
+    }
+
+    fn hard_sigmoid(&self) -> Array<A, D>
+    where
+        A: Float,
+    {
+        self.activate(hard_sigmoid)
+    }
+
+    fn hard_swish(&self) -> Array<A, D>
+    where
+        A: Float,
+    {
+        self.activate(hard_swish)
+    }
+
+    fn leaky_relu(&self, alpha: A) -> Array<A, D>
+    where
+        A: Float,
+    {
+        self.activate(|x| leaky_relu(x, alpha))
+    }
+
+    fn mish(&self) -> Array<A, D>
+    where
+        A: Float,
+    {
+        self.activate(mish)
+    }
+
+    fn selu(&self) -> Array<A, D>
+    where
+        A: Float,
+    {
+        self.activate(selu)
+    }
+
+    fn silu(&self) -> Array<A, D>
+    where
+        A: ComplexFloat,
+    {
+        self.activate(silu)
+    }
+
+    fn softplus(&self) -> Array<A, D>
+    where
+        A: Float,
+    {
+        self.activate(softplus)
+    }
+
+    fn softsign(&self) -> Array<A, D>
+    where
+        A: Float,
+    {
+        self.activate(softsign)
+    }
+
+    fn swish(&self, beta: A) -> Array<A, D>
+    where
+        A: ComplexFloat,
+    {
+        self.activate(|x| swish(x, beta))
+    }
+
+    fn heavyside_derivative(&self) -> Array<A, D>
+    where
+        A: Zero,
+    {
+        self.activate(heavyside_derivative)
+    }
+
+    fn linear_derivative(&self) -> Array<A, D>
+    where
+        A: One,
+    {
+        self.activate(linear_derivative)
+    }
+
+    fn relu_derivative(&self) -> Array<A, D>
+    where
+        A: One + PartialOrd + Zero,
+    {
+        self.activate(relu_derivative)
+    }
+
+    fn sigmoid_derivative(&self) -> Array<A, D>
+    where
+        A: ComplexFloat,
+    {
+        self.activate(sigmoid_derivative)
+    }
+
+    fn tanh_derivative(&self) -> Array<A, D>
+    where
+        A: ComplexFloat,
+    {
+        self.activate(tanh_derivative)
+    }
+    /// Computes the vector-Jacobian product of the [softmax](NdActivate::softmax), i.e. the
+    /// gradient with respect to the input given the gradient `grad` with respect to the output.
+    fn softmax_vjp<T>(&self, grad: &ArrayBase<T, D>) -> Array<A, D>
+    where
+        A: ComplexFloat,
+        T: Data<Elem = A>,
+    {
+        utils::vjp(self.softmax(), grad)
+    }
+    /// Computes the vector-Jacobian product of the [softmax_axis](NdActivate::softmax_axis).
+    fn softmax_axis_vjp<T>(&self, axis: usize, grad: &ArrayBase<T, D>) -> Array<A, D>
+    where
+        A: ComplexFloat,
+        D: RemoveAxis,
+        T: Data<Elem = A>,
+    {
+        utils::vjp_axis(self.softmax_axis(axis), axis, grad)
+    }
 }
 /*
  ************* Implementations *************
@@ -5,7 +5,7 @@
 use super::utils::*;
 use nd::*;
 use num::complex::{Complex, ComplexFloat};
-use num::traits::Zero;
+use num::traits::{Float, Zero};
 
 unary!(
     ReLU::relu(self),
@@ -14,6 +14,30 @@ unary!(
     Tanh::tanh(self),
 );
 
+unary!(
+    ELU::elu(self),
+    GELU::gelu(self),
+    GELUTanh::gelu_tanh(self),
+    HardSigmoid::hard_sigmoid(self),
+    HardSwish::hard_swish(self),
+    LeakyReLU::leaky_relu(self),
+    Mish::mish(self),
+    SELU::selu(self),
+    SiLU::silu(self),
+    Softplus::softplus(self),
+    Softsign::softsign(self),
+);
+
+/// The default `alpha` of the [ELU] trait.
+pub(crate) fn elu_default<T: Float>(args: T) -> T {
+    elu(args, T::one())
+}
+
+/// The default negative slope of the [LeakyReLU] trait.
+pub(crate) fn leaky_relu_default<T: Float>(args: T) -> T {
+    leaky_relu(args, T::from(0.01).unwrap())
+}
+
 pub trait SoftmaxAxis: Softmax {
     fn softmax_axis(self, axis: usize) -> Self::Output;
 }
@@ -89,6 +113,33 @@ macro_rules! nonlinear_rho {
             }
         }
     };
+    (fn $name:ident::$call:ident($f:expr) where A: $($rest:tt)* ) => {
+        impl<A, S, D> $name for ArrayBase<S, D>
+        where
+            D: Dimension,
+            S: Data<Elem = A>,
+            A: $($rest)*
+        {
+            type Output = Array<A, D>;
+
+            fn $call(self) -> Self::Output {
+                self.mapv($f)
+            }
+        }
+
+        impl<'a, A, S, D> $name for &'a ArrayBase<S, D>
+        where
+            D: Dimension,
+            S: Data<Elem = A>,
+            A: $($rest)*
+        {
+            type Output = Array<A, D>;
+
+            fn $call(self) -> Self::Output {
+                self.mapv($f)
+            }
+        }
+    };
     (alt $name:ident::$call:ident where A: $($rest:tt)* ) => {
         impl<A, S, D> $name for ArrayBase<S, D>
         where
@@ -149,10 +200,45 @@ nonlinear!(
     ]>(tanh),
 );
 
+nonlinear!(
+    ELU::elu<[f32, f64]>(elu_default),
+    GELU::gelu<[f32, f64]>(gelu),
+    GELUTanh::gelu_tanh<[
+        f32,
+        f64,
+        Complex<f32>,
+        Complex<f64>
+    ]>(gelu_tanh),
+    HardSigmoid::hard_sigmoid<[f32, f64]>(hard_sigmoid),
+    HardSwish::hard_swish<[f32, f64]>(hard_swish),
+    LeakyReLU::leaky_relu<[f32, f64]>(leaky_relu_default),
+    Mish::mish<[f32, f64]>(mish),
+    SELU::selu<[f32, f64]>(selu),
+    SiLU::silu<[
+        f32,
+        f64,
+        Complex<f32>,
+        Complex<f64>
+    ]>(silu),
+    Softplus::softplus<[f32, f64]>(softplus),
+    Softsign::softsign<[f32, f64]>(softsign),
+);
+
 nonlinear_rho!(ReLU::relu where A: Clone + PartialOrd + Zero);
 nonlinear_rho!(Sigmoid::sigmoid where A: ComplexFloat);
 nonlinear_rho!(alt Softmax::softmax where A: ComplexFloat + ScalarOperand);
 nonlinear_rho!(Tanh::tanh where A: ComplexFloat);
+nonlinear_rho!(fn ELU::elu(elu_default) where A: Float);
+nonlinear_rho!(GELU::gelu where A: Float);
+nonlinear_rho!(GELUTanh::gelu_tanh where A: ComplexFloat);
+nonlinear_rho!(HardSigmoid::hard_sigmoid where A: Float);
+nonlinear_rho!(HardSwish::hard_swish where A: Float);
+nonlinear_rho!(fn LeakyReLU::leaky_relu(leaky_relu_default) where A: Float);
+nonlinear_rho!(Mish::mish where A: Float);
+nonlinear_rho!(SELU::selu where A: Float);
+nonlinear_rho!(SiLU::silu where A: ComplexFloat);
+nonlinear_rho!(Softplus::softplus where A: Float);
+nonlinear_rho!(Softsign::softsign where A: Float);
 
 impl<A, S, D> SoftmaxAxis for ArrayBase<S, D>
 where
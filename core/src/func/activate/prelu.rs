@@ -0,0 +1,155 @@
+/*
+    Appellation: prelu <activate>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::{leaky_relu, Activate};
+use crate::nn::optim::Optimizable;
+use crate::PredictError;
+use nd::prelude::*;
+use nd::{ArrayViewD, ArrayViewMutD, Data};
+use num::traits::Float;
+
+/// [PReLU] is a [leaky relu](leaky_relu) whose negative slope is learned.
+///
+/// A slope is kept for each channel, i.e. each index along the last axis of the input, unless
+/// a single slope is [shared](PReLU::shared) by every channel. The slopes are updated by
+/// optimizers through the [Optimizable] implementation, given the gradient computed by
+/// [grad](PReLU::grad). Both fail with a [PredictError::ShapeMismatch] unless there is a
+/// single slope or one for each channel of the input.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct PReLU<A = f64> {
+    pub(crate) alpha: Array1<A>,
+}
+
+impl<A> PReLU<A>
+where
+    A: Float,
+{
+    pub fn new(alpha: Array1<A>) -> Self {
+        Self { alpha }
+    }
+    /// Creates a new instance with a slope of `alpha` for each of the given channels.
+    pub fn from_elem(channels: usize, alpha: A) -> Self {
+        Self::new(Array1::from_elem(channels, alpha))
+    }
+    /// Creates a new instance whose slope is shared by every channel.
+    pub fn shared(alpha: A) -> Self {
+        Self::from_elem(1, alpha)
+    }
+
+    pub const fn alpha(&self) -> &Array1<A> {
+        &self.alpha
+    }
+
+    pub fn alpha_mut(&mut self) -> &mut Array1<A> {
+        &mut self.alpha
+    }
+    /// Returns the index of the slope applied to the given channel.
+    fn index(&self, channel: usize) -> usize {
+        if self.alpha.len() == 1 {
+            0
+        } else {
+            channel
+        }
+    }
+    /// Returns the slope applied to the given channel.
+    fn slope(&self, channel: usize) -> A {
+        self.alpha[self.index(channel)]
+    }
+    /// Ensures there is either a single slope or one for each channel of the input.
+    fn check<S, D>(&self, args: &ArrayBase<S, D>) -> Result<(), PredictError>
+    where
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        let channels = args.shape().last().copied().unwrap_or(1);
+        if self.alpha.len() == 1 || self.alpha.len() == channels {
+            Ok(())
+        } else {
+            Err(PredictError::ShapeMismatch)
+        }
+    }
+
+    pub fn forward<S, D>(&self, args: &ArrayBase<S, D>) -> Result<Array<A, D>, PredictError>
+    where
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        self.check(args)?;
+        let mut res = args.to_owned();
+        let axis = Axis(res.ndim().saturating_sub(1));
+        for mut lane in res.lanes_mut(axis) {
+            for (c, x) in lane.iter_mut().enumerate() {
+                *x = leaky_relu(*x, self.slope(c));
+            }
+        }
+        Ok(res)
+    }
+    /// Computes the gradients with respect to the input and the slopes, given the gradient
+    /// `grad` with respect to the output.
+    pub fn grad<S, T, D>(
+        &self,
+        args: &ArrayBase<S, D>,
+        grad: &ArrayBase<T, D>,
+    ) -> Result<(Array<A, D>, Array1<A>), PredictError>
+    where
+        D: Dimension,
+        S: Data<Elem = A>,
+        T: Data<Elem = A>,
+    {
+        self.check(args)?;
+        let mut dx = grad.to_owned();
+        let mut dalpha = Array1::zeros(self.alpha.len());
+        let axis = Axis(args.ndim().saturating_sub(1));
+        for (lane, mut dlane) in args.lanes(axis).into_iter().zip(dx.lanes_mut(axis)) {
+            for (c, (&x, g)) in lane.iter().zip(dlane.iter_mut()).enumerate() {
+                if x <= A::zero() {
+                    let idx = self.index(c);
+                    dalpha[idx] = dalpha[idx] + *g * x;
+                    *g = *g * self.slope(c);
+                }
+            }
+        }
+        Ok((dx, dalpha))
+    }
+}
+
+impl<A> Default for PReLU<A>
+where
+    A: Float,
+{
+    fn default() -> Self {
+        Self::shared(A::from(0.25).unwrap())
+    }
+}
+
+impl<'a, A, S, D> Activate<&'a ArrayBase<S, D>> for PReLU<A>
+where
+    A: Float,
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    type Output = Result<Array<A, D>, PredictError>;
+
+    fn activate(&self, args: &'a ArrayBase<S, D>) -> Self::Output {
+        self.forward(args)
+    }
+}
+
+impl<A, S> Optimizable<A, ArrayBase<S, Ix1>> for PReLU<A>
+where
+    S: Data<Elem = A>,
+{
+    fn zip_grad(
+        &mut self,
+        grads: &ArrayBase<S, Ix1>,
+        f: &mut dyn FnMut(ArrayViewMutD<'_, A>, ArrayViewD<'_, A>),
+    ) {
+        f(self.alpha.view_mut().into_dyn(), grads.view().into_dyn())
+    }
+}
@@ -2,11 +2,11 @@
     Appellation: utils <module>
     Contrib: FL03 <jo3mccain@icloud.com>
 */
-use crate::math::Exp;
+use crate::math::arith::erf;
 use nd::prelude::{Array, ArrayBase, Axis, Dimension};
-use nd::{Data, RemoveAxis, ScalarOperand};
+use nd::{Data, RemoveAxis, ScalarOperand, Zip};
 use num::complex::ComplexFloat;
-use num::traits::{One, Zero};
+use num::traits::{Float, One, Zero};
 
 /// Heaviside activation function
 pub fn heavyside<T>(x: T) -> T
@@ -37,26 +37,77 @@ where
 {
     (T::one() + args.neg().exp()).recip()
 }
-///
+/// Computes the softmax of all the elements of the array; the inputs are shifted by their
+/// maximum before being exponentiated to avoid overflowing.
 pub fn softmax<A, S, D>(args: &ArrayBase<S, D>) -> Array<A, D>
 where
     A: ComplexFloat + ScalarOperand,
     D: Dimension,
     S: Data<Elem = A>,
 {
-    let e = args.exp();
-    &e / e.sum()
+    let shift = shift(args.iter());
+    let e = args.mapv(|x| (x - shift).exp());
+    let sum = e.sum();
+    e.mapv(|x| x / sum)
 }
-///
+/// Computes the softmax along each lane of the given axis, such that every lane sums to one.
 pub fn softmax_axis<A, S, D>(args: &ArrayBase<S, D>, axis: usize) -> Array<A, D>
 where
     A: ComplexFloat + ScalarOperand,
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    let mut res = args.to_owned();
+    for mut lane in res.lanes_mut(Axis(axis)) {
+        let shift = shift(lane.iter());
+        lane.mapv_inplace(|x| (x - shift).exp());
+        let sum = lane.sum();
+        lane.mapv_inplace(|x| x / sum);
+    }
+    res
+}
+/// Computes the logarithm of the [softmax] of all the elements of the array, i.e.
+/// `x - logsumexp(x)`, without exponentiating the inputs directly.
+pub fn log_softmax<A, S, D>(args: &ArrayBase<S, D>) -> Array<A, D>
+where
+    A: ComplexFloat + ScalarOperand,
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    let lse = logsumexp(args);
+    args.mapv(|x| x - lse)
+}
+/// Computes the logarithm of the [softmax_axis] along each lane of the given axis.
+pub fn log_softmax_axis<A, S, D>(args: &ArrayBase<S, D>, axis: usize) -> Array<A, D>
+where
+    A: ComplexFloat + ScalarOperand,
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    let mut res = args.to_owned();
+    for mut lane in res.lanes_mut(Axis(axis)) {
+        let lse = lse(lane.iter());
+        lane.mapv_inplace(|x| x - lse);
+    }
+    res
+}
+/// Computes `ln(sum(exp(x)))` over all the elements of the array.
+pub fn logsumexp<A, S, D>(args: &ArrayBase<S, D>) -> A
+where
+    A: ComplexFloat,
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    lse(args.iter())
+}
+/// Computes `ln(sum(exp(x)))` along the given axis, removing it.
+pub fn logsumexp_axis<A, S, D>(args: &ArrayBase<S, D>, axis: usize) -> Array<A, D::Smaller>
+where
+    A: ComplexFloat,
     D: RemoveAxis,
     S: Data<Elem = A>,
 {
-    let axis = Axis(axis);
-    let e = args.exp();
-    &e / &e.sum_axis(axis)
+    args.map_axis(Axis(axis), |lane| lse(lane.iter()))
 }
 ///
 pub fn tanh<T>(args: T) -> T
@@ -65,3 +116,330 @@ where
 {
     args.tanh()
 }
+/// The Gaussian error linear unit, `x * P(X <= x)` for a standard normal `X`, computed
+/// using the error function.
+pub fn gelu<T>(args: T) -> T
+where
+    T: Float,
+{
+    let half = T::from(0.5).unwrap();
+    half * args * (T::one() + erf(args * T::from(core::f64::consts::FRAC_1_SQRT_2).unwrap()))
+}
+/// The tanh approximation of the [gelu] function.
+pub fn gelu_tanh<T>(args: T) -> T
+where
+    T: ComplexFloat,
+{
+    let c = |v: f64| T::from(v).unwrap();
+    let inner = c((2.0 / core::f64::consts::PI).sqrt()) * (args + c(0.044715) * args.powi(3));
+    c(0.5) * args * (T::one() + inner.tanh())
+}
+/// The exponential linear unit; negative inputs saturate towards `-alpha`.
+pub fn elu<T>(args: T, alpha: T) -> T
+where
+    T: Float,
+{
+    if args > T::zero() {
+        args
+    } else {
+        alpha * args.exp_m1()
+    }
+}
+/// The hard (piecewise linear) approximation of the [sigmoid], `clamp((x + 3) / 6, 0, 1)`.
+pub fn hard_sigmoid<T>(args: T) -> T
+where
+    T: Float,
+{
+    let three = T::from(3).unwrap();
+    ((args + three) / (three + three))
+        .max(T::zero())
+        .min(T::one())
+}
+/// The hard swish function, `x * hard_sigmoid(x)`.
+pub fn hard_swish<T>(args: T) -> T
+where
+    T: Float,
+{
+    args * hard_sigmoid(args)
+}
+/// The leaky [relu]; negative inputs are scaled by `alpha` rather than discarded.
+pub fn leaky_relu<T>(args: T, alpha: T) -> T
+where
+    T: Float,
+{
+    if args > T::zero() {
+        args
+    } else {
+        alpha * args
+    }
+}
+/// The self-regularizing activation `x * tanh(softplus(x))`.
+pub fn mish<T>(args: T) -> T
+where
+    T: Float,
+{
+    args * softplus(args).tanh()
+}
+/// The scaled [elu], whose constants are chosen such that activations are self-normalizing.
+pub fn selu<T>(args: T) -> T
+where
+    T: Float,
+{
+    let alpha = T::from(1.6732632423543772).unwrap();
+    let scale = T::from(1.0507009873554805).unwrap();
+    scale * elu(args, alpha)
+}
+/// The sigmoid linear unit, `x * sigmoid(x)`; equivalent to the [swish] with a `beta` of one.
+pub fn silu<T>(args: T) -> T
+where
+    T: ComplexFloat,
+{
+    args * sigmoid(args)
+}
+/// The smooth approximation of the [relu], `ln(1 + exp(x))`, computed without overflowing
+/// for large inputs.
+pub fn softplus<T>(args: T) -> T
+where
+    T: Float,
+{
+    args.max(T::zero()) + (-args.abs()).exp().ln_1p()
+}
+/// The softsign function, `x / (1 + |x|)`.
+pub fn softsign<T>(args: T) -> T
+where
+    T: Float,
+{
+    args / (T::one() + args.abs())
+}
+/// The swish function, `x * sigmoid(beta * x)`.
+pub fn swish<T>(args: T, beta: T) -> T
+where
+    T: ComplexFloat,
+{
+    args * sigmoid(beta * args)
+}
+/// The derivative of the [heavyside] function, which vanishes almost everywhere.
+pub fn heavyside_derivative<T>(_args: T) -> T
+where
+    T: Zero,
+{
+    T::zero()
+}
+/// The derivative of the linear (identity) activation.
+pub fn linear_derivative<T>(_args: T) -> T
+where
+    T: One,
+{
+    T::one()
+}
+/// The derivative of the [relu] function; the subgradient at zero is taken to be zero.
+pub fn relu_derivative<T>(args: T) -> T
+where
+    T: One + PartialOrd + Zero,
+{
+    heavyside(args)
+}
+/// The derivative of the [sigmoid] function, i.e. `sigmoid(x) * (1 - sigmoid(x))`.
+pub fn sigmoid_derivative<T>(args: T) -> T
+where
+    T: ComplexFloat,
+{
+    let s = sigmoid(args);
+    s * (T::one() - s)
+}
+/// The derivative of the [tanh] function, i.e. `1 - tanh(x)^2`.
+pub fn tanh_derivative<T>(args: T) -> T
+where
+    T: ComplexFloat,
+{
+    let t = args.tanh();
+    T::one() - t * t
+}
+/// The derivative of the [elu] function.
+pub fn elu_derivative<T>(args: T, alpha: T) -> T
+where
+    T: Float,
+{
+    if args > T::zero() {
+        T::one()
+    } else {
+        alpha * args.exp()
+    }
+}
+/// The derivative of the [gelu] function, `cdf(x) + x * pdf(x)` for the standard normal.
+pub fn gelu_derivative<T>(args: T) -> T
+where
+    T: Float,
+{
+    let half = T::from(0.5).unwrap();
+    let cdf = half * (T::one() + erf(args * T::from(core::f64::consts::FRAC_1_SQRT_2).unwrap()));
+    let pdf = (-half * args * args).exp() * T::from(0.3989422804014327).unwrap();
+    cdf + args * pdf
+}
+/// The derivative of the [gelu_tanh] function.
+pub fn gelu_tanh_derivative<T>(args: T) -> T
+where
+    T: ComplexFloat,
+{
+    let c = |v: f64| T::from(v).unwrap();
+    let k = c((2.0 / core::f64::consts::PI).sqrt());
+    let t = (k * (args + c(0.044715) * args.powi(3))).tanh();
+    let du = k * (T::one() + c(3.0 * 0.044715) * args * args);
+    c(0.5) * (T::one() + t) + c(0.5) * args * (T::one() - t * t) * du
+}
+/// The derivative of the [hard_sigmoid] function; it is `1 / 6` between the saturation
+/// points and zero beyond them.
+pub fn hard_sigmoid_derivative<T>(args: T) -> T
+where
+    T: Float,
+{
+    let three = T::from(3).unwrap();
+    if args > -three && args < three {
+        (three + three).recip()
+    } else {
+        T::zero()
+    }
+}
+/// The derivative of the [hard_swish] function.
+pub fn hard_swish_derivative<T>(args: T) -> T
+where
+    T: Float,
+{
+    hard_sigmoid(args) + args * hard_sigmoid_derivative(args)
+}
+/// The derivative of the [leaky_relu] function.
+pub fn leaky_relu_derivative<T>(args: T, alpha: T) -> T
+where
+    T: Float,
+{
+    if args > T::zero() {
+        T::one()
+    } else {
+        alpha
+    }
+}
+/// The derivative of the [mish] function.
+pub fn mish_derivative<T>(args: T) -> T
+where
+    T: Float,
+{
+    let t = softplus(args).tanh();
+    t + args * (T::one() - t * t) * softplus_derivative(args)
+}
+/// The derivative of the [selu] function.
+pub fn selu_derivative<T>(args: T) -> T
+where
+    T: Float,
+{
+    let alpha = T::from(1.6732632423543772).unwrap();
+    let scale = T::from(1.0507009873554805).unwrap();
+    scale * elu_derivative(args, alpha)
+}
+/// The derivative of the [silu] function, `sigmoid(x) * (1 + x * (1 - sigmoid(x)))`.
+pub fn silu_derivative<T>(args: T) -> T
+where
+    T: ComplexFloat,
+{
+    let s = sigmoid(args);
+    s * (T::one() + args * (T::one() - s))
+}
+/// The derivative of the [softplus] function, which is the logistic sigmoid.
+pub fn softplus_derivative<T>(args: T) -> T
+where
+    T: Float,
+{
+    (T::one() + (-args).exp()).recip()
+}
+/// The derivative of the [softsign] function, `1 / (1 + |x|)^2`.
+pub fn softsign_derivative<T>(args: T) -> T
+where
+    T: Float,
+{
+    (T::one() + args.abs()).powi(2).recip()
+}
+/// Computes the vector-Jacobian product of the [softmax] function at `args`, i.e. the
+/// gradient with respect to `args` given the gradient `grad` with respect to its output.
+pub fn softmax_vjp<A, S, T, D>(args: &ArrayBase<S, D>, grad: &ArrayBase<T, D>) -> Array<A, D>
+where
+    A: ComplexFloat + ScalarOperand,
+    D: Dimension,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    vjp(softmax(args), grad)
+}
+/// Computes the vector-Jacobian product of the [softmax_axis] function at `args`.
+pub fn softmax_axis_vjp<A, S, T, D>(
+    args: &ArrayBase<S, D>,
+    axis: usize,
+    grad: &ArrayBase<T, D>,
+) -> Array<A, D>
+where
+    A: ComplexFloat + ScalarOperand,
+    D: RemoveAxis,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    vjp_axis(softmax_axis(args, axis), axis, grad)
+}
+
+/// Computes `y * (grad - sum(grad * y))` given the output `y` of a softmax.
+pub(crate) fn vjp<A, T, D>(y: Array<A, D>, grad: &ArrayBase<T, D>) -> Array<A, D>
+where
+    A: ComplexFloat,
+    D: Dimension,
+    T: Data<Elem = A>,
+{
+    let dot = (&y * grad).sum();
+    y * &grad.mapv(|g| g - dot)
+}
+/// Computes the vector-Jacobian product of a softmax taken along each lane of the given axis.
+pub(crate) fn vjp_axis<A, T, D>(
+    mut y: Array<A, D>,
+    axis: usize,
+    grad: &ArrayBase<T, D>,
+) -> Array<A, D>
+where
+    A: ComplexFloat,
+    D: Dimension,
+    T: Data<Elem = A>,
+{
+    let axis = Axis(axis);
+    Zip::from(y.lanes_mut(axis))
+        .and(grad.lanes(axis))
+        .for_each(|mut y, g| {
+            let dot = (&y * &g).sum();
+            y.zip_mut_with(&g, |y, &g| *y = *y * (g - dot));
+        });
+    y
+}
+
+/// Returns the largest real part amongst the given values, by which they are shifted before
+/// being exponentiated; should it not be finite, the values are left unshifted.
+fn shift<'a, A, I>(iter: I) -> A
+where
+    A: ComplexFloat + 'a,
+    I: IntoIterator<Item = &'a A>,
+{
+    let max = iter
+        .into_iter()
+        .fold(A::Real::neg_infinity(), |m, x| Float::max(m, x.re()));
+    if Float::is_finite(max) {
+        A::from(max).unwrap()
+    } else {
+        A::zero()
+    }
+}
+
+/// Computes the log-sum-exp of the given values, shifting them by their maximum.
+fn lse<'a, A, I>(iter: I) -> A
+where
+    A: ComplexFloat + 'a,
+    I: IntoIterator<Item = &'a A> + Clone,
+{
+    let shift = shift(iter.clone());
+    let sum = iter
+        .into_iter()
+        .fold(A::zero(), |acc, &x| acc + (x - shift).exp());
+    shift + sum.ln()
+}
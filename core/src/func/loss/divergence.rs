@@ -0,0 +1,149 @@
+/*
+    Appellation: divergence <loss>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+//! # Divergences
+//!
+//! Divergences measure the dissimilarity of two batches of discrete distributions, given as
+//! `(samples, classes)` arrays whose rows sum to one. The divergence of each sample is the sum
+//! of the (optionally weighted) contributions of each class; `None` is returned should the
+//! class weights not match the classes.
+use super::{Loss, LossGrad, Reduction};
+use nd::prelude::*;
+use nd::{Data, ScalarOperand, Zip};
+use num::traits::Float;
+
+losses! {
+    /// [KLDivergence] is the Kullback-Leibler divergence of the predicted distribution from
+    /// the target distribution, i.e. `sum(target * ln(target / pred))`.
+    KLDivergence<class> {},
+    /// [JSDivergence] is the Jensen-Shannon divergence; a symmetric and bounded alternative to
+    /// the [KLDivergence], measuring the divergence of each distribution from their mixture.
+    JSDivergence<class> {},
+}
+
+/// Returns the contribution of `p` to the divergence `KL(p || q)`, treating `0 ln 0` as zero.
+fn kl<A>(p: A, q: A) -> A
+where
+    A: Float,
+{
+    if p <= A::zero() {
+        return A::zero();
+    }
+    p * (p.ln() - q.max(A::epsilon()).ln())
+}
+
+/// Weights the contributions of each class before summing them into per-sample divergences,
+/// which are then reduced.
+fn divergence<A>(
+    mut values: Array2<A>,
+    weights: Option<&Array1<A>>,
+    reduction: Reduction,
+) -> Option<ArrayD<A>>
+where
+    A: Float + ScalarOperand,
+{
+    if let Some(w) = weights {
+        if w.len() != values.ncols() {
+            return None;
+        }
+        values = values * w;
+    }
+    Some(reduction.reduce(&values.sum_axis(Axis(1))))
+}
+
+/// Weights the gradient of each class, scaling it according to the reduction.
+fn divergence_grad<A>(
+    mut grad: Array2<A>,
+    weights: Option<&Array1<A>>,
+    reduction: Reduction,
+) -> Option<Array2<A>>
+where
+    A: Float + ScalarOperand,
+{
+    if let Some(w) = weights {
+        if w.len() != grad.ncols() {
+            return None;
+        }
+        grad = grad * w;
+    }
+    let scale = reduction.grad_scale(A::from(grad.nrows()).unwrap());
+    Some(grad * scale)
+}
+
+/*
+ ************* Implementations *************
+*/
+impl<A, S, T> Loss<ArrayBase<S, Ix2>, ArrayBase<T, Ix2>> for KLDivergence<A>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    type Output = Option<ArrayD<A>>;
+
+    fn loss(&self, pred: &ArrayBase<S, Ix2>, target: &ArrayBase<T, Ix2>) -> Self::Output {
+        let values = Zip::from(target).and(pred).map_collect(|&p, &q| kl(p, q));
+        divergence(values, self.weights.as_ref(), self.reduction)
+    }
+}
+
+impl<A, S, T> Loss<ArrayBase<S, Ix2>, ArrayBase<T, Ix2>> for JSDivergence<A>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    type Output = Option<ArrayD<A>>;
+
+    fn loss(&self, pred: &ArrayBase<S, Ix2>, target: &ArrayBase<T, Ix2>) -> Self::Output {
+        let half = A::from(0.5).unwrap();
+        let values = Zip::from(target).and(pred).map_collect(|&p, &q| {
+            let m = half * (p + q);
+            half * (kl(p, m) + kl(q, m))
+        });
+        divergence(values, self.weights.as_ref(), self.reduction)
+    }
+}
+
+impl<A, S, T> LossGrad<ArrayBase<S, Ix2>, ArrayBase<T, Ix2>> for KLDivergence<A>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    type Grad = Option<Array2<A>>;
+
+    fn loss_grad(&self, pred: &ArrayBase<S, Ix2>, target: &ArrayBase<T, Ix2>) -> Self::Grad {
+        let grad = Zip::from(target).and(pred).map_collect(|&p, &q| {
+            if p <= A::zero() {
+                A::zero()
+            } else {
+                -p / q.max(A::epsilon())
+            }
+        });
+        divergence_grad(grad, self.weights.as_ref(), self.reduction)
+    }
+}
+
+impl<A, S, T> LossGrad<ArrayBase<S, Ix2>, ArrayBase<T, Ix2>> for JSDivergence<A>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    type Grad = Option<Array2<A>>;
+
+    fn loss_grad(&self, pred: &ArrayBase<S, Ix2>, target: &ArrayBase<T, Ix2>) -> Self::Grad {
+        // the terms involving the mixture cancel, leaving ln(q / m) / 2
+        let half = A::from(0.5).unwrap();
+        let grad = Zip::from(target).and(pred).map_collect(|&p, &q| {
+            let m = half * (p + q);
+            if m <= A::zero() {
+                return A::zero();
+            }
+            half * (q.max(A::epsilon()).ln() - m.ln())
+        });
+        divergence_grad(grad, self.weights.as_ref(), self.reduction)
+    }
+}
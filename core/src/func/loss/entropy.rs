@@ -2,6 +2,35 @@
     Appellation: entropy <module>
     Contrib: FL03 <jo3mccain@icloud.com>
 */
+//! # Cross-Entropy Losses
+//!
+//! This module implements the family of cross-entropy losses used for classification. Each
+//! loss may be configured with a [Reduction](crate::func::Reduction), per-class weights, and
+//! label smoothing, which mixes the targets with the uniform distribution over the classes in
+//! proportion to `label_smoothing`.
+//!
+//! - Binary losses compare predictions of any shape against targets in `[0, 1]`; the class
+//!   weights, if any, are ordered `[negative, positive]`.
+//! - Categorical losses compare `(samples, classes)` predictions against either one-hot (or
+//!   soft) targets of the same shape, or, for the sparse variants, a 1-dimensional array of
+//!   integer labels.
+//!
+//! The `WithLogits` variants accept raw scores rather than probabilities, computing the loss
+//! directly from the logits in a numerically stable manner. The [NLLLoss] instead expects
+//! log-probabilities, while the [FocalLoss] down-weights well-classified samples to focus on
+//! the harder examples of heavily imbalanced data.
+pub(crate) mod binary;
+pub(crate) mod categorical;
+
+pub(crate) mod prelude {
+    pub use super::{BinaryCrossEntropy, BinaryCrossEntropyWithLogits, Entropy};
+    pub use super::{CrossEntropy, CrossEntropyWithLogits, FocalLoss, NLLLoss};
+    pub use super::{SparseCrossEntropy, SparseCrossEntropyWithLogits};
+}
+
+use nd::prelude::*;
+use nd::{Data, ScalarOperand};
+use num::traits::Float;
 
 pub trait Entropy<T = Self> {
     type Output;
@@ -9,4 +38,42 @@ pub trait Entropy<T = Self> {
     fn cross_entropy(&self, target: &T) -> Self::Output;
 }
 
-pub struct CrossEntropy;
+losses! {
+    /// [BinaryCrossEntropy] is the negative log-likelihood of binary targets under the
+    /// predicted probabilities.
+    BinaryCrossEntropy<class> { label_smoothing: A::zero() },
+    /// [BinaryCrossEntropyWithLogits] fuses a sigmoid with the [BinaryCrossEntropy].
+    BinaryCrossEntropyWithLogits<class> { label_smoothing: A::zero() },
+    /// [CrossEntropy] is the negative log-likelihood of categorical targets under the predicted
+    /// class probabilities.
+    CrossEntropy<class> { label_smoothing: A::zero() },
+    /// [CrossEntropyWithLogits] fuses a softmax with the [CrossEntropy].
+    CrossEntropyWithLogits<class> { label_smoothing: A::zero() },
+    /// [NLLLoss] is the negative log-likelihood of categorical targets given the predicted
+    /// log-probabilities of each class, as produced by a log-softmax.
+    NLLLoss<class> {},
+    /// [FocalLoss] scales the [CrossEntropy] of each sample by `(1 - p)^gamma`, where `p` is
+    /// the probability predicted for the target class; a `gamma` of zero recovers the
+    /// cross-entropy.
+    FocalLoss<class> { gamma: A::from(2).unwrap() },
+    /// [SparseCrossEntropy] is the [CrossEntropy] of class probabilities against integer labels.
+    SparseCrossEntropy<class> { label_smoothing: A::zero() },
+    /// [SparseCrossEntropyWithLogits] is the [CrossEntropyWithLogits] of raw scores against
+    /// integer labels.
+    SparseCrossEntropyWithLogits<class> { label_smoothing: A::zero() },
+}
+
+/// Computes the mean categorical cross-entropy of a batch of predicted class probabilities.
+impl<A, S, T> Entropy<ArrayBase<T, Ix2>> for ArrayBase<S, Ix2>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    type Output = A;
+
+    fn cross_entropy(&self, target: &ArrayBase<T, Ix2>) -> Self::Output {
+        let n = A::from(self.nrows()).unwrap();
+        -(target * &self.mapv(|p| p.max(A::epsilon()).ln())).sum() / n
+    }
+}
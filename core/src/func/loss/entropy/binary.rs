@@ -0,0 +1,178 @@
+/*
+    Appellation: binary <entropy>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::{BinaryCrossEntropy, BinaryCrossEntropyWithLogits};
+use crate::func::activate::softplus;
+use crate::func::{Loss, LossGrad};
+use nd::prelude::*;
+use nd::{Data, ScalarOperand, Zip};
+use num::traits::Float;
+
+/// Computes the elementwise binary cross-entropy given the negative log-likelihoods of the
+/// positive (`pos`) and negative (`neg`) classes; given their derivatives instead, the result
+/// is the elementwise gradient. Returns `None` unless the weights, if any, hold exactly two
+/// classes.
+fn binary<A, S, T, D, F>(
+    pred: &ArrayBase<S, D>,
+    target: &ArrayBase<T, D>,
+    smoothing: A,
+    weights: Option<&Array1<A>>,
+    nll: F,
+) -> Option<Array<A, D>>
+where
+    A: Float,
+    D: Dimension,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+    F: Fn(A) -> (A, A),
+{
+    let two = A::one() + A::one();
+    let (w0, w1) = match weights.map(|w| w.as_slice()) {
+        None => (A::one(), A::one()),
+        Some(Some(&[w0, w1])) => (w0, w1),
+        Some(_) => return None,
+    };
+    let res = Zip::from(pred).and(target).map_collect(|&p, &y| {
+        let y = y * (A::one() - smoothing) + smoothing / two;
+        let (pos, neg) = nll(p);
+        w1 * y * pos + w0 * (A::one() - y) * neg
+    });
+    Some(res)
+}
+
+impl<A> BinaryCrossEntropy<A>
+where
+    A: Float + ScalarOperand,
+{
+    /// Computes the unreduced loss of each element, returning `None` should the weights not
+    /// hold exactly two classes.
+    pub fn losses<S, T, D>(
+        &self,
+        pred: &ArrayBase<S, D>,
+        target: &ArrayBase<T, D>,
+    ) -> Option<Array<A, D>>
+    where
+        D: Dimension,
+        S: Data<Elem = A>,
+        T: Data<Elem = A>,
+    {
+        let eps = A::epsilon();
+        binary(
+            pred,
+            target,
+            self.label_smoothing,
+            self.weights.as_ref(),
+            |p| {
+                let p = p.max(eps).min(A::one() - eps);
+                (-p.ln(), -(A::one() - p).ln())
+            },
+        )
+    }
+}
+
+impl<A> BinaryCrossEntropyWithLogits<A>
+where
+    A: Float + ScalarOperand,
+{
+    /// Computes the unreduced loss of each element, returning `None` should the weights not
+    /// hold exactly two classes.
+    pub fn losses<S, T, D>(
+        &self,
+        logits: &ArrayBase<S, D>,
+        target: &ArrayBase<T, D>,
+    ) -> Option<Array<A, D>>
+    where
+        D: Dimension,
+        S: Data<Elem = A>,
+        T: Data<Elem = A>,
+    {
+        // -ln(sigmoid(x)) = softplus(-x) and -ln(1 - sigmoid(x)) = softplus(x)
+        binary(
+            logits,
+            target,
+            self.label_smoothing,
+            self.weights.as_ref(),
+            |x| (softplus(-x), softplus(x)),
+        )
+    }
+}
+
+impl<A, S, T, D> Loss<ArrayBase<S, D>, ArrayBase<T, D>> for BinaryCrossEntropy<A>
+where
+    A: Float + ScalarOperand,
+    D: Dimension,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    type Output = Option<ArrayD<A>>;
+
+    fn loss(&self, pred: &ArrayBase<S, D>, target: &ArrayBase<T, D>) -> Self::Output {
+        let losses = self.losses(pred, target)?;
+        Some(self.reduction.reduce(&losses))
+    }
+}
+
+impl<A, S, T, D> Loss<ArrayBase<S, D>, ArrayBase<T, D>> for BinaryCrossEntropyWithLogits<A>
+where
+    A: Float + ScalarOperand,
+    D: Dimension,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    type Output = Option<ArrayD<A>>;
+
+    fn loss(&self, logits: &ArrayBase<S, D>, target: &ArrayBase<T, D>) -> Self::Output {
+        let losses = self.losses(logits, target)?;
+        Some(self.reduction.reduce(&losses))
+    }
+}
+
+impl<A, S, T, D> LossGrad<ArrayBase<S, D>, ArrayBase<T, D>> for BinaryCrossEntropy<A>
+where
+    A: Float + ScalarOperand,
+    D: Dimension,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    type Grad = Option<Array<A, D>>;
+
+    fn loss_grad(&self, pred: &ArrayBase<S, D>, target: &ArrayBase<T, D>) -> Self::Grad {
+        let eps = A::epsilon();
+        let grad = binary(
+            pred,
+            target,
+            self.label_smoothing,
+            self.weights.as_ref(),
+            |p| {
+                let p = p.max(eps).min(A::one() - eps);
+                (-p.recip(), (A::one() - p).recip())
+            },
+        )?;
+        Some(grad * self.reduction.grad_scale(A::from(pred.len()).unwrap()))
+    }
+}
+
+impl<A, S, T, D> LossGrad<ArrayBase<S, D>, ArrayBase<T, D>> for BinaryCrossEntropyWithLogits<A>
+where
+    A: Float + ScalarOperand,
+    D: Dimension,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    type Grad = Option<Array<A, D>>;
+
+    fn loss_grad(&self, logits: &ArrayBase<S, D>, target: &ArrayBase<T, D>) -> Self::Grad {
+        let grad = binary(
+            logits,
+            target,
+            self.label_smoothing,
+            self.weights.as_ref(),
+            |x| {
+                let p = (-softplus(-x)).exp();
+                (p - A::one(), p)
+            },
+        )?;
+        Some(grad * self.reduction.grad_scale(A::from(logits.len()).unwrap()))
+    }
+}
@@ -0,0 +1,255 @@
+/*
+    Appellation: categorical <entropy>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::{CrossEntropy, CrossEntropyWithLogits, FocalLoss, NLLLoss};
+use super::{SparseCrossEntropy, SparseCrossEntropyWithLogits};
+use crate::func::{Loss, LossGrad};
+use nd::prelude::*;
+use nd::{Data, ScalarOperand};
+use num::traits::Float;
+
+/// Expands integer labels into one-hot rows over the given number of classes, returning
+/// `None` should any label exceed them.
+pub(crate) fn one_hot<A, S>(labels: &ArrayBase<S, Ix1>, classes: usize) -> Option<Array2<A>>
+where
+    A: Float,
+    S: Data<Elem = usize>,
+{
+    let mut res = Array2::zeros((labels.len(), classes));
+    for (mut row, &label) in res.rows_mut().into_iter().zip(labels) {
+        *row.get_mut(label)? = A::one();
+    }
+    Some(res)
+}
+
+/// Computes the log-softmax of each row, shifting by the maximum to avoid overflow.
+pub(crate) fn log_softmax_rows<A, S>(logits: &ArrayBase<S, Ix2>) -> Array2<A>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+{
+    let mut res = logits.to_owned();
+    for mut row in res.rows_mut() {
+        let max = row.fold(A::neg_infinity(), |m, &x| m.max(x));
+        let lse = max + row.fold(A::zero(), |acc, &x| acc + (x - max).exp()).ln();
+        row.mapv_inplace(|x| x - lse);
+    }
+    res
+}
+
+/// Returns the coefficients of the log-probability of each class, i.e. the smoothed and
+/// weighted targets, alongside the normalizer of the mean: the total weight of the
+/// (unsmoothed) targets. Returns `None` should the weights not match the classes.
+fn coefficients<A, S>(
+    target: &ArrayBase<S, Ix2>,
+    smoothing: A,
+    weights: Option<&Array1<A>>,
+) -> Option<(Array2<A>, A)>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+{
+    let k = A::from(target.ncols()).unwrap();
+    let smoothed = target.mapv(|y| y * (A::one() - smoothing) + smoothing / k);
+    match weights {
+        Some(w) if w.len() != target.ncols() => None,
+        Some(w) => Some((smoothed * w, (target * w).sum())),
+        None => Some((smoothed, A::from(target.nrows()).unwrap())),
+    }
+}
+
+/// Implements the categorical [Loss] and [LossGrad] of the given losses from their
+/// log-probabilities (`log_probs`) and the gradient (`grad`) of the unreduced loss given the
+/// coefficients of each class; the mean is normalized by the total weight of the targets,
+/// while [Reduction::None](crate::func::Reduction::None) yields the loss of each sample.
+macro_rules! categorical {
+    ($($name:ident {
+        smoothing: |$s:ident| $smoothing:expr,
+        log_probs: |$lp:ident, $x:ident| $log_probs:expr,
+        grad: |$g:ident, $p:ident, $c:ident| $grad:expr $(,)?
+    });* $(;)?) => {
+        $(
+            impl<A> $name<A>
+            where
+                A: Float + ScalarOperand,
+            {
+                /// Computes the unreduced loss of each sample, returning `None` should the weights
+                /// not match the classes.
+                pub fn losses<S, T>(
+                    &self,
+                    pred: &ArrayBase<S, Ix2>,
+                    target: &ArrayBase<T, Ix2>,
+                ) -> Option<Array1<A>>
+                where
+                    S: Data<Elem = A>,
+                    T: Data<Elem = A>,
+                {
+                    self.losses_with_norm(pred, target).map(|(losses, _)| losses)
+                }
+
+                fn losses_with_norm<S, T>(
+                    &self,
+                    pred: &ArrayBase<S, Ix2>,
+                    target: &ArrayBase<T, Ix2>,
+                ) -> Option<(Array1<A>, A)>
+                where
+                    S: Data<Elem = A>,
+                    T: Data<Elem = A>,
+                {
+                    let $s = self;
+                    let (coeffs, norm) = coefficients(target, $smoothing, self.weights.as_ref())?;
+                    let ($lp, $x) = (self, pred);
+                    let losses = -(coeffs * $log_probs);
+                    Some((losses.sum_axis(Axis(1)), norm))
+                }
+            }
+
+            impl<A, S, T> Loss<ArrayBase<S, Ix2>, ArrayBase<T, Ix2>> for $name<A>
+            where
+                A: Float + ScalarOperand,
+                S: Data<Elem = A>,
+                T: Data<Elem = A>,
+            {
+                type Output = Option<ArrayD<A>>;
+
+                fn loss(&self, pred: &ArrayBase<S, Ix2>, target: &ArrayBase<T, Ix2>) -> Self::Output {
+                    let (losses, norm) = self.losses_with_norm(pred, target)?;
+                    Some(self.reduction.reduce_by(&losses, norm))
+                }
+            }
+
+            impl<A, S, T> LossGrad<ArrayBase<S, Ix2>, ArrayBase<T, Ix2>> for $name<A>
+            where
+                A: Float + ScalarOperand,
+                S: Data<Elem = A>,
+                T: Data<Elem = A>,
+            {
+                type Grad = Option<Array2<A>>;
+
+                fn loss_grad(&self, pred: &ArrayBase<S, Ix2>, target: &ArrayBase<T, Ix2>) -> Self::Grad {
+                    let $s = self;
+                    let (coeffs, norm) = coefficients(target, $smoothing, self.weights.as_ref())?;
+                    let ($g, $p, $c) = (self, pred, &coeffs);
+                    let grad: Array2<A> = $grad;
+                    Some(grad * self.reduction.grad_scale(norm))
+                }
+            }
+        )*
+    };
+}
+
+/// Implements the [Loss] and [LossGrad] of the given losses against integer labels, which are
+/// expanded into one-hot targets; both return `None` should a label exceed the classes.
+macro_rules! sparse {
+    ($($name:ident$(.$dense:ident())?),* $(,)?) => {
+        $(
+            impl<A, S, T> Loss<ArrayBase<S, Ix2>, ArrayBase<T, Ix1>> for $name<A>
+            where
+                A: Float + ScalarOperand,
+                S: Data<Elem = A>,
+                T: Data<Elem = usize>,
+            {
+                type Output = Option<ArrayD<A>>;
+
+                fn loss(&self, pred: &ArrayBase<S, Ix2>, labels: &ArrayBase<T, Ix1>) -> Self::Output {
+                    let target = one_hot::<A, T>(labels, pred.ncols())?;
+                    self$(.$dense())?.loss(pred, &target)
+                }
+            }
+
+            impl<A, S, T> LossGrad<ArrayBase<S, Ix2>, ArrayBase<T, Ix1>> for $name<A>
+            where
+                A: Float + ScalarOperand,
+                S: Data<Elem = A>,
+                T: Data<Elem = usize>,
+            {
+                type Grad = Option<Array2<A>>;
+
+                fn loss_grad(&self, pred: &ArrayBase<S, Ix2>, labels: &ArrayBase<T, Ix1>) -> Self::Grad {
+                    let target = one_hot::<A, T>(labels, pred.ncols())?;
+                    self$(.$dense())?.loss_grad(pred, &target)
+                }
+            }
+        )*
+    };
+}
+
+categorical! {
+    CrossEntropy {
+        smoothing: |this| this.label_smoothing,
+        log_probs: |_this, pred| pred.mapv(|p| p.max(A::epsilon()).ln()),
+        grad: |_this, pred, coeffs| -(coeffs * &pred.mapv(|p| p.max(A::epsilon()).recip())),
+    };
+    CrossEntropyWithLogits {
+        smoothing: |this| this.label_smoothing,
+        log_probs: |_this, logits| log_softmax_rows(logits),
+        // the gradient of each row is softmax(x) * sum(c) - c, given the coefficients c
+        grad: |_this, logits, coeffs| {
+            let total = coeffs.sum_axis(Axis(1)).insert_axis(Axis(1));
+            log_softmax_rows(logits).mapv(A::exp) * total - coeffs
+        },
+    };
+    NLLLoss {
+        smoothing: |_this| A::zero(),
+        log_probs: |_this, log_probs| log_probs.to_owned(),
+        grad: |_this, _log_probs, coeffs| -coeffs.to_owned(),
+    };
+    FocalLoss {
+        smoothing: |_this| A::zero(),
+        // modulating the log-probabilities scales the cross-entropy of each class
+        log_probs: |this, pred| pred.mapv(|p| {
+            let p = p.max(A::epsilon()).min(A::one());
+            (A::one() - p).powf(this.gamma) * p.ln()
+        }),
+        // d/dp (1 - p)^g ln(p) = (1 - p)^g / p - g (1 - p)^(g - 1) ln(p)
+        grad: |this, pred, coeffs| {
+            let dlog_probs = pred.mapv(|p| {
+                let p = p.max(A::epsilon()).min(A::one());
+                let q = A::one() - p;
+                let modulated = if this.gamma.is_zero() || q.is_zero() {
+                    A::zero()
+                } else {
+                    this.gamma * q.powf(this.gamma - A::one()) * p.ln()
+                };
+                q.powf(this.gamma) / p - modulated
+            });
+            -(coeffs * &dlog_probs)
+        },
+    };
+}
+
+sparse! {
+    NLLLoss,
+    FocalLoss,
+    SparseCrossEntropy.dense(),
+    SparseCrossEntropyWithLogits.dense(),
+}
+
+impl<A> SparseCrossEntropy<A>
+where
+    A: Clone,
+{
+    /// Returns the equivalent [CrossEntropy] of one-hot targets.
+    pub fn dense(&self) -> CrossEntropy<A> {
+        CrossEntropy {
+            label_smoothing: self.label_smoothing.clone(),
+            reduction: self.reduction,
+            weights: self.weights.clone(),
+        }
+    }
+}
+
+impl<A> SparseCrossEntropyWithLogits<A>
+where
+    A: Clone,
+{
+    /// Returns the equivalent [CrossEntropyWithLogits] of one-hot targets.
+    pub fn dense(&self) -> CrossEntropyWithLogits<A> {
+        CrossEntropyWithLogits {
+            label_smoothing: self.label_smoothing.clone(),
+            reduction: self.reduction,
+            weights: self.weights.clone(),
+        }
+    }
+}
@@ -0,0 +1,345 @@
+/*
+    Appellation: margin <loss>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+//! # Margin Losses
+//!
+//! Margin losses penalize raw scores which fail to separate the classes by at least a given
+//! margin, as used to train support vector machines. The scores of a batch are compared
+//! against either
+//!
+//! - targets of the same shape, in which each column is treated as a binary problem whose
+//!   labels are either `1` or, for any value that isn't positive, `-1`; or
+//! - a 1-dimensional array of integer labels, in which case the multi-class (Weston-Watkins)
+//!   formulation penalizes each class scored within the margin of the target class.
+//!
+//! Either returns `None` should a label exceed the classes, or the class weights not match
+//! them.
+use super::{Loss, LossGrad, Reduction};
+use nd::prelude::*;
+use nd::{Data, ScalarOperand, Zip};
+use num::traits::Float;
+
+losses! {
+    /// [Hinge] is the loss `max(0, margin - y * score)` of a (soft-margin) linear SVM.
+    Hinge<class> { margin: A::one() },
+    /// [SquaredHinge] squares the [Hinge] loss, penalizing larger violations more heavily
+    /// while remaining differentiable.
+    SquaredHinge<class> { margin: A::one() },
+}
+
+/// Returns `true` if the class weights, if any, hold a weight for each of the `n` classes.
+fn valid_weights<A>(weights: Option<&Array1<A>>, n: usize) -> bool {
+    !matches!(weights, Some(w) if w.len() != n)
+}
+
+/// Returns `true` if every label is one of the `n` classes and the class weights are valid.
+fn valid_labels<A, T>(labels: &ArrayBase<T, Ix1>, weights: Option<&Array1<A>>, n: usize) -> bool
+where
+    T: Data<Elem = usize>,
+{
+    valid_weights(weights, n) && labels.iter().all(|&label| label < n)
+}
+
+/// Computes the per-sample margin loss given binary targets for each column.
+fn binary<A, S, T, F>(
+    scores: &ArrayBase<S, Ix2>,
+    target: &ArrayBase<T, Ix2>,
+    margin: A,
+    weights: Option<&Array1<A>>,
+    reduction: Reduction,
+    f: F,
+) -> Option<ArrayD<A>>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+    F: Fn(A) -> A,
+{
+    if !valid_weights(weights, scores.ncols()) {
+        return None;
+    }
+    let mut losses = Zip::from(scores).and(target).map_collect(|&s, &y| {
+        let y = if y > A::zero() { A::one() } else { -A::one() };
+        f((margin - y * s).max(A::zero()))
+    });
+    if let Some(w) = weights {
+        losses = losses * w;
+    }
+    Some(reduction.reduce(&losses.sum_axis(Axis(1))))
+}
+
+/// Computes the per-sample multi-class margin loss, weighting each sample by the weight of its
+/// class; the mean is normalized by the total weight.
+fn multiclass<A, S, T, F>(
+    scores: &ArrayBase<S, Ix2>,
+    labels: &ArrayBase<T, Ix1>,
+    margin: A,
+    weights: Option<&Array1<A>>,
+    reduction: Reduction,
+    f: F,
+) -> Option<ArrayD<A>>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+    F: Fn(A) -> A,
+{
+    if !valid_labels(labels, weights, scores.ncols()) {
+        return None;
+    }
+    let weight = |label: usize| weights.map_or(A::one(), |w| w[label]);
+    let losses = Zip::from(scores.rows())
+        .and(labels)
+        .map_collect(|row, &label| {
+            let target = row[label];
+            let loss = row.indexed_iter().fold(A::zero(), |acc, (j, &s)| {
+                if j == label {
+                    return acc;
+                }
+                acc + f((margin + s - target).max(A::zero()))
+            });
+            weight(label) * loss
+        });
+    let norm = labels
+        .iter()
+        .fold(A::zero(), |acc, &label| acc + weight(label));
+    Some(reduction.reduce_by(&losses, norm))
+}
+
+/// Computes the gradient of the [binary] margin loss, given the derivative `df` of the
+/// penalty applied to each violation.
+fn binary_grad<A, S, T, F>(
+    scores: &ArrayBase<S, Ix2>,
+    target: &ArrayBase<T, Ix2>,
+    margin: A,
+    weights: Option<&Array1<A>>,
+    reduction: Reduction,
+    df: F,
+) -> Option<Array2<A>>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+    F: Fn(A) -> A,
+{
+    if !valid_weights(weights, scores.ncols()) {
+        return None;
+    }
+    let mut grad = Zip::from(scores).and(target).map_collect(|&s, &y| {
+        let y = if y > A::zero() { A::one() } else { -A::one() };
+        let v = margin - y * s;
+        if v > A::zero() {
+            -y * df(v)
+        } else {
+            A::zero()
+        }
+    });
+    if let Some(w) = weights {
+        grad = grad * w;
+    }
+    let scale = reduction.grad_scale(A::from(scores.nrows()).unwrap());
+    Some(grad * scale)
+}
+
+/// Computes the gradient of the [multiclass] margin loss, given the derivative `df` of the
+/// penalty applied to each violation.
+fn multiclass_grad<A, S, T, F>(
+    scores: &ArrayBase<S, Ix2>,
+    labels: &ArrayBase<T, Ix1>,
+    margin: A,
+    weights: Option<&Array1<A>>,
+    reduction: Reduction,
+    df: F,
+) -> Option<Array2<A>>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+    F: Fn(A) -> A,
+{
+    if !valid_labels(labels, weights, scores.ncols()) {
+        return None;
+    }
+    let weight = |label: usize| weights.map_or(A::one(), |w| w[label]);
+    let norm = labels
+        .iter()
+        .fold(A::zero(), |acc, &label| acc + weight(label));
+    let scale = reduction.grad_scale(norm);
+    let mut grad = Array2::zeros(scores.raw_dim());
+    for ((row, mut g), &label) in scores.rows().into_iter().zip(grad.rows_mut()).zip(labels) {
+        let w = weight(label) * scale;
+        for (j, &s) in row.indexed_iter() {
+            let v = margin + s - row[label];
+            if j != label && v > A::zero() {
+                let d = w * df(v);
+                g[j] = g[j] + d;
+                g[label] = g[label] - d;
+            }
+        }
+    }
+    Some(grad)
+}
+
+/*
+ ************* Implementations *************
+*/
+impl<A, S, T> Loss<ArrayBase<S, Ix2>, ArrayBase<T, Ix2>> for Hinge<A>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    type Output = Option<ArrayD<A>>;
+
+    fn loss(&self, scores: &ArrayBase<S, Ix2>, target: &ArrayBase<T, Ix2>) -> Self::Output {
+        binary(
+            scores,
+            target,
+            self.margin,
+            self.weights.as_ref(),
+            self.reduction,
+            |x| x,
+        )
+    }
+}
+
+impl<A, S, T> Loss<ArrayBase<S, Ix2>, ArrayBase<T, Ix1>> for Hinge<A>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+{
+    type Output = Option<ArrayD<A>>;
+
+    fn loss(&self, scores: &ArrayBase<S, Ix2>, labels: &ArrayBase<T, Ix1>) -> Self::Output {
+        multiclass(
+            scores,
+            labels,
+            self.margin,
+            self.weights.as_ref(),
+            self.reduction,
+            |x| x,
+        )
+    }
+}
+
+impl<A, S, T> Loss<ArrayBase<S, Ix2>, ArrayBase<T, Ix2>> for SquaredHinge<A>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    type Output = Option<ArrayD<A>>;
+
+    fn loss(&self, scores: &ArrayBase<S, Ix2>, target: &ArrayBase<T, Ix2>) -> Self::Output {
+        binary(
+            scores,
+            target,
+            self.margin,
+            self.weights.as_ref(),
+            self.reduction,
+            |x| x * x,
+        )
+    }
+}
+
+impl<A, S, T> Loss<ArrayBase<S, Ix2>, ArrayBase<T, Ix1>> for SquaredHinge<A>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+{
+    type Output = Option<ArrayD<A>>;
+
+    fn loss(&self, scores: &ArrayBase<S, Ix2>, labels: &ArrayBase<T, Ix1>) -> Self::Output {
+        multiclass(
+            scores,
+            labels,
+            self.margin,
+            self.weights.as_ref(),
+            self.reduction,
+            |x| x * x,
+        )
+    }
+}
+
+impl<A, S, T> LossGrad<ArrayBase<S, Ix2>, ArrayBase<T, Ix2>> for Hinge<A>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    type Grad = Option<Array2<A>>;
+
+    fn loss_grad(&self, scores: &ArrayBase<S, Ix2>, target: &ArrayBase<T, Ix2>) -> Self::Grad {
+        binary_grad(
+            scores,
+            target,
+            self.margin,
+            self.weights.as_ref(),
+            self.reduction,
+            |_| A::one(),
+        )
+    }
+}
+
+impl<A, S, T> LossGrad<ArrayBase<S, Ix2>, ArrayBase<T, Ix1>> for Hinge<A>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+{
+    type Grad = Option<Array2<A>>;
+
+    fn loss_grad(&self, scores: &ArrayBase<S, Ix2>, labels: &ArrayBase<T, Ix1>) -> Self::Grad {
+        multiclass_grad(
+            scores,
+            labels,
+            self.margin,
+            self.weights.as_ref(),
+            self.reduction,
+            |_| A::one(),
+        )
+    }
+}
+
+impl<A, S, T> LossGrad<ArrayBase<S, Ix2>, ArrayBase<T, Ix2>> for SquaredHinge<A>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    type Grad = Option<Array2<A>>;
+
+    fn loss_grad(&self, scores: &ArrayBase<S, Ix2>, target: &ArrayBase<T, Ix2>) -> Self::Grad {
+        binary_grad(
+            scores,
+            target,
+            self.margin,
+            self.weights.as_ref(),
+            self.reduction,
+            |x| x + x,
+        )
+    }
+}
+
+impl<A, S, T> LossGrad<ArrayBase<S, Ix2>, ArrayBase<T, Ix1>> for SquaredHinge<A>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+{
+    type Grad = Option<Array2<A>>;
+
+    fn loss_grad(&self, scores: &ArrayBase<S, Ix2>, labels: &ArrayBase<T, Ix1>) -> Self::Grad {
+        multiclass_grad(
+            scores,
+            labels,
+            self.margin,
+            self.weights.as_ref(),
+            self.reduction,
+            |x| x + x,
+        )
+    }
+}
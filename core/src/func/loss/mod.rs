@@ -3,17 +3,24 @@
     Contrib: FL03 <jo3mccain@icloud.com>
 */
 pub use self::reg::prelude::*;
-pub use self::{entropy::*, utils::*};
+pub use self::{divergence::*, entropy::*, margin::*, reduce::Reduction, utils::*};
 
+pub(crate) mod reduce;
 pub(crate) mod utils;
 
+pub mod divergence;
 pub mod entropy;
+pub mod margin;
 pub mod reg;
 
 pub(crate) mod prelude {
+    pub use super::divergence::*;
+    pub use super::entropy::prelude::*;
+    pub use super::margin::*;
+    pub use super::reduce::Reduction;
     pub use super::reg::prelude::*;
     pub use super::utils::*;
-    pub use super::Loss;
+    pub use super::{Loss, LossGrad};
 }
 
 pub trait Loss<A, B = A> {
@@ -21,3 +28,15 @@ pub trait Loss<A, B = A> {
 
     fn loss(&self, a: &A, cmp: &B) -> Self::Output;
 }
+
+/// [LossGrad] computes the gradient of a [Loss] with respect to its first argument, the
+/// prediction, returning an array of the same shape; this may be used to seed the
+/// backpropagation of a model directly.
+///
+/// The gradient accounts for the reduction of the loss. Should the loss be left unreduced,
+/// the gradient is that of the sum of its values, i.e. each loss is seeded with one.
+pub trait LossGrad<A, B = A>: Loss<A, B> {
+    type Grad;
+
+    fn loss_grad(&self, a: &A, cmp: &B) -> Self::Grad;
+}
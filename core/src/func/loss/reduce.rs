@@ -0,0 +1,135 @@
+/*
+    Appellation: reduce <loss>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use nd::prelude::*;
+use nd::{Data, ScalarOperand};
+use num::traits::Float;
+use strum::{AsRefStr, Display, EnumCount, EnumIs, EnumIter, EnumString, VariantNames};
+
+/// [Reduction] determines how the elementwise (or per-sample) values of a loss are combined.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumCount,
+    EnumIs,
+    EnumIter,
+    EnumString,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    VariantNames,
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum Reduction {
+    #[default]
+    Mean,
+    Sum,
+    /// Leaves the values unreduced.
+    None,
+}
+
+impl Reduction {
+    /// Reduces the given values; the result is zero-dimensional unless the reduction is
+    /// [Reduction::None].
+    pub fn reduce<A, S, D>(&self, values: &ArrayBase<S, D>) -> ArrayD<A>
+    where
+        A: Float + ScalarOperand,
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        self.reduce_by(values, A::from(values.len()).unwrap())
+    }
+    /// Reduces the given values, dividing their sum by `norm` rather than their number when
+    /// computing the mean; used by weighted losses to normalize by the total weight.
+    pub fn reduce_by<A, S, D>(&self, values: &ArrayBase<S, D>, norm: A) -> ArrayD<A>
+    where
+        A: Float + ScalarOperand,
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        match self {
+            Reduction::Mean => arr0(values.sum() / norm).into_dyn(),
+            Reduction::Sum => arr0(values.sum()).into_dyn(),
+            Reduction::None => values.to_owned().into_dyn(),
+        }
+    }
+    /// Returns the factor by which the gradient of the values is scaled, where `norm` is the
+    /// normalizer of the mean.
+    pub fn grad_scale<A>(&self, norm: A) -> A
+    where
+        A: Float,
+    {
+        match self {
+            Reduction::Mean => norm.recip(),
+            _ => A::one(),
+        }
+    }
+    /// Computes the gradient of [reduce_weighted](Reduction::reduce_weighted) given that of the
+    /// unweighted values, returning `None` should there not be a weight for each sample.
+    pub fn grad_weighted<A, D>(
+        &self,
+        grad: Array<A, D>,
+        weights: Option<&Array1<A>>,
+    ) -> Option<Array<A, D>>
+    where
+        A: Float + ScalarOperand,
+        D: Dimension,
+    {
+        let weights = match weights {
+            Some(weights) if grad.ndim() > 0 => weights,
+            _ => {
+                let scale = self.grad_scale(A::from(grad.len()).unwrap());
+                return Some(grad * scale);
+            }
+        };
+        if weights.len() != grad.len_of(Axis(0)) {
+            return None;
+        }
+        let per_sample = A::from(grad.len() / grad.len_of(Axis(0)).max(1)).unwrap();
+        let scale = self.grad_scale(weights.sum() * per_sample);
+        let mut grad = grad.into_dyn();
+        for (mut sample, &w) in grad.outer_iter_mut().zip(weights) {
+            sample.mapv_inplace(|x| x * w * scale);
+        }
+        grad.into_dimensionality::<D>().ok()
+    }
+    /// Scales the values of each sample, i.e. each index of the first axis, by its weight
+    /// before reducing them; the weighted mean is normalized by the total weight. Returns
+    /// `None` should there not be a weight for each sample.
+    pub fn reduce_weighted<A, S, D>(
+        &self,
+        values: &ArrayBase<S, D>,
+        weights: Option<&Array1<A>>,
+    ) -> Option<ArrayD<A>>
+    where
+        A: Float + ScalarOperand,
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        let weights = match weights {
+            Some(weights) if values.ndim() > 0 => weights,
+            _ => return Some(self.reduce(values)),
+        };
+        if weights.len() != values.len_of(Axis(0)) {
+            return None;
+        }
+        let mut values = values.to_owned().into_dyn();
+        for (mut sample, &w) in values.outer_iter_mut().zip(weights) {
+            sample.mapv_inplace(|x| x * w);
+        }
+        let per_sample = A::from(values.len() / values.len_of(Axis(0)).max(1)).unwrap();
+        Some(self.reduce_by(&values, weights.sum() * per_sample))
+    }
+}
@@ -4,10 +4,17 @@
 */
 //! # Regressive Loss Functions
 //!
-//!
+//! In addition to the mean absolute and squared errors, this module provides a number of
+//! [robust](robust) losses which may be configured with a [Reduction](crate::func::Reduction)
+//! and per-sample weights, as well as the [penalties](penalty) used to regularize the
+//! parameters of a model.
 
 pub mod avg;
+pub mod penalty;
+pub mod robust;
 
 pub(crate) mod prelude {
     pub use super::avg::*;
+    pub use super::penalty::*;
+    pub use super::robust::*;
 }
@@ -2,10 +2,11 @@
     Appellation: avg <module>
     Contrib: FL03 <jo3mccain@icloud.com>
 */
+use crate::func::LossGrad;
 use crate::math::{Abs, Squared};
 use nd::prelude::*;
 use nd::{Data, ScalarOperand};
-use num::traits::{FromPrimitive, Num, Pow, Signed};
+use num::traits::{Float, FromPrimitive, Num, Pow, Signed};
 
 pub trait MeanAbsoluteError<Rhs = Self> {
     type Output;
@@ -63,3 +64,37 @@ where
         (target - self).sqrd().mean()
     }
 }
+
+impl<A, S, D> LossGrad<ArrayBase<S, D>, ArrayBase<S, D>> for MAE
+where
+    A: Float + FromPrimitive + ScalarOperand + Signed,
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    type Grad = Array<A, D>;
+
+    fn loss_grad(&self, pred: &ArrayBase<S, D>, target: &ArrayBase<S, D>) -> Self::Grad {
+        let n = A::from(pred.len()).unwrap();
+        (pred - target).mapv(|d| {
+            if d.is_zero() {
+                A::zero()
+            } else {
+                d.signum() / n
+            }
+        })
+    }
+}
+
+impl<A, S, D> LossGrad<ArrayBase<S, D>, ArrayBase<S, D>> for MSE
+where
+    A: Float + FromPrimitive + Pow<i32, Output = A> + ScalarOperand,
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    type Grad = Array<A, D>;
+
+    fn loss_grad(&self, pred: &ArrayBase<S, D>, target: &ArrayBase<S, D>) -> Self::Grad {
+        let scale = A::from(2).unwrap() / A::from(pred.len()).unwrap();
+        (pred - target) * scale
+    }
+}
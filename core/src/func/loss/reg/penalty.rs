@@ -0,0 +1,219 @@
+/*
+    Appellation: penalty <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+//! # Weight Penalties
+//!
+//! Penalties regularize a model by discouraging certain configurations of its parameters;
+//! each is computed over a single tensor alongside its gradient, allowing it to be added to
+//! the loss or, through [Regularized](crate::nn::optim::Regularized), to the gradients of an
+//! optimizer.
+use nd::prelude::*;
+use nd::{Data, ScalarOperand};
+use num::traits::Float;
+
+/// [Penalty] describes a regularization term computed over a tensor of parameters.
+pub trait Penalty<A> {
+    fn penalty<S, D>(&self, params: &ArrayBase<S, D>) -> A
+    where
+        D: Dimension,
+        S: Data<Elem = A>;
+    /// Computes the gradient of the [penalty](Penalty::penalty) with respect to the parameters.
+    fn penalty_grad<S, D>(&self, params: &ArrayBase<S, D>) -> Array<A, D>
+    where
+        D: Dimension,
+        S: Data<Elem = A>;
+}
+
+/// [L1] penalizes the sum of the absolute values of the parameters, `lambda * sum(|w|)`,
+/// encouraging sparse solutions; the subgradient at zero is taken to be zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct L1<A = f64> {
+    pub lambda: A,
+}
+
+/// [L2] penalizes the squared norm of the parameters, `lambda / 2 * sum(w^2)`, such that its
+/// gradient is `lambda * w`; this is the weight decay of coupled optimizers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct L2<A = f64> {
+    pub lambda: A,
+}
+
+/// [ElasticNet] combines the [L1] and [L2] penalties, `l1 * sum(|w|) + l2 / 2 * sum(w^2)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct ElasticNet<A = f64> {
+    pub l1: A,
+    pub l2: A,
+}
+
+/// [Orthogonal] penalizes the deviation of the rows of a weight matrix from an orthonormal
+/// set, `lambda * ||W W^T - I||^2` under the Frobenius norm.
+///
+/// Tensors of higher dimension are treated as a matrix with a row for each index of their
+/// first axis, while those of fewer than two dimensions are not penalized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct Orthogonal<A = f64> {
+    pub lambda: A,
+}
+
+/// Flattens the parameters into a matrix with a row for each index of the first axis.
+fn rows<A, S, D>(params: &ArrayBase<S, D>) -> Array2<A>
+where
+    A: Clone,
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    let rows = params.len_of(Axis(0));
+    let cols = params.len() / rows.max(1);
+    Array2::from_shape_vec((rows, cols), params.iter().cloned().collect()).unwrap()
+}
+
+/*
+ ************* Implementations *************
+*/
+impl<A> L1<A> {
+    pub fn new(lambda: A) -> Self {
+        Self { lambda }
+    }
+}
+
+impl<A> L2<A> {
+    pub fn new(lambda: A) -> Self {
+        Self { lambda }
+    }
+}
+
+impl<A> ElasticNet<A> {
+    pub fn new(l1: A, l2: A) -> Self {
+        Self { l1, l2 }
+    }
+}
+
+impl<A> Orthogonal<A> {
+    pub fn new(lambda: A) -> Self {
+        Self { lambda }
+    }
+}
+
+impl<A> Penalty<A> for L1<A>
+where
+    A: Float,
+{
+    fn penalty<S, D>(&self, params: &ArrayBase<S, D>) -> A
+    where
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        self.lambda * params.fold(A::zero(), |acc, w| acc + w.abs())
+    }
+
+    fn penalty_grad<S, D>(&self, params: &ArrayBase<S, D>) -> Array<A, D>
+    where
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        params.mapv(|w| {
+            if w.is_zero() {
+                A::zero()
+            } else {
+                self.lambda * w.signum()
+            }
+        })
+    }
+}
+
+impl<A> Penalty<A> for L2<A>
+where
+    A: Float,
+{
+    fn penalty<S, D>(&self, params: &ArrayBase<S, D>) -> A
+    where
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        let half = A::from(0.5).unwrap();
+        half * self.lambda * params.fold(A::zero(), |acc, &w| acc + w * w)
+    }
+
+    fn penalty_grad<S, D>(&self, params: &ArrayBase<S, D>) -> Array<A, D>
+    where
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        params.mapv(|w| self.lambda * w)
+    }
+}
+
+impl<A> Penalty<A> for ElasticNet<A>
+where
+    A: Float,
+{
+    fn penalty<S, D>(&self, params: &ArrayBase<S, D>) -> A
+    where
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        L1::new(self.l1).penalty(params) + L2::new(self.l2).penalty(params)
+    }
+
+    fn penalty_grad<S, D>(&self, params: &ArrayBase<S, D>) -> Array<A, D>
+    where
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        L1::new(self.l1).penalty_grad(params) + L2::new(self.l2).penalty_grad(params)
+    }
+}
+
+impl<A> Penalty<A> for Orthogonal<A>
+where
+    A: Float + ScalarOperand,
+{
+    fn penalty<S, D>(&self, params: &ArrayBase<S, D>) -> A
+    where
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        if params.ndim() < 2 {
+            return A::zero();
+        }
+        let w = rows(params);
+        let gram = w.dot(&w.t()) - Array2::eye(w.nrows());
+        self.lambda * gram.fold(A::zero(), |acc, &x| acc + x * x)
+    }
+    /// The gradient of the penalty is `4 * lambda * (W W^T - I) W`.
+    fn penalty_grad<S, D>(&self, params: &ArrayBase<S, D>) -> Array<A, D>
+    where
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        if params.ndim() < 2 {
+            return Array::zeros(params.raw_dim());
+        }
+        let w = rows(params);
+        let gram = w.dot(&w.t()) - Array2::eye(w.nrows());
+        let four = A::from(4).unwrap();
+        let grad = gram.dot(&w) * (four * self.lambda);
+        Array::from_shape_vec(params.raw_dim(), grad.into_raw_vec()).unwrap()
+    }
+}
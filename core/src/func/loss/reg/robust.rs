@@ -0,0 +1,161 @@
+/*
+    Appellation: robust <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+//! # Robust Regression Losses
+//!
+//! Each loss is computed elementwise before being combined according to its
+//! [Reduction](crate::func::Reduction). Optional per-sample weights, such as the weights of a
+//! dataset, scale the losses of each index along the first axis of the predictions.
+use crate::func::activate::softplus;
+use crate::func::loss::{Loss, LossGrad};
+use nd::prelude::*;
+use nd::{Data, ScalarOperand, Zip};
+use num::traits::Float;
+
+/// Implements the elementwise [Loss] and [LossGrad] of the given losses from the loss (`f`)
+/// and derivative (`df`) of a single prediction `p` and target `y`.
+macro_rules! robust {
+    ($($name:ident => |$this:ident, $p:ident, $y:ident| $f:expr, $df:expr);* $(;)?) => {
+        $(
+            impl<A> $name<A>
+            where
+                A: Float,
+            {
+                /// Computes the loss using the given sample weights in place of those
+                /// configured, returning [None] if there is not a weight for each index of the
+                /// first axis.
+                pub fn weighted_loss<S, T, D>(
+                    &self,
+                    pred: &ArrayBase<S, D>,
+                    target: &ArrayBase<T, D>,
+                    weights: Option<&Array1<A>>,
+                ) -> Option<ArrayD<A>>
+                where
+                    A: ScalarOperand,
+                    D: Dimension,
+                    S: Data<Elem = A>,
+                    T: Data<Elem = A>,
+                {
+                    let $this = self;
+                    let losses = Zip::from(pred).and(target).map_collect(|&$p, &$y| $f);
+                    self.reduction.reduce_weighted(&losses, weights)
+                }
+            }
+
+            impl<A, S, T, D> Loss<ArrayBase<S, D>, ArrayBase<T, D>> for $name<A>
+            where
+                A: Float + ScalarOperand,
+                D: Dimension,
+                S: Data<Elem = A>,
+                T: Data<Elem = A>,
+            {
+                type Output = Option<ArrayD<A>>;
+
+                fn loss(&self, pred: &ArrayBase<S, D>, target: &ArrayBase<T, D>) -> Self::Output {
+                    self.weighted_loss(pred, target, self.weights.as_ref())
+                }
+            }
+
+            impl<A, S, T, D> LossGrad<ArrayBase<S, D>, ArrayBase<T, D>> for $name<A>
+            where
+                A: Float + ScalarOperand,
+                D: Dimension,
+                S: Data<Elem = A>,
+                T: Data<Elem = A>,
+            {
+                type Grad = Option<Array<A, D>>;
+
+                fn loss_grad(&self, pred: &ArrayBase<S, D>, target: &ArrayBase<T, D>) -> Self::Grad {
+                    let $this = self;
+                    let grad = Zip::from(pred).and(target).map_collect(|&$p, &$y| $df);
+                    self.reduction.grad_weighted(grad, self.weights.as_ref())
+                }
+            }
+        )*
+    };
+}
+
+losses! {
+    /// [Huber] is quadratic for residuals within `delta` of zero and linear beyond, limiting
+    /// the influence of outliers.
+    Huber<sample>(delta: A::one()) {},
+    /// [SmoothL1] is the [Huber] loss scaled by `1 / beta`, such that it approaches the
+    /// absolute error as `beta` tends to zero.
+    SmoothL1<sample>(beta: A::one()) {},
+    /// [LogCosh] is the logarithm of the hyperbolic cosine of the residual; it behaves like
+    /// the squared error for small residuals and the absolute error for large ones.
+    LogCosh<sample> {},
+    /// [Quantile], or pinball, loss penalizes under-predictions by `quantile` and
+    /// over-predictions by `1 - quantile`; its minimizer is the corresponding quantile of the
+    /// targets, making it suitable for predicting intervals. The default is the median, for
+    /// which the loss is half the absolute error.
+    Quantile<sample>(quantile: A::from(0.5).unwrap()) {},
+    /// [MSLE] is the squared error between the logarithms of one plus the predictions and
+    /// targets, emphasizing relative rather than absolute errors.
+    MSLE<sample> {},
+}
+
+robust! {
+    Huber => |this, p, y| {
+        let r = (p - y).abs();
+        let half = A::from(0.5).unwrap();
+        if r <= this.delta {
+            half * r * r
+        } else {
+            this.delta * (r - half * this.delta)
+        }
+    }, {
+        let r = p - y;
+        if r.abs() <= this.delta {
+            r
+        } else {
+            this.delta * r.signum()
+        }
+    };
+    SmoothL1 => |this, p, y| {
+        let r = (p - y).abs();
+        let half = A::from(0.5).unwrap();
+        if r < this.beta {
+            half * r * r / this.beta
+        } else {
+            r - half * this.beta
+        }
+    }, {
+        let r = p - y;
+        if r.abs() < this.beta {
+            r / this.beta
+        } else if r.is_zero() {
+            A::zero()
+        } else {
+            r.signum()
+        }
+    };
+    LogCosh => |_this, p, y| {
+        // ln(cosh(r)) = |r| + ln(1 + exp(-2|r|)) - ln(2)
+        let r = (p - y).abs();
+        r + softplus(-(r + r)) - A::from(core::f64::consts::LN_2).unwrap()
+    }, (p - y).tanh();
+    Quantile => |this, p, y| {
+        let r = y - p;
+        (this.quantile * r).max((this.quantile - A::one()) * r)
+    }, {
+        if y > p {
+            -this.quantile
+        } else if y < p {
+            A::one() - this.quantile
+        } else {
+            A::zero()
+        }
+    };
+    MSLE => |_this, p, y| {
+        let d = p.ln_1p() - y.ln_1p();
+        d * d
+    }, {
+        let d = p.ln_1p() - y.ln_1p();
+        (d + d) / (A::one() + p)
+    };
+}
+
+/// [Pinball] is an alias for the [Quantile] loss.
+pub type Pinball<A = f64> = Quantile<A>;
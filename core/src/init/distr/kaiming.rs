@@ -0,0 +1,147 @@
+/*
+    Appellation: kaiming <distr>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+//! # Kaiming
+//!
+//! Kaiming (or He) initialization, developed in 2015 by Kaiming He et al., preserves the
+//! variance of the activations of rectified networks by scaling the weights according to the
+//! number of units feeding into (or out of) each layer. The gain compensates for the effect
+//! of the nonlinearity; see [Activation::gain](crate::func::activate::Activation::gain).
+use num::Float;
+use rand::Rng;
+use rand_distr::uniform::{SampleUniform, Uniform};
+use rand_distr::{Distribution, Normal, NormalError, StandardNormal};
+use strum::{AsRefStr, Display, EnumCount, EnumIs, EnumIter, EnumString, VariantNames};
+
+/// [FanMode] determines whether the variance of the weights is preserved in the forward
+/// pass, by scaling with the number of inputs, or in the backward pass, by scaling with the
+/// number of outputs.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumCount,
+    EnumIs,
+    EnumIter,
+    EnumString,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    VariantNames,
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum FanMode {
+    #[default]
+    FanIn,
+    FanOut,
+}
+
+/// Normal Kaiming initializers sample from a normal distribution centered at 0 with a standard
+/// deviation of `σ = gain / sqrt(fan)`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct KaimingNormal<F>
+where
+    F: Float,
+    StandardNormal: Distribution<F>,
+{
+    std: F,
+}
+
+/// Uniform Kaiming initializers sample from the range `(-b, b)`, where the boundary
+/// `b = gain * sqrt(3 / fan)` yields the same variance as [KaimingNormal].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct KaimingUniform<X>
+where
+    X: SampleUniform,
+{
+    boundary: X,
+}
+
+/*
+ ************* Implementations *************
+*/
+impl FanMode {
+    /// Selects the fan of the given `(fan_in, fan_out)` pair; see [fans](crate::init::fans).
+    pub fn select(&self, (fan_in, fan_out): (usize, usize)) -> usize {
+        match self {
+            FanMode::FanIn => fan_in,
+            FanMode::FanOut => fan_out,
+        }
+    }
+}
+
+impl<F> KaimingNormal<F>
+where
+    F: Float,
+    StandardNormal: Distribution<F>,
+{
+    pub fn new(fan: usize, gain: F) -> Self {
+        Self {
+            std: gain / F::from(fan).unwrap().sqrt(),
+        }
+    }
+
+    pub fn distr(&self) -> Result<Normal<F>, NormalError> {
+        Normal::new(F::zero(), self.std_dev())
+    }
+
+    pub fn std_dev(&self) -> F {
+        self.std
+    }
+}
+
+impl<F> Distribution<F> for KaimingNormal<F>
+where
+    F: Float,
+    StandardNormal: Distribution<F>,
+{
+    fn sample<R>(&self, rng: &mut R) -> F
+    where
+        R: Rng + ?Sized,
+    {
+        self.distr().unwrap().sample(rng)
+    }
+}
+
+impl<X> KaimingUniform<X>
+where
+    X: Float + SampleUniform,
+{
+    pub fn new(fan: usize, gain: X) -> Self {
+        Self {
+            boundary: gain * (X::from(3).unwrap() / X::from(fan).unwrap()).sqrt(),
+        }
+    }
+
+    pub fn boundary(&self) -> X {
+        self.boundary
+    }
+
+    pub fn distr(&self) -> Uniform<X> {
+        let bnd = self.boundary();
+        Uniform::new(-bnd, bnd)
+    }
+}
+
+impl<X> Distribution<X> for KaimingUniform<X>
+where
+    X: Float + SampleUniform,
+{
+    fn sample<R>(&self, rng: &mut R) -> X
+    where
+        R: Rng + ?Sized,
+    {
+        self.distr().sample(rng)
+    }
+}
@@ -22,8 +22,12 @@ where
     F: Float,
     StandardNormal: Distribution<F>,
 {
-    /// Create a new truncated normal distribution with a given mean and standard deviation
+    /// Create a new truncated normal distribution with a given mean and standard deviation;
+    /// the standard deviation must be finite and non-negative.
     pub fn new(mean: F, std: F) -> Result<Self, NormalError> {
+        if !std.is_finite() || std < F::zero() {
+            return Err(NormalError::BadVariance);
+        }
         Ok(Self { mean, std })
     }
 
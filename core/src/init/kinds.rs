@@ -0,0 +1,156 @@
+/*
+    Appellation: kinds <init>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::distr::*;
+use super::{fans, orthogonal, Initialize};
+use nd::{Array, Dimension, ScalarOperand, ShapeBuilder};
+use num::traits::Float;
+use rand::Rng;
+use rand_distr::uniform::{SampleUniform, Uniform};
+use rand_distr::{Distribution, Normal, NormalError, StandardNormal};
+use strum::{AsRefStr, Display, EnumCount, EnumIs, EnumIter, EnumString, VariantNames};
+
+/// [Init] enumerates the strategies used to initialize the parameters of a model, enabling
+/// them to be selected from formats such as JSON or TOML, e.g. `"zeros"` or
+/// `{ "kaiming": { "mode": "fan_out" } }`.
+///
+/// Strategies depending on the size of a layer do so through the `(fan_in, fan_out)` of its
+/// weights; see [fans]. Unless a bound is given, [uniform](Init::Uniform) samples from
+/// `(-b, b)` where `b = 1 / sqrt(fan_in)`, while the gain of the [kaiming](Init::Kaiming)
+/// scheme defaults to that of the ReLU, `sqrt(2)`, and that of the
+/// [orthogonal](Init::Orthogonal) scheme to one. Much like a negative standard deviation, a
+/// bound which isn't positive is rejected with [NormalError::BadVariance].
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumCount,
+    EnumIs,
+    EnumIter,
+    EnumString,
+    PartialEq,
+    PartialOrd,
+    VariantNames,
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum Init {
+    #[default]
+    Zeros,
+    Ones,
+    Uniform {
+        #[cfg_attr(feature = "serde", serde(default))]
+        bound: Option<f64>,
+    },
+    Normal {
+        mean: f64,
+        std: f64,
+    },
+    #[cfg_attr(feature = "serde", serde(rename = "trunc_normal"))]
+    #[strum(serialize = "trunc_normal")]
+    TruncNormal {
+        mean: f64,
+        std: f64,
+    },
+    Lecun,
+    Xavier {
+        #[cfg_attr(feature = "serde", serde(default))]
+        uniform: bool,
+    },
+    Kaiming {
+        #[cfg_attr(feature = "serde", serde(default))]
+        mode: FanMode,
+        #[cfg_attr(feature = "serde", serde(default))]
+        gain: Option<f64>,
+        #[cfg_attr(feature = "serde", serde(default))]
+        uniform: bool,
+    },
+    Orthogonal {
+        #[cfg_attr(feature = "serde", serde(default))]
+        gain: Option<f64>,
+    },
+}
+
+/*
+ ************* Implementations *************
+*/
+impl Init {
+    /// Generates an array of the given shape, computing the fans from the shape itself.
+    pub fn array<A, D, Sh>(&self, shape: Sh) -> Result<Array<A, D>, NormalError>
+    where
+        A: Float + SampleUniform + ScalarOperand,
+        D: Dimension,
+        Sh: ShapeBuilder<Dim = D>,
+        StandardNormal: Distribution<A>,
+        <A as SampleUniform>::Sampler: Clone,
+    {
+        let dim = shape.into_shape().raw_dim().clone();
+        let fans = fans(dim.slice());
+        self.array_with(dim, fans, &mut rand::thread_rng())
+    }
+    /// Generates an array of the given shape using the `(fan_in, fan_out)` of the weights it
+    /// belongs to; biases, for instance, are scaled according to the fans of their layer.
+    pub fn array_with<A, D, Sh, R>(
+        &self,
+        shape: Sh,
+        (fan_in, fan_out): (usize, usize),
+        rng: &mut R,
+    ) -> Result<Array<A, D>, NormalError>
+    where
+        A: Float + SampleUniform + ScalarOperand,
+        D: Dimension,
+        R: Rng + ?Sized,
+        Sh: ShapeBuilder<Dim = D>,
+        StandardNormal: Distribution<A>,
+        <A as SampleUniform>::Sampler: Clone,
+    {
+        let c = |x: f64| A::from(x).unwrap();
+        let res = match *self {
+            Init::Zeros => Array::zeros(shape),
+            Init::Ones => Array::ones(shape),
+            Init::Uniform { bound } => {
+                let bnd = bound.unwrap_or_else(|| (fan_in as f64).recip().sqrt());
+                if !(bnd > 0.0 && bnd.is_finite()) {
+                    return Err(NormalError::BadVariance);
+                }
+                Array::rand_with(shape, Uniform::new(c(-bnd), c(bnd)), rng)
+            }
+            Init::Normal { mean, std } => {
+                Array::rand_with(shape, Normal::new(c(mean), c(std))?, rng)
+            }
+            Init::TruncNormal { mean, std } => {
+                Array::rand_with(shape, TruncatedNormal::new(c(mean), c(std))?, rng)
+            }
+            Init::Lecun => Array::rand_with(shape, LecunNormal::new(fan_in), rng),
+            Init::Xavier { uniform: true } => {
+                Array::rand_with(shape, XavierUniform::new(fan_in, fan_out), rng)
+            }
+            Init::Xavier { uniform: false } => {
+                Array::rand_with(shape, XavierNormal::new(fan_in, fan_out), rng)
+            }
+            Init::Kaiming {
+                mode,
+                gain,
+                uniform,
+            } => {
+                let fan = mode.select((fan_in, fan_out));
+                let gain = c(gain.unwrap_or(core::f64::consts::SQRT_2));
+                if uniform {
+                    Array::rand_with(shape, KaimingUniform::new(fan, gain), rng)
+                } else {
+                    Array::rand_with(shape, KaimingNormal::new(fan, gain), rng)
+                }
+            }
+            Init::Orthogonal { gain } => orthogonal(shape, c(gain.unwrap_or(1.0)), rng),
+        };
+        Ok(res)
+    }
+}
@@ -12,9 +12,13 @@
 #![cfg(feature = "rand")]
 
 pub use self::distr::prelude::*;
+pub use self::kinds::*;
+pub use self::rng::RngContext;
 pub use self::traits::*;
 pub use self::utils::*;
 
+pub(crate) mod kinds;
+pub(crate) mod rng;
 pub(crate) mod traits;
 pub(crate) mod utils;
 
@@ -23,11 +27,13 @@ pub mod initializer;
 pub mod distr {
     pub use self::prelude::*;
 
+    pub mod kaiming;
     pub mod lecun;
     pub mod trunc;
     pub mod xavier;
 
     pub(crate) mod prelude {
+        pub use super::kaiming::*;
         pub use super::lecun::*;
         pub use super::trunc::*;
         pub use super::xavier::*;
@@ -43,6 +49,8 @@ pub use rand_distr;
 
 pub(crate) mod prelude {
     pub use super::distr::prelude::*;
+    pub use super::kinds::Init;
+    pub use super::rng::RngContext;
     pub use super::traits::{Initialize, InitializeExt};
     pub use super::utils::*;
 }
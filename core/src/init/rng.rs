@@ -0,0 +1,87 @@
+/*
+    Appellation: rng <init>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+/// [RngContext] is a seedable, [StdRng]-backed generator from which every source of randomness
+/// of a model, e.g. its initializers, dropout layers or the shuffling of its dataset, may be
+/// drawn, making an entire run reproducible from a single seed.
+///
+/// Rather than sharing one stream, each layer should be given its own [fork](RngContext::fork);
+/// the seed of a fork depends only on the seed of its parent and the number of forks preceding
+/// it, so the values drawn by one layer are unaffected by how many values another consumes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RngContext {
+    forks: u64,
+    rng: StdRng,
+    seed: u64,
+}
+
+/// The finalizer of the SplitMix64 generator, used to derive the seeds of forks.
+fn splitmix(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/*
+ ************* Implementations *************
+*/
+impl RngContext {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            forks: 0,
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+        }
+    }
+    /// Creates a new context from a seed drawn from the thread-local generator.
+    pub fn from_entropy() -> Self {
+        Self::new(rand::thread_rng().gen())
+    }
+    /// Returns the seed the context was created with.
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+    /// Returns the number of times the context has been forked.
+    pub const fn forks(&self) -> u64 {
+        self.forks
+    }
+    /// Creates a new, independent context whose seed is derived from that of this context and
+    /// the number of forks preceding it.
+    pub fn fork(&mut self) -> Self {
+        self.forks += 1;
+        Self::new(splitmix(self.seed ^ splitmix(self.forks)))
+    }
+
+    pub fn rng_mut(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+}
+
+impl From<u64> for RngContext {
+    fn from(seed: u64) -> Self {
+        Self::new(seed)
+    }
+}
+
+impl RngCore for RngContext {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
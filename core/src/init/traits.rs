@@ -3,9 +3,10 @@
     Contrib: FL03 <jo3mccain@icloud.com>
 */
 use crate::init::distr::*;
+use crate::init::{fans, identity, orthogonal, sparse};
 
 use core::ops::Neg;
-use nd::{ArrayBase, DataOwned, Dimension, RawData, ShapeBuilder};
+use nd::{Array, ArrayBase, DataOwned, Dimension, RawData, ScalarOperand, ShapeBuilder};
 use ndrand::RandomExt;
 use num::complex::ComplexDistribution;
 use num::traits::Float;
@@ -65,6 +66,74 @@ where
         let dist = Bernoulli::new(p)?;
         Ok(Self::rand(shape, dist))
     }
+    /// Create an array filled with the given value.
+    fn constant<Sh>(shape: Sh, value: A) -> Self
+    where
+        S: DataOwned,
+        Sh: ShapeBuilder<Dim = D>,
+        Self: From<Array<A, D>>,
+    {
+        Array::from_elem(shape, value).into()
+    }
+    /// Create an array with ones wherever its first two indices coincide; see [identity](crate::init::identity).
+    fn identity<Sh>(shape: Sh) -> Self
+    where
+        A: num::One + num::Zero,
+        S: DataOwned,
+        Sh: ShapeBuilder<Dim = D>,
+        Self: From<Array<A, D>>,
+    {
+        identity(shape).into()
+    }
+    /// Initialize the object according to the normal Kaiming (He) initialization scheme; the
+    /// fans are computed from the shape, assuming a layout of `(outputs, inputs, ...)`.
+    fn kaiming_normal<Sh>(shape: Sh, mode: FanMode, gain: A) -> Self
+    where
+        A: Float,
+        S: DataOwned,
+        Sh: ShapeBuilder<Dim = D>,
+        StandardNormal: Distribution<A>,
+    {
+        Self::kaiming_normal_with(shape, mode, gain, &mut rand::thread_rng())
+    }
+    /// Initialize the object according to the normal Kaiming (He) initialization scheme,
+    /// drawing from the given generator.
+    fn kaiming_normal_with<Sh, R>(shape: Sh, mode: FanMode, gain: A, rng: &mut R) -> Self
+    where
+        A: Float,
+        R: Rng + ?Sized,
+        S: DataOwned,
+        Sh: ShapeBuilder<Dim = D>,
+        StandardNormal: Distribution<A>,
+    {
+        let dim = shape.into_shape().raw_dim().clone();
+        let fan = mode.select(fans(dim.slice()));
+        Self::rand_with(dim, KaimingNormal::new(fan, gain), rng)
+    }
+    /// Initialize the object according to the uniform Kaiming (He) initialization scheme.
+    fn kaiming_uniform<Sh>(shape: Sh, mode: FanMode, gain: A) -> Self
+    where
+        A: Float + SampleUniform,
+        S: DataOwned,
+        Sh: ShapeBuilder<Dim = D>,
+        <A as SampleUniform>::Sampler: Clone,
+    {
+        Self::kaiming_uniform_with(shape, mode, gain, &mut rand::thread_rng())
+    }
+    /// Initialize the object according to the uniform Kaiming (He) initialization scheme,
+    /// drawing from the given generator.
+    fn kaiming_uniform_with<Sh, R>(shape: Sh, mode: FanMode, gain: A, rng: &mut R) -> Self
+    where
+        A: Float + SampleUniform,
+        R: Rng + ?Sized,
+        S: DataOwned,
+        Sh: ShapeBuilder<Dim = D>,
+        <A as SampleUniform>::Sampler: Clone,
+    {
+        let dim = shape.into_shape().raw_dim().clone();
+        let fan = mode.select(fans(dim.slice()));
+        Self::rand_with(dim, KaimingUniform::new(fan, gain), rng)
+    }
     /// Initialize the object according to the Lecun Initialization scheme.
     /// LecunNormal distributions are truncated [Normal](rand_distr::Normal)
     /// distributions centered at 0 with a standard deviation equal to the
@@ -90,6 +159,30 @@ where
         let distr = Normal::new(mean, std)?;
         Ok(Self::rand(shape, distr))
     }
+    /// Generate a (semi-)orthogonal array, scaled by `gain`, from the QR decomposition of a
+    /// Gaussian matrix; see [orthogonal](crate::init::orthogonal).
+    fn orthogonal<Sh>(shape: Sh, gain: A) -> Self
+    where
+        A: Float + ScalarOperand,
+        S: DataOwned,
+        Sh: ShapeBuilder<Dim = D>,
+        StandardNormal: Distribution<A>,
+        Self: From<Array<A, D>>,
+    {
+        Self::orthogonal_with(shape, gain, &mut rand::thread_rng())
+    }
+    /// Generate a (semi-)orthogonal array, scaled by `gain`, drawing from the given generator.
+    fn orthogonal_with<Sh, R>(shape: Sh, gain: A, rng: &mut R) -> Self
+    where
+        A: Float + ScalarOperand,
+        R: Rng + ?Sized,
+        S: DataOwned,
+        Sh: ShapeBuilder<Dim = D>,
+        StandardNormal: Distribution<A>,
+        Self: From<Array<A, D>>,
+    {
+        orthogonal(shape, gain, rng).into()
+    }
 
     fn randc<Sh>(shape: Sh, re: A, im: A) -> Self
     where
@@ -100,6 +193,35 @@ where
         let distr = ComplexDistribution::new(re, im);
         Self::rand(shape, distr)
     }
+    /// Generate a sparse array, zeroing the given proportion of each column while drawing the
+    /// remaining elements from `N(0, std)`; see [sparse](crate::init::sparse).
+    fn sparse<Sh>(shape: Sh, sparsity: f64, std: A) -> Result<Self, NormalError>
+    where
+        A: Float,
+        S: DataOwned,
+        Sh: ShapeBuilder<Dim = D>,
+        StandardNormal: Distribution<A>,
+        Self: From<Array<A, D>>,
+    {
+        Self::sparse_with(shape, sparsity, std, &mut rand::thread_rng())
+    }
+    /// Generate a sparse array, drawing from the given generator.
+    fn sparse_with<Sh, R>(
+        shape: Sh,
+        sparsity: f64,
+        std: A,
+        rng: &mut R,
+    ) -> Result<Self, NormalError>
+    where
+        A: Float,
+        R: Rng + ?Sized,
+        S: DataOwned,
+        Sh: ShapeBuilder<Dim = D>,
+        StandardNormal: Distribution<A>,
+        Self: From<Array<A, D>>,
+    {
+        sparse(shape, sparsity, std, rng).map(Self::from)
+    }
     /// Generate a random array using the [StandardNormal](rand_distr::StandardNormal) distribution
     fn stdnorm<Sh>(shape: Sh) -> Self
     where
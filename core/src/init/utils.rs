@@ -2,14 +2,18 @@
    Appellation: utils <mod>
    Contrib: FL03 <jo3mccain@icloud.com>
 */
+use crate::math::linalg::qr;
+use crate::rust::Vec;
 use ndarray::*;
 use ndrand::RandomExt;
 use num::complex::{Complex, ComplexDistribution};
+use num::traits::Float;
 use num::Num;
 use rand::distributions::uniform::{SampleUniform, Uniform};
 use rand::rngs::StdRng;
-use rand::{rngs, SeedableRng};
-use rand_distr::{Distribution, StandardNormal};
+use rand::seq::SliceRandom;
+use rand::{rngs, Rng, SeedableRng};
+use rand_distr::{Distribution, Normal, NormalError, StandardNormal};
 
 /// Generate a random array of complex numbers with real and imaginary parts in the range [0, 1)
 pub fn randc<A, S, D>(shape: impl IntoDimension<Dim = D>) -> ArrayBase<S, D>
@@ -60,3 +64,100 @@ where
         &mut rngs::StdRng::seed_from_u64(key),
     )
 }
+/// Computes the `(fan_in, fan_out)` of a weight tensor laid out as `(outputs, inputs, ...)`;
+/// any trailing axes, such as those of a convolutional kernel, contribute to both.
+pub fn fans(shape: &[usize]) -> (usize, usize) {
+    match shape.len() {
+        0 => (1, 1),
+        1 => (shape[0], shape[0]),
+        _ => {
+            let receptive = shape[2..].iter().product::<usize>();
+            (shape[1] * receptive, shape[0] * receptive)
+        }
+    }
+}
+/// Creates an array with ones wherever its first two indices coincide; one dimensional arrays
+/// are filled with ones.
+pub fn identity<A, D, Sh>(shape: Sh) -> Array<A, D>
+where
+    A: Clone + num::One + num::Zero,
+    D: Dimension,
+    Sh: ShapeBuilder<Dim = D>,
+{
+    Array::from_shape_fn(shape, |idx| {
+        let idx = idx.into_dimension();
+        if idx.ndim() < 2 || idx[0] == idx[1] {
+            A::one()
+        } else {
+            A::zero()
+        }
+    })
+}
+/// Creates a (semi-)orthogonal array, scaled by `gain`, from the QR decomposition of a
+/// Gaussian matrix.
+///
+/// Arrays of more than two dimensions are treated as a matrix with a row for each index of
+/// their first axis; the rows are orthonormal if they are no more numerous than the columns,
+/// and the columns otherwise.
+pub fn orthogonal<A, D, Sh, R>(shape: Sh, gain: A, rng: &mut R) -> Array<A, D>
+where
+    A: Float + ScalarOperand,
+    D: Dimension,
+    R: Rng + ?Sized,
+    Sh: ShapeBuilder<Dim = D>,
+    StandardNormal: Distribution<A>,
+{
+    let dim = shape.into_shape().raw_dim().clone();
+    let (rows, cols) = matrix(&dim);
+    let gaussian = Array2::random_using((rows.max(cols), rows.min(cols)), StandardNormal, rng);
+    let (mut q, r) = qr(&gaussian);
+    // fixing the signs of the diagonal of R makes the distribution of Q uniform
+    for (mut col, &d) in q.columns_mut().into_iter().zip(r.diag()) {
+        if d < A::zero() {
+            col.mapv_inplace(|x| -x);
+        }
+    }
+    let q = if rows < cols { q.reversed_axes() } else { q };
+    Array::from_shape_vec(dim, q.iter().map(|&x| x * gain).collect()).unwrap()
+}
+/// Creates a sparse array whose non-zero elements are drawn from a normal distribution with
+/// a standard deviation of `std`; within each column, the proportion of elements given by
+/// `sparsity` (rounded up) is set to zero.
+///
+/// Arrays of more than two dimensions are treated as a matrix with a row for each index of
+/// their first axis.
+pub fn sparse<A, D, Sh, R>(
+    shape: Sh,
+    sparsity: f64,
+    std: A,
+    rng: &mut R,
+) -> Result<Array<A, D>, NormalError>
+where
+    A: Float,
+    D: Dimension,
+    R: Rng + ?Sized,
+    Sh: ShapeBuilder<Dim = D>,
+    StandardNormal: Distribution<A>,
+{
+    let dim = shape.into_shape().raw_dim().clone();
+    let (rows, cols) = matrix(&dim);
+    let mut res = Array2::random_using((rows, cols), Normal::new(A::zero(), std)?, rng);
+    let zeros = ((sparsity * rows as f64).ceil() as usize).min(rows);
+    let mut indices = (0..rows).collect::<Vec<_>>();
+    for mut col in res.columns_mut() {
+        indices.shuffle(rng);
+        for &i in &indices[..zeros] {
+            col[i] = A::zero();
+        }
+    }
+    Ok(Array::from_shape_vec(dim, res.into_raw_vec()).unwrap())
+}
+
+/// Returns the shape of the matrix with a row for each index of the first axis.
+fn matrix<D>(dim: &D) -> (usize, usize)
+where
+    D: Dimension,
+{
+    let rows = if dim.ndim() == 0 { 1 } else { dim[0] };
+    (rows, dim.size() / rows.max(1))
+}
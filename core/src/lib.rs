@@ -23,10 +23,12 @@ pub use self::init::{Initialize, InitializeExt};
 pub(crate) mod macros;
 pub(crate) mod primitives;
 
+pub mod autodiff;
 pub mod error;
 pub mod func;
 pub mod init;
 pub mod math;
+pub mod metrics;
 pub mod nn;
 pub mod ops;
 
@@ -38,11 +40,13 @@ pub mod prelude {
     #[allow(unused_imports)]
     pub(crate) use super::primitives::rust::*;
 
+    pub use super::autodiff::prelude::*;
     pub use super::error::prelude::*;
     pub use super::func::prelude::*;
     #[cfg(feature = "rand")]
     pub use super::init::prelude::*;
     pub use super::math::prelude::*;
+    pub use super::metrics::prelude::*;
     pub use super::nn::prelude::*;
     pub use super::ops::prelude::*;
     pub use super::primitives::*;
@@ -33,4 +33,74 @@ macro_rules! losses {
             }
         }
     };
+    // losses configured with a reduction, optional weights of each `class` or `sample`, and the
+    // given hyperparameters; those in parentheses are the arguments of `new`
+    ($($(#[doc = $doc:literal])* $name:ident<$per:ident>$(($($arg:ident: $init:expr),* $(,)?))? {$($field:ident: $default:expr),* $(,)?}),* $(,)?) => {
+        $(
+            losses!(@impl $(#[doc = $doc])* $name<$per>($($($arg: $init),*)?) {$($field: $default),*});
+        )*
+    };
+    (@impl $(#[doc = $doc:literal])* $name:ident<$per:ident>($($arg:ident: $init:expr),*) {$($field:ident: $default:expr),*}) => {
+        $(#[doc = $doc])*
+        #[derive(Clone, Debug, PartialEq)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Deserialize, serde::Serialize),
+            serde(rename_all = "snake_case")
+        )]
+        pub struct $name<A = f64> {
+            $(pub $arg: A,)*
+            $(pub $field: A,)*
+            pub reduction: $crate::func::Reduction,
+            pub weights: Option<nd::Array1<A>>,
+        }
+
+        impl<A> $name<A>
+        where
+            A: num::traits::Float,
+        {
+            pub fn new($($arg: A),*) -> Self {
+                Self {
+                    $($arg,)*
+                    $($field: $default,)*
+                    reduction: $crate::func::Reduction::Mean,
+                    weights: None,
+                }
+            }
+
+            paste::paste! {
+                $(
+                    pub fn [<with_ $arg>](self, $arg: A) -> Self {
+                        Self { $arg, ..self }
+                    }
+                )*
+                $(
+                    pub fn [<with_ $field>](self, $field: A) -> Self {
+                        Self { $field, ..self }
+                    }
+                )*
+            }
+
+            pub fn with_reduction(self, reduction: $crate::func::Reduction) -> Self {
+                Self { reduction, ..self }
+            }
+
+            #[doc = concat!("Scales the loss of each ", stringify!($per), " by the corresponding weight.")]
+            pub fn with_weights(self, weights: nd::Array1<A>) -> Self {
+                Self {
+                    weights: Some(weights),
+                    ..self
+                }
+            }
+        }
+
+        impl<A> Default for $name<A>
+        where
+            A: num::traits::Float,
+        {
+            fn default() -> Self {
+                Self::new($($init),*)
+            }
+        }
+    };
 }
@@ -3,7 +3,7 @@
     Contrib: FL03 <jo3mccain@icloud.com>
 */
 use num::integer::Roots;
-use num::traits::FromPrimitive;
+use num::traits::{Float, FromPrimitive};
 
 pub trait Root {
     type Output;
@@ -66,3 +66,35 @@ impl_root! {
     u128,
     usize,
 }
+
+/// Computes the error function, `erf(x) = 2 / sqrt(pi) * integral(exp(-t^2), 0, x)`, using a
+/// Chebyshev approximation of its complement whose fractional error is below `1.2e-7`.
+pub fn erf<T>(x: T) -> T
+where
+    T: Float,
+{
+    let c = |v: f64| T::from(v).unwrap();
+    let z = x.abs();
+    let t = (T::one() + c(0.5) * z).recip();
+    let poly = [
+        -1.26551223,
+        1.00002368,
+        0.37409196,
+        0.09678418,
+        -0.18628806,
+        0.27886807,
+        -1.13520398,
+        1.48851587,
+        -0.82215223,
+        0.17087277,
+    ]
+    .iter()
+    .rev()
+    .fold(T::zero(), |acc, &k| acc * t + c(k));
+    let erfc = t * (poly - z * z).exp();
+    if x >= T::zero() {
+        T::one() - erfc
+    } else {
+        erfc - T::one()
+    }
+}
@@ -0,0 +1,58 @@
+/*
+    Appellation: linalg <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use crate::rust::Vec;
+use nd::prelude::*;
+use nd::{Data, ScalarOperand};
+use num::traits::Float;
+
+/// Computes the reduced QR decomposition of an `m x n` matrix using Householder reflections,
+/// returning an `m x k` matrix `Q` with orthonormal columns and an upper-triangular `k x n`
+/// matrix `R`, where `k = min(m, n)`, such that `A = QR`.
+pub fn qr<A, S>(a: &ArrayBase<S, Ix2>) -> (Array2<A>, Array2<A>)
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+{
+    let (m, n) = a.dim();
+    let k = m.min(n);
+    let mut r = a.to_owned();
+    let mut reflectors = Vec::with_capacity(k);
+    for j in 0..k {
+        let mut v = r.slice(s![j.., j]).to_owned();
+        let norm = v.dot(&v).sqrt();
+        // reflect onto the axis with the opposite sign to avoid cancellation
+        v[0] = v[0] + v[0].signum() * norm;
+        let scale = v.dot(&v).sqrt();
+        if scale > A::zero() {
+            v.mapv_inplace(|x| x / scale);
+        }
+        reflect(&v, r.slice_mut(s![j.., j..]));
+        reflectors.push(v);
+    }
+    // accumulate the reflections onto the leading columns of the identity
+    let mut q = Array2::eye(m).slice(s![.., ..k]).to_owned();
+    for (j, v) in reflectors.iter().enumerate().rev() {
+        reflect(v, q.slice_mut(s![j.., ..]));
+    }
+    let mut r = r.slice(s![..k, ..]).to_owned();
+    for ((i, j), x) in r.indexed_iter_mut() {
+        if i > j {
+            *x = A::zero();
+        }
+    }
+    (q, r)
+}
+
+/// Applies the Householder reflection `I - 2vv^T`, given the unit vector `v`, to the matrix.
+fn reflect<A>(v: &Array1<A>, mut a: ArrayViewMut2<'_, A>)
+where
+    A: Float + ScalarOperand,
+{
+    let two = A::one() + A::one();
+    let w = v.dot(&a);
+    for (mut row, &vi) in a.rows_mut().into_iter().zip(v) {
+        row.zip_mut_with(&w, |x, &wj| *x = *x - two * vi * wj);
+    }
+}
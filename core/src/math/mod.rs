@@ -10,6 +10,7 @@
 pub use self::traits::*;
 
 pub mod arith;
+pub mod linalg;
 pub mod stats;
 pub mod traits;
 
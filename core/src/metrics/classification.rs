@@ -0,0 +1,505 @@
+/*
+    Appellation: classification <metrics>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::{Accumulate, Average, Metric, MetricError};
+use nd::prelude::*;
+use nd::{Data, Zip};
+use num::traits::{Float, ToPrimitive};
+
+/// Returns the index of the largest element of the given row.
+pub(crate) fn argmax<A, S>(row: ArrayBase<S, Ix1>) -> usize
+where
+    A: PartialOrd,
+    S: Data<Elem = A>,
+{
+    row.indexed_iter()
+        .fold(None, |best: Option<(usize, &A)>, (i, x)| match best {
+            Some((_, y)) if y >= x => best,
+            _ => Some((i, x)),
+        })
+        .map(|(i, _)| i)
+        .unwrap_or_default()
+}
+
+/// Ensures that there is a target for each of the `n` predictions.
+fn check_samples<S>(n: usize, target: &ArrayBase<S, Ix1>) -> Result<(), MetricError>
+where
+    S: Data,
+{
+    match n == target.len() {
+        true => Ok(()),
+        false => Err(MetricError::ShapeMismatch),
+    }
+}
+
+/// Ensures that every label is the index of one of the given number of classes.
+fn check_labels<S>(labels: &ArrayBase<S, Ix1>, classes: usize) -> Result<(), MetricError>
+where
+    S: Data<Elem = usize>,
+{
+    match labels.iter().any(|&label| label >= classes) {
+        true => Err(MetricError::InvalidLabel),
+        false => Ok(()),
+    }
+}
+
+/// [Accuracy] is the fraction of samples whose predicted label matches the target.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct Accuracy {
+    pub(crate) correct: usize,
+    pub(crate) total: usize,
+}
+
+/// [TopKAccuracy] is the fraction of samples whose target is among the `k` classes of highest
+/// score; ties are resolved in favour of the target.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct TopKAccuracy {
+    pub(crate) correct: usize,
+    pub k: usize,
+    pub(crate) total: usize,
+}
+
+/// A [ConfusionMatrix] counts the samples of each class (rows) by their predicted class
+/// (columns); the per-class precision, recall, and F1 scores are derived from it.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct ConfusionMatrix {
+    pub(crate) matrix: Array2<usize>,
+}
+
+/// [LogLoss] is the mean negative log-likelihood of the targets under the predicted
+/// probabilities, which are clipped to `[eps, 1 - eps]`.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct LogLoss {
+    pub eps: f64,
+    pub(crate) sum: f64,
+    pub(crate) total: usize,
+}
+
+macro_rules! scores {
+    ($($(#[doc = $doc:literal])* $name:ident::$method:ident),* $(,)?) => {
+        $(
+            $(#[doc = $doc])*
+            #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+            #[cfg_attr(
+                feature = "serde",
+                derive(serde::Deserialize, serde::Serialize),
+                serde(rename_all = "snake_case")
+            )]
+            pub struct $name {
+                pub average: Average,
+                pub(crate) matrix: ConfusionMatrix,
+            }
+
+            impl $name {
+                pub fn new(classes: usize, average: Average) -> Self {
+                    Self {
+                        average,
+                        matrix: ConfusionMatrix::new(classes),
+                    }
+                }
+
+                pub const fn confusion_matrix(&self) -> &ConfusionMatrix {
+                    &self.matrix
+                }
+            }
+
+            impl Metric for $name {
+                type Output = f64;
+
+                fn compute(&self) -> f64 {
+                    self.matrix.$method(self.average)
+                }
+
+                fn reset(&mut self) {
+                    self.matrix.reset()
+                }
+            }
+
+            impl<P, T> Accumulate<P, T> for $name
+            where
+                ConfusionMatrix: Accumulate<P, T>,
+            {
+                fn update(&mut self, pred: &P, target: &T) -> Result<(), MetricError> {
+                    self.matrix.update(pred, target)
+                }
+            }
+        )*
+    };
+}
+
+scores! {
+    /// [Precision] is the fraction of positive predictions which are correct.
+    Precision::precision,
+    /// [Recall] is the fraction of positive samples which are predicted as such.
+    Recall::recall,
+    /// [F1Score] is the harmonic mean of precision and recall.
+    F1Score::f1,
+}
+
+/// Divides `a` by `b`, returning zero rather than NaN when `b` is zero.
+fn ratio(a: f64, b: f64) -> f64 {
+    if b == 0.0 {
+        0.0
+    } else {
+        a / b
+    }
+}
+
+/*
+ ************* Implementations *************
+*/
+impl Accuracy {
+    pub fn new() -> Self {
+        Self {
+            correct: 0,
+            total: 0,
+        }
+    }
+}
+
+impl Metric for Accuracy {
+    type Output = f64;
+
+    fn compute(&self) -> f64 {
+        self.correct as f64 / self.total as f64
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<A, S, T> Accumulate<ArrayBase<S, Ix2>, ArrayBase<T, Ix1>> for Accuracy
+where
+    A: PartialOrd,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+{
+    fn update(
+        &mut self,
+        pred: &ArrayBase<S, Ix2>,
+        target: &ArrayBase<T, Ix1>,
+    ) -> Result<(), MetricError> {
+        check_samples(pred.nrows(), target)?;
+        check_labels(target, pred.ncols())?;
+        for (row, &label) in pred.rows().into_iter().zip(target) {
+            self.correct += (argmax(row) == label) as usize;
+            self.total += 1;
+        }
+        Ok(())
+    }
+}
+
+impl<S, T> Accumulate<ArrayBase<S, Ix1>, ArrayBase<T, Ix1>> for Accuracy
+where
+    S: Data<Elem = usize>,
+    T: Data<Elem = usize>,
+{
+    fn update(
+        &mut self,
+        pred: &ArrayBase<S, Ix1>,
+        target: &ArrayBase<T, Ix1>,
+    ) -> Result<(), MetricError> {
+        check_samples(pred.len(), target)?;
+        for (p, t) in pred.iter().zip(target) {
+            self.correct += (p == t) as usize;
+            self.total += 1;
+        }
+        Ok(())
+    }
+}
+
+impl TopKAccuracy {
+    pub fn new(k: usize) -> Self {
+        Self {
+            correct: 0,
+            k,
+            total: 0,
+        }
+    }
+}
+
+impl Metric for TopKAccuracy {
+    type Output = f64;
+
+    fn compute(&self) -> f64 {
+        self.correct as f64 / self.total as f64
+    }
+
+    fn reset(&mut self) {
+        self.correct = 0;
+        self.total = 0;
+    }
+}
+
+impl<A, S, T> Accumulate<ArrayBase<S, Ix2>, ArrayBase<T, Ix1>> for TopKAccuracy
+where
+    A: PartialOrd,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+{
+    fn update(
+        &mut self,
+        pred: &ArrayBase<S, Ix2>,
+        target: &ArrayBase<T, Ix1>,
+    ) -> Result<(), MetricError> {
+        check_samples(pred.nrows(), target)?;
+        check_labels(target, pred.ncols())?;
+        for (row, &label) in pred.rows().into_iter().zip(target) {
+            let score = &row[label];
+            let rank = row.iter().filter(|&x| x > score).count();
+            self.correct += (rank < self.k) as usize;
+            self.total += 1;
+        }
+        Ok(())
+    }
+}
+
+impl ConfusionMatrix {
+    pub fn new(classes: usize) -> Self {
+        Self {
+            matrix: Array2::zeros((classes, classes)),
+        }
+    }
+    /// Records a single sample of class `target` predicted as `pred`.
+    pub fn add(&mut self, pred: usize, target: usize) -> Result<(), MetricError> {
+        let count = self
+            .matrix
+            .get_mut([target, pred])
+            .ok_or(MetricError::InvalidLabel)?;
+        *count += 1;
+        Ok(())
+    }
+
+    pub fn classes(&self) -> usize {
+        self.matrix.nrows()
+    }
+
+    pub const fn matrix(&self) -> &Array2<usize> {
+        &self.matrix
+    }
+    /// Returns the number of samples recorded.
+    pub fn total(&self) -> usize {
+        self.matrix.sum()
+    }
+    /// Returns the number of samples of each class.
+    pub fn support(&self) -> Array1<usize> {
+        self.matrix.sum_axis(Axis(1))
+    }
+    /// Returns the fraction of samples predicted correctly.
+    pub fn accuracy(&self) -> f64 {
+        self.matrix.diag().sum() as f64 / self.total() as f64
+    }
+
+    pub fn precision_per_class(&self) -> Array1<f64> {
+        let predicted = self.matrix.sum_axis(Axis(0));
+        Array1::from_shape_fn(self.classes(), |c| {
+            ratio(self.matrix[[c, c]] as f64, predicted[c] as f64)
+        })
+    }
+
+    pub fn recall_per_class(&self) -> Array1<f64> {
+        let support = self.support();
+        Array1::from_shape_fn(self.classes(), |c| {
+            ratio(self.matrix[[c, c]] as f64, support[c] as f64)
+        })
+    }
+
+    pub fn f1_per_class(&self) -> Array1<f64> {
+        let (p, r) = (self.precision_per_class(), self.recall_per_class());
+        Zip::from(&p)
+            .and(&r)
+            .map_collect(|&p, &r| ratio(2.0 * p * r, p + r))
+    }
+
+    pub fn precision(&self, average: Average) -> f64 {
+        match average {
+            Average::Micro => self.micro(),
+            _ => self.average(self.precision_per_class(), average),
+        }
+    }
+
+    pub fn recall(&self, average: Average) -> f64 {
+        match average {
+            Average::Micro => self.micro(),
+            _ => self.average(self.recall_per_class(), average),
+        }
+    }
+
+    pub fn f1(&self, average: Average) -> f64 {
+        match average {
+            Average::Micro => self.micro(),
+            _ => self.average(self.f1_per_class(), average),
+        }
+    }
+    /// Pools the true positives of every class; since every sample is assigned exactly one
+    /// label, the micro-averaged precision, recall, and F1 score coincide with the accuracy.
+    fn micro(&self) -> f64 {
+        ratio(self.matrix.diag().sum() as f64, self.total() as f64)
+    }
+
+    fn average(&self, scores: Array1<f64>, average: Average) -> f64 {
+        match average {
+            Average::Weighted => {
+                let support = self.support().mapv(|s| s as f64);
+                ratio(scores.dot(&support), support.sum())
+            }
+            _ => scores.mean().unwrap_or_default(),
+        }
+    }
+}
+
+impl Metric for ConfusionMatrix {
+    type Output = Array2<usize>;
+
+    fn compute(&self) -> Array2<usize> {
+        self.matrix.clone()
+    }
+
+    fn reset(&mut self) {
+        self.matrix.fill(0);
+    }
+}
+
+impl<A, S, T> Accumulate<ArrayBase<S, Ix2>, ArrayBase<T, Ix1>> for ConfusionMatrix
+where
+    A: PartialOrd,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+{
+    fn update(
+        &mut self,
+        pred: &ArrayBase<S, Ix2>,
+        target: &ArrayBase<T, Ix1>,
+    ) -> Result<(), MetricError> {
+        if pred.ncols() > self.classes() {
+            return Err(MetricError::InvalidLabel);
+        }
+        check_samples(pred.nrows(), target)?;
+        check_labels(target, self.classes())?;
+        for (row, &label) in pred.rows().into_iter().zip(target) {
+            self.add(argmax(row), label)?;
+        }
+        Ok(())
+    }
+}
+
+impl<S, T> Accumulate<ArrayBase<S, Ix1>, ArrayBase<T, Ix1>> for ConfusionMatrix
+where
+    S: Data<Elem = usize>,
+    T: Data<Elem = usize>,
+{
+    fn update(
+        &mut self,
+        pred: &ArrayBase<S, Ix1>,
+        target: &ArrayBase<T, Ix1>,
+    ) -> Result<(), MetricError> {
+        check_samples(pred.len(), target)?;
+        check_labels(pred, self.classes())?;
+        check_labels(target, self.classes())?;
+        for (&p, &t) in pred.iter().zip(target) {
+            self.add(p, t)?;
+        }
+        Ok(())
+    }
+}
+
+impl LogLoss {
+    pub fn new() -> Self {
+        Self {
+            eps: 1e-15,
+            sum: 0.0,
+            total: 0,
+        }
+    }
+
+    pub fn with_eps(self, eps: f64) -> Self {
+        Self { eps, ..self }
+    }
+
+    fn nll(&self, p: f64) -> f64 {
+        -p.clamp(self.eps, 1.0 - self.eps).ln()
+    }
+}
+
+impl Default for LogLoss {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metric for LogLoss {
+    type Output = f64;
+
+    fn compute(&self) -> f64 {
+        self.sum / self.total as f64
+    }
+
+    fn reset(&mut self) {
+        self.sum = 0.0;
+        self.total = 0;
+    }
+}
+
+impl<A, S, T> Accumulate<ArrayBase<S, Ix1>, ArrayBase<T, Ix1>> for LogLoss
+where
+    A: Float,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    fn update(
+        &mut self,
+        pred: &ArrayBase<S, Ix1>,
+        target: &ArrayBase<T, Ix1>,
+    ) -> Result<(), MetricError> {
+        check_samples(pred.len(), target)?;
+        for (p, y) in pred.iter().zip(target) {
+            let (p, y) = (p.to_f64().unwrap(), y.to_f64().unwrap());
+            self.sum += y * self.nll(p) + (1.0 - y) * self.nll(1.0 - p);
+            self.total += 1;
+        }
+        Ok(())
+    }
+}
+
+impl<A, S, T> Accumulate<ArrayBase<S, Ix2>, ArrayBase<T, Ix1>> for LogLoss
+where
+    A: ToPrimitive,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+{
+    fn update(
+        &mut self,
+        pred: &ArrayBase<S, Ix2>,
+        target: &ArrayBase<T, Ix1>,
+    ) -> Result<(), MetricError> {
+        check_samples(pred.nrows(), target)?;
+        check_labels(target, pred.ncols())?;
+        for (row, &label) in pred.rows().into_iter().zip(target) {
+            self.sum += self.nll(row[label].to_f64().unwrap());
+            self.total += 1;
+        }
+        Ok(())
+    }
+}
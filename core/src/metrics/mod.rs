@@ -0,0 +1,104 @@
+/*
+    Appellation: metrics <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+//! # Metrics
+//!
+//! This module provides a collection of evaluation metrics for classification and regression.
+//!
+//! Every metric is implemented as a streaming accumulator: predictions and targets are fed in
+//! batches via [Accumulate::update] before the final value is obtained with [Metric::compute],
+//! allowing metrics to be evaluated over datasets too large to fit into a
+//! single array. Functional equivalents evaluating a single pair of arrays are provided as well.
+//!
+//! ### Conventions
+//!
+//! - Multi-class predictions are `(samples, classes)` arrays of scores or probabilities, paired
+//!   with a 1-dimensional array of integer labels; a prediction is the class of highest score.
+//! - Binary predictions are 1-dimensional arrays of scores or probabilities, paired with targets
+//!   whose positive class is denoted by `1` and negative class by `0`.
+//! - Regression metrics compare arrays of the same shape, treating every element as a sample.
+//!
+//! Computing a metric before any sample has been observed yields NaN, while a label beyond the
+//! number of classes, or a batch whose predictions and targets differ in number, is rejected
+//! with a [MetricError].
+pub use self::{classification::*, ranking::*, regression::*, utils::*};
+
+pub(crate) mod utils;
+
+pub mod classification;
+pub mod ranking;
+pub mod regression;
+
+pub(crate) mod prelude {
+    pub use super::classification::*;
+    pub use super::ranking::*;
+    pub use super::regression::*;
+    pub use super::utils::*;
+    pub use super::{Accumulate, Average, Metric, MetricError};
+}
+
+use strum::{AsRefStr, Display, EnumCount, EnumIs, EnumIter, EnumString, VariantNames};
+
+err! {
+    MetricError {
+        /// A target label is beyond the number of classes.
+        InvalidLabel,
+        /// The number of predictions differs from that of the targets.
+        ShapeMismatch,
+    }
+}
+
+impl_err!(MetricError);
+
+/// [Metric] describes a streaming accumulator capable of computing its final value from the
+/// statistics gathered so far.
+pub trait Metric {
+    type Output;
+    /// Computes the value of the metric over every batch observed since the last reset.
+    fn compute(&self) -> Self::Output;
+    /// Discards the accumulated statistics.
+    fn reset(&mut self);
+}
+
+/// [Accumulate] updates a [Metric] with a batch of predictions (`P`) and targets (`T`).
+pub trait Accumulate<P, T = P>: Metric {
+    /// Accumulates the statistics of a batch, leaving them untouched should the batch be
+    /// rejected.
+    fn update(&mut self, pred: &P, target: &T) -> Result<(), MetricError>;
+}
+
+/// [Average] determines how per-class scores are reduced to a single value.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumCount,
+    EnumIs,
+    EnumIter,
+    EnumString,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    VariantNames,
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum Average {
+    /// Computes the score from the true and false positives pooled across every class.
+    Micro,
+    /// Computes the unweighted mean of the per-class scores.
+    #[default]
+    Macro,
+    /// Computes the mean of the per-class scores, weighted by the support of each class.
+    Weighted,
+}
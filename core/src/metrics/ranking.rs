@@ -0,0 +1,179 @@
+/*
+    Appellation: ranking <metrics>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::{Accumulate, Metric, MetricError};
+use crate::rust::Vec;
+use core::cmp::Ordering;
+use nd::{ArrayBase, Data, Ix1};
+use num::traits::ToPrimitive;
+
+/// [RocAuc] is the area under the receiver operating characteristic curve of a binary
+/// classifier, i.e. the probability that a random positive sample is scored above a random
+/// negative one. The value is undefined (NaN) unless both classes have been observed.
+///
+/// Since the metric depends upon the ranking of every sample, the accumulator retains each
+/// score observed.
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct RocAuc {
+    pub(crate) samples: Vec<(f64, bool)>,
+}
+
+/// [PrAuc] summarizes the precision-recall curve of a binary classifier as its average
+/// precision: the mean of the precision achieved at each threshold, weighted by the increase in
+/// recall. The value is undefined (NaN) unless a positive sample has been observed.
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct PrAuc {
+    pub(crate) samples: Vec<(f64, bool)>,
+}
+
+/// Records each score alongside whether the corresponding target belongs to the positive class.
+fn extend<A, B, S, T>(
+    samples: &mut Vec<(f64, bool)>,
+    pred: &ArrayBase<S, Ix1>,
+    target: &ArrayBase<T, Ix1>,
+) where
+    A: ToPrimitive,
+    B: ToPrimitive,
+    S: Data<Elem = A>,
+    T: Data<Elem = B>,
+{
+    samples.extend(
+        pred.iter()
+            .zip(target)
+            .map(|(p, t)| (p.to_f64().unwrap(), t.to_f64().unwrap() > 0.5)),
+    )
+}
+
+/// Sorts the samples by their score in descending order.
+fn sorted(samples: &[(f64, bool)]) -> Vec<(f64, bool)> {
+    let mut samples = samples.to_vec();
+    samples.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+    samples
+}
+
+/*
+ ************* Implementations *************
+*/
+impl RocAuc {
+    pub fn new() -> Self {
+        Self {
+            samples: Vec::new(),
+        }
+    }
+}
+
+impl Metric for RocAuc {
+    type Output = f64;
+    /// Computes the Mann-Whitney U statistic, assigning tied scores their average rank.
+    fn compute(&self) -> f64 {
+        let samples = sorted(&self.samples);
+        let n = samples.len();
+        let positives = samples.iter().filter(|(_, y)| *y).count();
+        let negatives = n - positives;
+        if positives == 0 || negatives == 0 {
+            return f64::NAN;
+        }
+        // ranks are assigned in ascending order of score, beginning from one
+        let (mut rank_sum, mut i) = (0.0, 0);
+        while i < n {
+            let mut j = i;
+            while j < n && samples[j].0 == samples[i].0 {
+                j += 1;
+            }
+            let rank = (n - j + 1 + n - i) as f64 / 2.0;
+            let tied = samples[i..j].iter().filter(|(_, y)| *y).count();
+            rank_sum += rank * tied as f64;
+            i = j;
+        }
+        let (p, q) = (positives as f64, negatives as f64);
+        (rank_sum - p * (p + 1.0) / 2.0) / (p * q)
+    }
+
+    fn reset(&mut self) {
+        self.samples.clear();
+    }
+}
+
+impl<A, B, S, T> Accumulate<ArrayBase<S, Ix1>, ArrayBase<T, Ix1>> for RocAuc
+where
+    A: ToPrimitive,
+    B: ToPrimitive,
+    S: Data<Elem = A>,
+    T: Data<Elem = B>,
+{
+    fn update(
+        &mut self,
+        pred: &ArrayBase<S, Ix1>,
+        target: &ArrayBase<T, Ix1>,
+    ) -> Result<(), MetricError> {
+        extend(&mut self.samples, pred, target);
+        Ok(())
+    }
+}
+
+impl PrAuc {
+    pub fn new() -> Self {
+        Self {
+            samples: Vec::new(),
+        }
+    }
+}
+
+impl Metric for PrAuc {
+    type Output = f64;
+
+    fn compute(&self) -> f64 {
+        let samples = sorted(&self.samples);
+        let positives = samples.iter().filter(|(_, y)| *y).count();
+        if positives == 0 {
+            return f64::NAN;
+        }
+        let (mut ap, mut tp, mut recall, mut i) = (0.0, 0usize, 0.0, 0);
+        // each distinct score acts as a threshold, admitting every sample scored at least as high
+        while i < samples.len() {
+            let mut j = i;
+            while j < samples.len() && samples[j].0 == samples[i].0 {
+                tp += samples[j].1 as usize;
+                j += 1;
+            }
+            let precision = tp as f64 / j as f64;
+            let next = tp as f64 / positives as f64;
+            ap += (next - recall) * precision;
+            recall = next;
+            i = j;
+        }
+        ap
+    }
+
+    fn reset(&mut self) {
+        self.samples.clear();
+    }
+}
+
+impl<A, B, S, T> Accumulate<ArrayBase<S, Ix1>, ArrayBase<T, Ix1>> for PrAuc
+where
+    A: ToPrimitive,
+    B: ToPrimitive,
+    S: Data<Elem = A>,
+    T: Data<Elem = B>,
+{
+    fn update(
+        &mut self,
+        pred: &ArrayBase<S, Ix1>,
+        target: &ArrayBase<T, Ix1>,
+    ) -> Result<(), MetricError> {
+        extend(&mut self.samples, pred, target);
+        Ok(())
+    }
+}
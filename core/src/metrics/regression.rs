@@ -0,0 +1,222 @@
+/*
+    Appellation: regression <metrics>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::{Accumulate, Metric, MetricError};
+use nd::{ArrayBase, Data, Dimension, Zip};
+use num::traits::ToPrimitive;
+
+/// [R2Score] is the coefficient of determination: the proportion of the variance of the targets
+/// explained by the predictions. A constant target yields a score of one if predicted exactly
+/// and zero otherwise.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct R2Score {
+    pub(crate) n: usize,
+    pub(crate) residuals: f64,
+    pub(crate) sum: f64,
+    pub(crate) sum_sq: f64,
+}
+
+/// [ExplainedVariance] compares the variance of the residuals to that of the targets; unlike
+/// [R2Score], it disregards any systematic offset of the predictions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct ExplainedVariance {
+    pub(crate) err: f64,
+    pub(crate) err_sq: f64,
+    pub(crate) n: usize,
+    pub(crate) sum: f64,
+    pub(crate) sum_sq: f64,
+}
+
+/// [Mape] is the mean absolute percentage error, expressed as a fraction; targets are bounded
+/// away from zero by `eps` to avoid dividing by zero.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct Mape {
+    pub eps: f64,
+    pub(crate) n: usize,
+    pub(crate) sum: f64,
+}
+
+/// Visits each pair of prediction and target as `f64`.
+fn zip<A, B, S, T, D, F>(pred: &ArrayBase<S, D>, target: &ArrayBase<T, D>, mut f: F)
+where
+    A: ToPrimitive,
+    B: ToPrimitive,
+    D: Dimension,
+    S: Data<Elem = A>,
+    T: Data<Elem = B>,
+    F: FnMut(f64, f64),
+{
+    Zip::from(pred)
+        .and(target)
+        .for_each(|p, t| f(p.to_f64().unwrap(), t.to_f64().unwrap()))
+}
+
+/// Returns `1 - num / den`, treating a vanishing denominator as described by [R2Score].
+fn explained(num: f64, den: f64) -> f64 {
+    if den == 0.0 {
+        return if num == 0.0 { 1.0 } else { 0.0 };
+    }
+    1.0 - num / den
+}
+
+/*
+ ************* Implementations *************
+*/
+impl R2Score {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Metric for R2Score {
+    type Output = f64;
+
+    fn compute(&self) -> f64 {
+        let n = self.n as f64;
+        let total = self.sum_sq - self.sum * self.sum / n;
+        explained(self.residuals, total)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<A, B, S, T, D> Accumulate<ArrayBase<S, D>, ArrayBase<T, D>> for R2Score
+where
+    A: ToPrimitive,
+    B: ToPrimitive,
+    D: Dimension,
+    S: Data<Elem = A>,
+    T: Data<Elem = B>,
+{
+    fn update(
+        &mut self,
+        pred: &ArrayBase<S, D>,
+        target: &ArrayBase<T, D>,
+    ) -> Result<(), MetricError> {
+        zip(pred, target, |p, t| {
+            self.n += 1;
+            self.residuals += (t - p).powi(2);
+            self.sum += t;
+            self.sum_sq += t * t;
+        });
+        Ok(())
+    }
+}
+
+impl ExplainedVariance {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Metric for ExplainedVariance {
+    type Output = f64;
+
+    fn compute(&self) -> f64 {
+        let n = self.n as f64;
+        let var_err = self.err_sq / n - (self.err / n).powi(2);
+        let var_target = self.sum_sq / n - (self.sum / n).powi(2);
+        explained(var_err, var_target)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<A, B, S, T, D> Accumulate<ArrayBase<S, D>, ArrayBase<T, D>> for ExplainedVariance
+where
+    A: ToPrimitive,
+    B: ToPrimitive,
+    D: Dimension,
+    S: Data<Elem = A>,
+    T: Data<Elem = B>,
+{
+    fn update(
+        &mut self,
+        pred: &ArrayBase<S, D>,
+        target: &ArrayBase<T, D>,
+    ) -> Result<(), MetricError> {
+        zip(pred, target, |p, t| {
+            let e = t - p;
+            self.n += 1;
+            self.err += e;
+            self.err_sq += e * e;
+            self.sum += t;
+            self.sum_sq += t * t;
+        });
+        Ok(())
+    }
+}
+
+impl Mape {
+    pub fn new() -> Self {
+        Self {
+            eps: f64::EPSILON,
+            n: 0,
+            sum: 0.0,
+        }
+    }
+
+    pub fn with_eps(self, eps: f64) -> Self {
+        Self { eps, ..self }
+    }
+}
+
+impl Default for Mape {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metric for Mape {
+    type Output = f64;
+
+    fn compute(&self) -> f64 {
+        self.sum / self.n as f64
+    }
+
+    fn reset(&mut self) {
+        self.n = 0;
+        self.sum = 0.0;
+    }
+}
+
+impl<A, B, S, T, D> Accumulate<ArrayBase<S, D>, ArrayBase<T, D>> for Mape
+where
+    A: ToPrimitive,
+    B: ToPrimitive,
+    D: Dimension,
+    S: Data<Elem = A>,
+    T: Data<Elem = B>,
+{
+    fn update(
+        &mut self,
+        pred: &ArrayBase<S, D>,
+        target: &ArrayBase<T, D>,
+    ) -> Result<(), MetricError> {
+        zip(pred, target, |p, t| {
+            self.n += 1;
+            self.sum += (t - p).abs() / t.abs().max(self.eps);
+        });
+        Ok(())
+    }
+}
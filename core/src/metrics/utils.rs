@@ -0,0 +1,165 @@
+/*
+    Appellation: utils <metrics>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::*;
+use nd::{ArrayBase, Data, Dimension, Ix1, Ix2};
+use num::traits::{Float, ToPrimitive};
+
+/// Evaluates a freshly initialized metric on a single batch.
+fn try_evaluate<M, P, T>(mut metric: M, pred: &P, target: &T) -> Result<M::Output, MetricError>
+where
+    M: Accumulate<P, T>,
+{
+    metric.update(pred, target)?;
+    Ok(metric.compute())
+}
+/// Evaluates a freshly initialized metric on a single batch, where the metric accepts any
+/// input and so never rejects the batch.
+fn evaluate<M, P, T>(mut metric: M, pred: &P, target: &T) -> M::Output
+where
+    M: Accumulate<P, T>,
+{
+    let _ = metric.update(pred, target);
+    metric.compute()
+}
+/// Computes the fraction of samples whose class of highest score matches the target label.
+pub fn accuracy<A, S, T>(
+    pred: &ArrayBase<S, Ix2>,
+    target: &ArrayBase<T, Ix1>,
+) -> Result<f64, MetricError>
+where
+    A: PartialOrd,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+{
+    try_evaluate(Accuracy::new(), pred, target)
+}
+/// Computes the fraction of samples whose target label is among the `k` classes of highest score.
+pub fn top_k_accuracy<A, S, T>(
+    pred: &ArrayBase<S, Ix2>,
+    target: &ArrayBase<T, Ix1>,
+    k: usize,
+) -> Result<f64, MetricError>
+where
+    A: PartialOrd,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+{
+    try_evaluate(TopKAccuracy::new(k), pred, target)
+}
+/// Tallies the samples of each class (rows) by their predicted class (columns).
+pub fn confusion_matrix<A, S, T>(
+    pred: &ArrayBase<S, Ix2>,
+    target: &ArrayBase<T, Ix1>,
+) -> Result<ConfusionMatrix, MetricError>
+where
+    A: PartialOrd,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+{
+    let mut matrix = ConfusionMatrix::new(pred.ncols());
+    matrix.update(pred, target)?;
+    Ok(matrix)
+}
+
+pub fn precision<A, S, T>(
+    pred: &ArrayBase<S, Ix2>,
+    target: &ArrayBase<T, Ix1>,
+    average: Average,
+) -> Result<f64, MetricError>
+where
+    A: PartialOrd,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+{
+    confusion_matrix(pred, target).map(|matrix| matrix.precision(average))
+}
+
+pub fn recall<A, S, T>(
+    pred: &ArrayBase<S, Ix2>,
+    target: &ArrayBase<T, Ix1>,
+    average: Average,
+) -> Result<f64, MetricError>
+where
+    A: PartialOrd,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+{
+    confusion_matrix(pred, target).map(|matrix| matrix.recall(average))
+}
+
+pub fn f1_score<A, S, T>(
+    pred: &ArrayBase<S, Ix2>,
+    target: &ArrayBase<T, Ix1>,
+    average: Average,
+) -> Result<f64, MetricError>
+where
+    A: PartialOrd,
+    S: Data<Elem = A>,
+    T: Data<Elem = usize>,
+{
+    confusion_matrix(pred, target).map(|matrix| matrix.f1(average))
+}
+/// Computes the area under the ROC curve of a binary classifier.
+pub fn roc_auc<A, B, S, T>(pred: &ArrayBase<S, Ix1>, target: &ArrayBase<T, Ix1>) -> f64
+where
+    A: ToPrimitive,
+    B: ToPrimitive,
+    S: Data<Elem = A>,
+    T: Data<Elem = B>,
+{
+    evaluate(RocAuc::new(), pred, target)
+}
+/// Computes the area under the precision-recall curve (average precision) of a binary classifier.
+pub fn pr_auc<A, B, S, T>(pred: &ArrayBase<S, Ix1>, target: &ArrayBase<T, Ix1>) -> f64
+where
+    A: ToPrimitive,
+    B: ToPrimitive,
+    S: Data<Elem = A>,
+    T: Data<Elem = B>,
+{
+    evaluate(PrAuc::new(), pred, target)
+}
+/// Computes the mean negative log-likelihood of binary targets under the predicted probabilities.
+pub fn log_loss<A, S, T>(pred: &ArrayBase<S, Ix1>, target: &ArrayBase<T, Ix1>) -> f64
+where
+    A: Float,
+    S: Data<Elem = A>,
+    T: Data<Elem = A>,
+{
+    evaluate(LogLoss::new(), pred, target)
+}
+
+pub fn r2_score<A, B, S, T, D>(pred: &ArrayBase<S, D>, target: &ArrayBase<T, D>) -> f64
+where
+    A: ToPrimitive,
+    B: ToPrimitive,
+    D: Dimension,
+    S: Data<Elem = A>,
+    T: Data<Elem = B>,
+{
+    evaluate(R2Score::new(), pred, target)
+}
+
+pub fn explained_variance<A, B, S, T, D>(pred: &ArrayBase<S, D>, target: &ArrayBase<T, D>) -> f64
+where
+    A: ToPrimitive,
+    B: ToPrimitive,
+    D: Dimension,
+    S: Data<Elem = A>,
+    T: Data<Elem = B>,
+{
+    evaluate(ExplainedVariance::new(), pred, target)
+}
+/// Computes the mean absolute percentage error as a fraction.
+pub fn mape<A, B, S, T, D>(pred: &ArrayBase<S, D>, target: &ArrayBase<T, D>) -> f64
+where
+    A: ToPrimitive,
+    B: ToPrimitive,
+    D: Dimension,
+    S: Data<Elem = A>,
+    T: Data<Elem = B>,
+{
+    evaluate(Mape::new(), pred, target)
+}
@@ -3,49 +3,125 @@
     Contrib: FL03 <jo3mccain@icloud.com>
 */
 #![allow(unused_imports)]
+pub use self::{alpha::*, channel::*, connect::*, path::*};
+
+use super::{Mode, Training};
 use crate::{Eval, Predict, PredictError};
 use nd::prelude::*;
-use nd::{DataOwned, ScalarOperand};
+use nd::{Data, DataOwned, ScalarOperand};
 #[cfg(feature = "rand")]
-use ndrand::{rand_distr::Bernoulli, RandomExt};
-use num::traits::Num;
+use ndrand::{rand::Rng, rand_distr::Bernoulli, RandomExt};
+use num::traits::{Num, NumCast};
+
+/// Implements the configuration and [Training] behaviour shared by the variants of the
+/// [Dropout] layer, each of which wraps a [Dropout] holding its probability and mode.
+macro_rules! dropout_variant {
+    ($($name:ident),* $(,)?) => {
+        $(dropout_variant!(@impl $name);)*
+    };
+    (@impl $name:ident) => {
+        impl $name {
+            pub fn with_mode(mut self, mode: $crate::nn::Mode) -> Self {
+                self.dropout.set_mode(mode);
+                self
+            }
+
+            pub const fn p(&self) -> f64 {
+                self.dropout.p()
+            }
+        }
+
+        impl $crate::nn::Training for $name {
+            fn mode(&self) -> $crate::nn::Mode {
+                self.dropout.mode()
+            }
+
+            fn set_mode(&mut self, mode: $crate::nn::Mode) {
+                self.dropout.set_mode(mode)
+            }
+        }
+    };
+}
 
+pub(crate) mod alpha;
+pub(crate) mod channel;
+pub(crate) mod connect;
+pub(crate) mod path;
+
+/// Draws a mask of the given shape whose elements are either zero, with probability `p`, or
+/// `1 / (1 - p)`.
 #[cfg(feature = "rand")]
-pub(crate) fn _dropout<A, S, D>(array: &ArrayBase<S, D>, p: f64) -> Array<A, D>
+pub(crate) fn _mask<A, D, R>(dim: D, p: f64, rng: &mut R) -> Array<A, D>
 where
-    A: Num + ScalarOperand,
+    A: Num + NumCast + ScalarOperand,
     D: Dimension,
-    S: DataOwned<Elem = A>,
+    R: Rng + ?Sized,
 {
-    // Create a Bernoulli distribution for dropout
     let distribution = Bernoulli::new(p).unwrap();
+    let mask: Array<bool, D> = Array::random_using(dim, distribution, rng);
+    let scale = if p < 1.0 {
+        <A as NumCast>::from((1f64 - p).recip()).unwrap()
+    } else {
+        A::zero()
+    };
+    mask.mapv(|x| if x { A::zero() } else { scale.clone() })
+}
 
-    // Create a mask of the same shape as the input array
-    let mask: Array<bool, D> = Array::random(array.dim(), distribution);
-    let mask = mask.mapv(|x| if x { A::zero() } else { A::one() });
+#[cfg(feature = "rand")]
+pub(crate) fn _dropout<A, S, D, R>(array: &ArrayBase<S, D>, p: f64, rng: &mut R) -> Array<A, D>
+where
+    A: Num + NumCast + ScalarOperand,
+    D: Dimension,
+    R: Rng + ?Sized,
+    S: Data<Elem = A>,
+{
+    // Create a mask of the same shape as the input array, scaling the survivors by 1 / (1 - p)
+    // to preserve the expected value of each element
+    let mask = _mask(array.raw_dim(), p, rng);
 
     // Element-wise multiplication to apply dropout
     array * mask
 }
 
-/// [Dropout] randomly zeroizes elements with a given probability (`p`).
+/// [DropOut] randomly zeroizes elements with a given probability (`p`), scaling the remaining
+/// elements by `1 / (1 - p)`.
 pub trait DropOut {
     type Output;
 
     fn dropout(&self, p: f64) -> Self::Output;
 }
 
-/// The [DropoutLayer] layer is randomly zeroizes inputs with a given probability (`p`).
+/// [DropOutExt] extends [DropOut] with the structured variants of dropout.
+pub trait DropOutExt: DropOut {
+    /// Zeroizes entire slices along the given axis; see [ChannelDropout].
+    fn channel_dropout(&self, axis: Axis, p: f64) -> Self::Output;
+    /// Drops elements while preserving the mean and variance of their inputs; see
+    /// [AlphaDropout].
+    fn alpha_dropout(&self, p: f64) -> Self::Output;
+    /// Zeroizes entire samples, i.e. slices along the first axis; see [DropPath].
+    fn drop_path(&self, p: f64) -> Self::Output;
+}
+
+/// The [Dropout] layer randomly zeroizes inputs with a given probability (`p`) while
+/// [training](Mode::Train), scaling the survivors by `1 / (1 - p)` (inverted dropout) so that
+/// the layer may act as the identity during [evaluation](Mode::Eval).
 /// This regularization technique is often used to prevent overfitting.
 ///
-///
 /// ### Config
 ///
 /// - (p) Probability of dropping an element
+///
+/// ### Reproducibility
+///
+/// The layer holds no generator of its own: [apply](Dropout::apply) draws its masks from the
+/// thread-local generator, while [apply_with](Dropout::apply_with) draws them from the one
+/// given, e.g. a [fork](crate::init::RngContext::fork) of a seeded context.
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Dropout {
     pub(crate) p: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) mode: Mode,
 }
 
 /*
@@ -54,29 +130,76 @@ pub struct Dropout {
 #[cfg(feature = "rand")]
 impl<A, S, D> DropOut for ArrayBase<S, D>
 where
-    A: Num + ScalarOperand,
+    A: Num + NumCast + ScalarOperand,
     D: Dimension,
     S: DataOwned<Elem = A>,
 {
     type Output = Array<A, D>;
 
     fn dropout(&self, p: f64) -> Self::Output {
-        _dropout(self, p)
+        _dropout(self, p, &mut ndrand::rand::thread_rng())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<A, S, D> DropOutExt for ArrayBase<S, D>
+where
+    A: Num + NumCast + ScalarOperand,
+    D: Dimension,
+    S: DataOwned<Elem = A>,
+{
+    fn channel_dropout(&self, axis: Axis, p: f64) -> Self::Output {
+        _channel_dropout(self, axis, p, &mut ndrand::rand::thread_rng())
+    }
+
+    fn alpha_dropout(&self, p: f64) -> Self::Output {
+        _alpha_dropout(self, p, &mut ndrand::rand::thread_rng())
+    }
+
+    fn drop_path(&self, p: f64) -> Self::Output {
+        _drop_path(self, p, &mut ndrand::rand::thread_rng())
     }
 }
 
 impl Dropout {
     pub fn new(p: f64) -> Self {
-        Self { p }
+        Self {
+            mode: Mode::Train,
+            p,
+        }
+    }
+
+    pub fn with_mode(self, mode: Mode) -> Self {
+        Self { mode, ..self }
     }
+    /// Applies the layer, drawing the mask from the thread-local generator; in
+    /// [evaluation](Mode::Eval) mode, the input is returned as is.
     #[cfg(feature = "rand")]
     pub fn apply<A, S, D>(&self, input: &ArrayBase<S, D>) -> Array<A, D>
     where
-        A: Num + ScalarOperand,
+        A: Num + NumCast + ScalarOperand,
+        D: Dimension,
+        S: DataOwned<Elem = A>,
+    {
+        self.apply_with(input, &mut ndrand::rand::thread_rng())
+    }
+    /// Applies the layer, drawing the mask from the given generator.
+    #[cfg(feature = "rand")]
+    pub fn apply_with<A, S, D, R>(&self, input: &ArrayBase<S, D>, rng: &mut R) -> Array<A, D>
+    where
+        A: Num + NumCast + ScalarOperand,
         D: Dimension,
+        R: Rng + ?Sized,
         S: DataOwned<Elem = A>,
     {
-        _dropout(input, self.p)
+        if !self.is_training() {
+            return input.to_owned();
+        }
+        _dropout(input, self.p, rng)
+    }
+
+    pub const fn p(&self) -> f64 {
+        self.p
     }
 
     pub fn scale(&self) -> f64 {
@@ -90,44 +213,54 @@ impl Default for Dropout {
     }
 }
 
+impl Training for Dropout {
+    fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+}
+
 #[cfg(feature = "rand")]
 impl<A, S, D> Eval<ArrayBase<S, D>> for Dropout
 where
-    A: Num + ScalarOperand,
+    A: Num + NumCast + ScalarOperand,
     D: Dimension,
     S: DataOwned<Elem = A>,
 {
     type Output = Array<A, D>;
 
     fn eval(&self, input: ArrayBase<S, D>) -> Self::Output {
-        input.dropout(self.p)
+        self.apply(&input)
     }
 }
 
 #[cfg(feature = "rand")]
 impl<'a, A, S, D> Eval<&'a ArrayBase<S, D>> for Dropout
 where
-    A: Num + ScalarOperand,
+    A: Num + NumCast + ScalarOperand,
     D: Dimension,
     S: DataOwned<Elem = A>,
 {
     type Output = Array<A, D>;
 
     fn eval(&self, input: &'a ArrayBase<S, D>) -> Self::Output {
-        input.dropout(self.p)
+        self.apply(input)
     }
 }
 
 #[cfg(feature = "rand")]
 impl<A, S, D> Predict<ArrayBase<S, D>> for Dropout
 where
-    A: Num + ScalarOperand,
+    A: Num + NumCast + ScalarOperand,
     D: Dimension,
     S: DataOwned<Elem = A>,
 {
     type Output = Array<A, D>;
 
     fn predict(&self, input: &ArrayBase<S, D>) -> Result<Self::Output, PredictError> {
-        Ok(input.dropout(self.p))
+        Ok(self.apply(input))
     }
 }
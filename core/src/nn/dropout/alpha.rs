@@ -0,0 +1,113 @@
+/*
+    Appellation: alpha <dropout>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+#![allow(unused_imports)]
+use super::Dropout;
+use crate::nn::Training;
+use crate::{Predict, PredictError};
+use nd::prelude::*;
+use nd::{Data, DataOwned, ScalarOperand, Zip};
+#[cfg(feature = "rand")]
+use ndrand::{rand::Rng, rand_distr::Bernoulli, RandomExt};
+use num::traits::{Num, NumCast};
+
+/// The value onto which the [selu](crate::func::activate::selu) saturates,
+/// `-scale * alpha`.
+#[cfg(feature = "rand")]
+pub(crate) const SELU_SATURATION: f64 = -1.0507009873554805 * 1.6732632423543772;
+
+#[cfg(feature = "rand")]
+pub(crate) fn _alpha_dropout<A, S, D, R>(
+    array: &ArrayBase<S, D>,
+    p: f64,
+    rng: &mut R,
+) -> Array<A, D>
+where
+    A: Num + NumCast + ScalarOperand,
+    D: Dimension,
+    R: Rng + ?Sized,
+    S: Data<Elem = A>,
+{
+    if p >= 1.0 {
+        return Array::from_elem(array.raw_dim(), A::zero());
+    }
+    let c = |x: f64| <A as NumCast>::from(x).unwrap();
+    // the affine transformation restoring the mean and variance of the inputs
+    let alpha = SELU_SATURATION;
+    let a = ((1.0 - p) * (1.0 + p * alpha * alpha)).recip().sqrt();
+    let b = -a * alpha * p;
+
+    let mask: Array<bool, D> =
+        Array::random_using(array.raw_dim(), Bernoulli::new(p).unwrap(), rng);
+    let (dropped, a, b) = (c(a * alpha + b), c(a), c(b));
+    Zip::from(array).and(&mask).map_collect(|x, &drop| {
+        if drop {
+            dropped.clone()
+        } else {
+            a.clone() * x.clone() + b.clone()
+        }
+    })
+}
+
+/// [AlphaDropout] is a variant of [Dropout] intended for self-normalizing networks, i.e.
+/// those using the [SELU](crate::func::activate::Activation::SELU) activation.
+///
+/// Rather than zeroizing them, dropped elements are set to the value onto which the SELU
+/// saturates, `-scale * alpha`, before an affine transformation is applied to every element
+/// so that, given inputs of zero mean and unit variance, both are preserved. The layer is
+/// the identity during evaluation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AlphaDropout {
+    pub(crate) dropout: Dropout,
+}
+
+impl AlphaDropout {
+    pub fn new(p: f64) -> Self {
+        Self {
+            dropout: Dropout::new(p),
+        }
+    }
+    /// Applies the layer, drawing the mask from the thread-local generator; in
+    /// [evaluation](crate::nn::Mode::Eval) mode, the input is returned as is.
+    #[cfg(feature = "rand")]
+    pub fn apply<A, S, D>(&self, input: &ArrayBase<S, D>) -> Array<A, D>
+    where
+        A: Num + NumCast + ScalarOperand,
+        D: Dimension,
+        S: DataOwned<Elem = A>,
+    {
+        self.apply_with(input, &mut ndrand::rand::thread_rng())
+    }
+    /// Applies the layer, drawing the mask from the given generator.
+    #[cfg(feature = "rand")]
+    pub fn apply_with<A, S, D, R>(&self, input: &ArrayBase<S, D>, rng: &mut R) -> Array<A, D>
+    where
+        A: Num + NumCast + ScalarOperand,
+        D: Dimension,
+        R: Rng + ?Sized,
+        S: DataOwned<Elem = A>,
+    {
+        if !self.is_training() {
+            return input.to_owned();
+        }
+        _alpha_dropout(input, self.p(), rng)
+    }
+}
+
+dropout_variant!(AlphaDropout);
+
+#[cfg(feature = "rand")]
+impl<A, S, D> Predict<ArrayBase<S, D>> for AlphaDropout
+where
+    A: Num + NumCast + ScalarOperand,
+    D: Dimension,
+    S: DataOwned<Elem = A>,
+{
+    type Output = Array<A, D>;
+
+    fn predict(&self, input: &ArrayBase<S, D>) -> Result<Self::Output, PredictError> {
+        Ok(self.apply(input))
+    }
+}
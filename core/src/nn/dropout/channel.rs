@@ -0,0 +1,114 @@
+/*
+    Appellation: channel <dropout>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+#![allow(unused_imports)]
+#[cfg(feature = "rand")]
+use super::_mask;
+use super::Dropout;
+use crate::nn::Training;
+use crate::{Predict, PredictError};
+use nd::prelude::*;
+use nd::{Data, DataOwned, ScalarOperand};
+#[cfg(feature = "rand")]
+use ndrand::rand::Rng;
+use num::traits::{Num, NumCast};
+
+#[cfg(feature = "rand")]
+pub(crate) fn _channel_dropout<A, S, D, R>(
+    array: &ArrayBase<S, D>,
+    axis: Axis,
+    p: f64,
+    rng: &mut R,
+) -> Array<A, D>
+where
+    A: Num + NumCast + ScalarOperand,
+    D: Dimension,
+    R: Rng + ?Sized,
+    S: Data<Elem = A>,
+{
+    // the mask spans the axes up to, and including, the given axis; it is broadcast across
+    // the remaining ones so that each slice is either kept or dropped as a whole
+    let mut dim = array.raw_dim();
+    for i in axis.index() + 1..dim.ndim() {
+        dim[i] = 1;
+    }
+    let mask = _mask(dim, p, rng);
+    array * &mask
+}
+
+/// [ChannelDropout], or spatial dropout, zeroizes entire channels rather than individual
+/// elements; this is better suited to inputs whose neighbouring elements are strongly
+/// correlated, e.g. the feature maps of a convolution.
+///
+/// A mask is drawn over the axes up to, and including, the channel `axis` and broadcast
+/// across the remaining ones; given inputs of shape `(batch, channels, height, width)` and
+/// an axis of one, each sample drops its own set of channels. As with [Dropout], the
+/// survivors are scaled by `1 / (1 - p)` and the layer is the identity during evaluation.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ChannelDropout {
+    pub(crate) axis: usize,
+    pub(crate) dropout: Dropout,
+}
+
+impl ChannelDropout {
+    pub fn new(axis: Axis, p: f64) -> Self {
+        Self {
+            axis: axis.index(),
+            dropout: Dropout::new(p),
+        }
+    }
+    /// Returns the axis along which channels are dropped.
+    pub const fn axis(&self) -> Axis {
+        Axis(self.axis)
+    }
+
+    pub fn with_axis(self, axis: Axis) -> Self {
+        Self {
+            axis: axis.index(),
+            ..self
+        }
+    }
+    /// Applies the layer, drawing the mask from the thread-local generator; in
+    /// [evaluation](crate::nn::Mode::Eval) mode, the input is returned as is.
+    #[cfg(feature = "rand")]
+    pub fn apply<A, S, D>(&self, input: &ArrayBase<S, D>) -> Array<A, D>
+    where
+        A: Num + NumCast + ScalarOperand,
+        D: Dimension,
+        S: DataOwned<Elem = A>,
+    {
+        self.apply_with(input, &mut ndrand::rand::thread_rng())
+    }
+    /// Applies the layer, drawing the mask from the given generator.
+    #[cfg(feature = "rand")]
+    pub fn apply_with<A, S, D, R>(&self, input: &ArrayBase<S, D>, rng: &mut R) -> Array<A, D>
+    where
+        A: Num + NumCast + ScalarOperand,
+        D: Dimension,
+        R: Rng + ?Sized,
+        S: DataOwned<Elem = A>,
+    {
+        if !self.is_training() {
+            return input.to_owned();
+        }
+        _channel_dropout(input, self.axis(), self.p(), rng)
+    }
+}
+
+dropout_variant!(ChannelDropout);
+
+#[cfg(feature = "rand")]
+impl<A, S, D> Predict<ArrayBase<S, D>> for ChannelDropout
+where
+    A: Num + NumCast + ScalarOperand,
+    D: Dimension,
+    S: DataOwned<Elem = A>,
+{
+    type Output = Array<A, D>;
+
+    fn predict(&self, input: &ArrayBase<S, D>) -> Result<Self::Output, PredictError> {
+        Ok(self.apply(input))
+    }
+}
@@ -0,0 +1,83 @@
+/*
+    Appellation: connect <dropout>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+#![allow(unused_imports)]
+#[cfg(feature = "rand")]
+use super::_mask;
+use super::Dropout;
+use crate::nn::Training;
+use nd::prelude::*;
+use nd::{Data, ScalarOperand};
+#[cfg(feature = "rand")]
+use ndrand::rand::Rng;
+use num::traits::{Num, NumCast};
+
+/// [DropConnect] generalizes [Dropout] to the connections of a layer, randomly zeroizing its
+/// weights rather than its activations with a given probability (`p`).
+///
+/// The layer masks the weights it is given, scaling the survivors by `1 / (1 - p)`; a
+/// fresh mask should be drawn for every forward pass while training, leaving the weights
+/// untouched during evaluation. Layers holding their own [DropConnect], e.g. a linear layer,
+/// multiply their weights by its [mask](DropConnect::mask) before applying them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DropConnect {
+    pub(crate) dropout: Dropout,
+}
+
+impl DropConnect {
+    pub fn new(p: f64) -> Self {
+        Self {
+            dropout: Dropout::new(p),
+        }
+    }
+    /// Masks the given weights, drawing from the thread-local generator; in
+    /// [evaluation](crate::nn::Mode::Eval) mode, the weights are returned as is.
+    #[cfg(feature = "rand")]
+    pub fn apply<A, S, D>(&self, weights: &ArrayBase<S, D>) -> Array<A, D>
+    where
+        A: Num + NumCast + ScalarOperand,
+        D: Dimension,
+        S: Data<Elem = A>,
+    {
+        self.apply_with(weights, &mut ndrand::rand::thread_rng())
+    }
+    /// Masks the given weights, drawing from the given generator.
+    #[cfg(feature = "rand")]
+    pub fn apply_with<A, S, D, R>(&self, weights: &ArrayBase<S, D>, rng: &mut R) -> Array<A, D>
+    where
+        A: Num + NumCast + ScalarOperand,
+        D: Dimension,
+        R: Rng + ?Sized,
+        S: Data<Elem = A>,
+    {
+        match self.mask_with(weights.raw_dim(), rng) {
+            Some(mask) => weights * &mask,
+            None => weights.to_owned(),
+        }
+    }
+    /// Draws a mask for weights of the given shape from the thread-local generator; see
+    /// [mask_with](DropConnect::mask_with).
+    #[cfg(feature = "rand")]
+    pub fn mask<A, D>(&self, dim: D) -> Option<Array<A, D>>
+    where
+        A: Num + NumCast + ScalarOperand,
+        D: Dimension,
+    {
+        self.mask_with(dim, &mut ndrand::rand::thread_rng())
+    }
+    /// Draws a mask for weights of the given shape whose elements are either zero or
+    /// `1 / (1 - p)`; no mask is drawn during [evaluation](crate::nn::Mode::Eval).
+    #[cfg(feature = "rand")]
+    pub fn mask_with<A, D, R>(&self, dim: D, rng: &mut R) -> Option<Array<A, D>>
+    where
+        A: Num + NumCast + ScalarOperand,
+        D: Dimension,
+        R: Rng + ?Sized,
+    {
+        self.is_training().then(|| _mask(dim, self.p(), rng))
+    }
+}
+
+dropout_variant!(DropConnect);
@@ -0,0 +1,87 @@
+/*
+    Appellation: path <dropout>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+#![allow(unused_imports)]
+#[cfg(feature = "rand")]
+use super::_channel_dropout;
+use super::Dropout;
+use crate::nn::Training;
+use crate::{Predict, PredictError};
+use nd::prelude::*;
+use nd::{Data, DataOwned, ScalarOperand};
+#[cfg(feature = "rand")]
+use ndrand::rand::Rng;
+use num::traits::{Num, NumCast};
+
+#[cfg(feature = "rand")]
+pub(crate) fn _drop_path<A, S, D, R>(array: &ArrayBase<S, D>, p: f64, rng: &mut R) -> Array<A, D>
+where
+    A: Num + NumCast + ScalarOperand,
+    D: Dimension,
+    R: Rng + ?Sized,
+    S: Data<Elem = A>,
+{
+    _channel_dropout(array, Axis(0), p, rng)
+}
+
+/// [DropPath] implements stochastic depth, dropping the residual branch of a block for entire
+/// samples, i.e. slices along the first axis, with a given probability (`p`).
+///
+/// Applied to the output of a branch before it is added back onto its input, the block
+/// reduces to the identity for the dropped samples. Survivors are scaled by `1 / (1 - p)`
+/// and the layer is the identity during evaluation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DropPath {
+    pub(crate) dropout: Dropout,
+}
+
+impl DropPath {
+    pub fn new(p: f64) -> Self {
+        Self {
+            dropout: Dropout::new(p),
+        }
+    }
+    /// Applies the layer, drawing the mask from the thread-local generator; in
+    /// [evaluation](crate::nn::Mode::Eval) mode, the input is returned as is.
+    #[cfg(feature = "rand")]
+    pub fn apply<A, S, D>(&self, input: &ArrayBase<S, D>) -> Array<A, D>
+    where
+        A: Num + NumCast + ScalarOperand,
+        D: Dimension,
+        S: DataOwned<Elem = A>,
+    {
+        self.apply_with(input, &mut ndrand::rand::thread_rng())
+    }
+    /// Applies the layer, drawing the mask from the given generator.
+    #[cfg(feature = "rand")]
+    pub fn apply_with<A, S, D, R>(&self, input: &ArrayBase<S, D>, rng: &mut R) -> Array<A, D>
+    where
+        A: Num + NumCast + ScalarOperand,
+        D: Dimension,
+        R: Rng + ?Sized,
+        S: DataOwned<Elem = A>,
+    {
+        if !self.is_training() {
+            return input.to_owned();
+        }
+        _drop_path(input, self.p(), rng)
+    }
+}
+
+dropout_variant!(DropPath);
+
+#[cfg(feature = "rand")]
+impl<A, S, D> Predict<ArrayBase<S, D>> for DropPath
+where
+    A: Num + NumCast + ScalarOperand,
+    D: Dimension,
+    S: DataOwned<Elem = A>,
+{
+    type Output = Array<A, D>;
+
+    fn predict(&self, input: &ArrayBase<S, D>) -> Result<Self::Output, PredictError> {
+        Ok(self.apply(input))
+    }
+}
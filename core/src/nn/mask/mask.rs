@@ -2,10 +2,19 @@
     Appellation: mask <module>
     Contrib: FL03 <jo3mccain@icloud.com>
 */
+use crate::func::activate::softmax_axis;
+use crate::utils::tril;
 use nd::iter::{Iter, IterMut};
 use nd::prelude::*;
-use nd::{Data, DataMut, OwnedRepr, RawData, RawDataClone};
+use nd::{Data, DataMut, OwnedRepr, RawData, RawDataClone, ScalarOperand, Zip};
+use num::complex::ComplexFloat;
 
+/// A [Mask] is a boolean array whose `true` elements mark the positions to be masked, i.e.
+/// excluded; for attention, the scores of a mask are indexed by `(query, key)`.
+///
+/// Masks are combined using [and](Mask::and) and [or](Mask::or); since `true` denotes an
+/// excluded position, the union of two masks, e.g. a [causal](Mask::causal) mask with a
+/// [padding](Mask::padding) mask, is given by the latter.
 pub struct Mask<S = OwnedRepr<bool>, D = Ix2>(ArrayBase<S, D>)
 where
     D: Dimension,
@@ -114,14 +123,123 @@ where
     }
 }
 
+impl Mask<OwnedRepr<bool>, Ix2> {
+    /// Creates a causal, or look-ahead, mask preventing each of the `n` positions from
+    /// attending to those following it, i.e. masking every `(i, j)` where `j > i`.
+    pub fn causal(n: usize) -> Self {
+        let visible = tril(&Array2::<u8>::ones((n, n)));
+        Self(visible.mapv(|x| x == 0))
+    }
+    /// Creates a mask for a sequence of `seq_len` positions, of which only the first `length`
+    /// are valid; every query is prevented from attending to the padded keys.
+    pub fn padding(length: usize, seq_len: usize) -> Self {
+        Self(Array2::from_shape_fn((seq_len, seq_len), |(_, j)| {
+            j >= length
+        }))
+    }
+    /// Creates a mask restricting each of the `n` positions to those within `window` of it,
+    /// i.e. masking every `(i, j)` where `|i - j| > window`; combine it with a
+    /// [causal](Mask::causal) mask to only attend to the preceding window.
+    pub fn sliding_window(n: usize, window: usize) -> Self {
+        Self(Array2::from_shape_fn((n, n), |(i, j)| {
+            i.abs_diff(j) > window
+        }))
+    }
+}
+
+impl Mask<OwnedRepr<bool>, Ix3> {
+    /// Creates a batch of [padding](Mask::padding) masks of shape `(batch, seq_len, seq_len)`
+    /// from the lengths of each sequence.
+    pub fn key_padding(lengths: &[usize], seq_len: usize) -> Self {
+        let dim = (lengths.len(), seq_len, seq_len);
+        Self(Array3::from_shape_fn(dim, |(b, _, j)| j >= lengths[b]))
+    }
+}
+
+impl<S, D> Mask<S, D>
+where
+    D: Dimension,
+    S: Data<Elem = bool>,
+{
+    /// Returns a mask of the positions masked by both masks.
+    pub fn and<T>(&self, other: &Mask<T, D>) -> Mask<OwnedRepr<bool>, D>
+    where
+        T: Data<Elem = bool>,
+    {
+        Mask(
+            Zip::from(&self.0)
+                .and(&other.0)
+                .map_collect(|&a, &b| a && b),
+        )
+    }
+    /// Returns a mask of the positions masked by either mask.
+    pub fn or<T>(&self, other: &Mask<T, D>) -> Mask<OwnedRepr<bool>, D>
+    where
+        T: Data<Elem = bool>,
+    {
+        Mask(
+            Zip::from(&self.0)
+                .and(&other.0)
+                .map_collect(|&a, &b| a || b),
+        )
+    }
+    /// Fills the masked positions of the given scores with negative infinity, such that they
+    /// are given a probability of zero by a subsequent softmax.
+    pub fn fill_scores<A, T>(&self, scores: &ArrayBase<T, D>) -> Array<A, D>
+    where
+        A: Clone + num::NumCast,
+        T: Data<Elem = A>,
+    {
+        let neg_inf = <A as num::NumCast>::from(f64::NEG_INFINITY).unwrap();
+        let mut res = scores.to_owned();
+        res.zip_mut_with(&self.0, |x, &m| {
+            if m {
+                *x = neg_inf.clone();
+            }
+        });
+        res
+    }
+    /// Returns a mask of the lanes along the given axis in which every position is masked.
+    pub fn full_lanes(&self, axis: Axis) -> Mask<OwnedRepr<bool>, D> {
+        let mut full = Array::from_elem(self.raw_dim(), false);
+        Zip::from(self.0.lanes(axis))
+            .and(full.lanes_mut(axis))
+            .for_each(|lane, mut res| res.fill(lane.iter().all(|&m| m)));
+        Mask(full)
+    }
+    /// Computes the softmax of the given scores along `axis`, assigning a probability of
+    /// exactly zero to every masked position; lanes in which every position is masked are
+    /// zeroed rather than left undefined.
+    pub fn softmax_axis<A, T>(&self, scores: &ArrayBase<T, D>, axis: usize) -> Array<A, D>
+    where
+        A: ComplexFloat + ScalarOperand,
+        T: Data<Elem = A>,
+    {
+        let full = self.full_lanes(Axis(axis));
+        let mut z = self.and(&!&full).fill_scores(scores);
+        z.zip_mut_with(&full.0, |x, &m| {
+            if m {
+                *x = A::zero();
+            }
+        });
+        let mut res = softmax_axis(&z, axis);
+        res.zip_mut_with(&self.0, |x, &m| {
+            if m {
+                *x = A::zero();
+            }
+        });
+        res
+    }
+}
+
 /*
  ************* Implementations *************
 */
 mod impls {
     use super::Mask;
     use core::borrow::{Borrow, BorrowMut};
-    use core::ops::{Deref, DerefMut, Index, IndexMut};
-    use nd::{ArrayBase, Data, DataMut, Dimension, NdIndex, RawData};
+    use core::ops::{Deref, DerefMut, Index, IndexMut, Not};
+    use nd::{ArrayBase, Data, DataMut, Dimension, NdIndex, OwnedRepr, RawData};
 
     impl<S, D> AsRef<ArrayBase<S, D>> for Mask<S, D>
     where
@@ -198,6 +316,30 @@ mod impls {
         }
     }
 
+    impl<S, D> Not for Mask<S, D>
+    where
+        D: Dimension,
+        S: Data<Elem = bool>,
+    {
+        type Output = Mask<OwnedRepr<bool>, D>;
+
+        fn not(self) -> Self::Output {
+            !&self
+        }
+    }
+
+    impl<S, D> Not for &Mask<S, D>
+    where
+        D: Dimension,
+        S: Data<Elem = bool>,
+    {
+        type Output = Mask<OwnedRepr<bool>, D>;
+
+        fn not(self) -> Self::Output {
+            Mask(self.0.mapv(|m| !m))
+        }
+    }
+
     impl<S, D, I> IndexMut<I> for Mask<S, D>
     where
         D: Dimension,
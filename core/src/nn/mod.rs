@@ -4,19 +4,20 @@
 */
 #[cfg(any(feature = "alloc", feature = "std"))]
 pub use self::types::*;
-pub use self::{dropout::*, error::ModelError, model::prelude::*};
+pub use self::{dropout::*, error::ModelError, mode::*, model::prelude::*};
 
 pub mod dropout;
 pub mod error;
 pub mod mask;
+pub mod mode;
 pub mod model;
-#[doc(hidden)]
 pub mod optim;
 
 pub(crate) mod prelude {
     pub use super::dropout::*;
     pub use super::error::*;
     pub use super::mask::prelude::*;
+    pub use super::mode::*;
     pub use super::model::prelude::*;
     pub use super::optim::prelude::*;
 }
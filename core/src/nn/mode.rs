@@ -0,0 +1,57 @@
+/*
+    Appellation: mode <nn>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use strum::{AsRefStr, Display, EnumCount, EnumIs, EnumIter, EnumString, VariantNames};
+
+/// [Mode] distinguishes the training of a model from its evaluation; layers such as
+/// [Dropout](super::Dropout) are only active while training and act as the identity otherwise.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumCount,
+    EnumIs,
+    EnumIter,
+    EnumString,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    VariantNames,
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum Mode {
+    #[default]
+    Train,
+    Eval,
+}
+
+/// [Training] is implemented by layers whose behaviour depends upon the [Mode]; containers
+/// propagate the mode to each of their layers.
+pub trait Training {
+    fn mode(&self) -> Mode;
+
+    fn set_mode(&mut self, mode: Mode);
+    /// Returns true if the layer is in [training](Mode::Train) mode.
+    fn is_training(&self) -> bool {
+        self.mode().is_train()
+    }
+    /// Switches the layer into [evaluation](Mode::Eval) mode.
+    fn eval_mode(&mut self) {
+        self.set_mode(Mode::Eval)
+    }
+    /// Switches the layer into [training](Mode::Train) mode.
+    fn train_mode(&mut self) {
+        self.set_mode(Mode::Train)
+    }
+}
@@ -0,0 +1,107 @@
+/*
+    Appellation: adagrad <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::optimizer::{buffer, buffer_with, hyper, tick};
+use super::{Optimize, OptimizerConfig};
+use crate::rust::Vec;
+use nd::{ArrayD, ArrayViewD, ArrayViewMutD, ScalarOperand, Zip};
+use num::traits::Float;
+
+/// [Adagrad] adapts the learning rate of each element according to the sum of its squared
+/// gradients, as described in
+/// [Adaptive Subgradient Methods for Online Learning and Stochastic Optimization](https://jmlr.org/papers/v12/duchi11a.html).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct Adagrad<A = f64> {
+    pub(crate) config: OptimizerConfig,
+    pub(crate) steps: Vec<usize>,
+    pub(crate) sum: Vec<Option<ArrayD<A>>>,
+}
+
+/// [Adadelta] extends [Adagrad] by restricting the accumulated gradients to a decaying window,
+/// as described in [ADADELTA: An Adaptive Learning Rate Method](https://arxiv.org/abs/1212.5701).
+///
+/// As the updates are already scaled by the ratio of the accumulated deltas and gradients,
+/// the learning rate defaults to one rather than to that of the shared [OptimizerConfig].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct Adadelta<A = f64> {
+    pub(crate) config: OptimizerConfig,
+    pub(crate) acc_delta: Vec<Option<ArrayD<A>>>,
+    pub(crate) square_avg: Vec<Option<ArrayD<A>>>,
+}
+
+optimizer!(Adagrad { steps, sum });
+optimizer!(Adadelta {
+    acc_delta,
+    square_avg
+});
+
+impl<A> Default for Adadelta<A> {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl<A> Optimize<A> for Adagrad<A>
+where
+    A: Float + ScalarOperand,
+{
+    fn update(&mut self, slot: usize, mut param: ArrayViewMutD<'_, A>, grad: ArrayViewD<'_, A>) {
+        let t = tick(&mut self.steps, slot);
+        let decay = hyper::<A>(self.config.lr_decay) * A::from(t - 1).unwrap();
+        let lr = hyper::<A>(self.config.learning_rate) / (A::one() + decay);
+        let (eps, wd) = (
+            hyper::<A>(self.config.eps),
+            hyper::<A>(self.config.weight_decay),
+        );
+        let init = hyper::<A>(self.config.initial_accumulator_value);
+        let sum = buffer_with(&mut self.sum, slot, grad.raw_dim(), init);
+        Zip::from(&mut param)
+            .and(&grad)
+            .and(sum)
+            .for_each(|p, &g, s| {
+                let g = g + wd * *p;
+                *s = *s + g * g;
+                *p = *p - lr * g / (s.sqrt() + eps);
+            });
+    }
+}
+
+impl<A> Optimize<A> for Adadelta<A>
+where
+    A: Float + ScalarOperand,
+{
+    fn update(&mut self, slot: usize, mut param: ArrayViewMutD<'_, A>, grad: ArrayViewD<'_, A>) {
+        let (lr, rho) = (
+            hyper::<A>(self.config.learning_rate),
+            hyper::<A>(self.config.rho),
+        );
+        let (eps, wd) = (
+            hyper::<A>(self.config.eps),
+            hyper::<A>(self.config.weight_decay),
+        );
+        let square_avg = buffer(&mut self.square_avg, slot, grad.raw_dim());
+        let acc_delta = buffer(&mut self.acc_delta, slot, grad.raw_dim());
+        Zip::from(&mut param)
+            .and(&grad)
+            .and(square_avg)
+            .and(acc_delta)
+            .for_each(|p, &g, v, u| {
+                let g = g + wd * *p;
+                *v = rho * *v + (A::one() - rho) * g * g;
+                let delta = (*u + eps).sqrt() / (*v + eps).sqrt() * g;
+                *u = rho * *u + (A::one() - rho) * delta * delta;
+                *p = *p - lr * delta;
+            });
+    }
+}
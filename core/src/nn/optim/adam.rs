@@ -0,0 +1,202 @@
+/*
+    Appellation: adam <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+//! # Adaptive Moment Estimation
+//!
+//! This module implements the family of optimizers derived from
+//! [Adam: A Method for Stochastic Optimization](https://arxiv.org/abs/1412.6980), each of which
+//! maintains running estimates of the first and second moments of the gradient.
+use super::optimizer::{buffer, hyper, tick};
+use super::{Optimize, OptimizerConfig};
+use crate::rust::Vec;
+use nd::{ArrayD, ArrayViewD, ArrayViewMutD, ScalarOperand, Zip};
+use num::traits::Float;
+
+/// [Adam] scales each update by the running estimates of the first and second moments of the
+/// gradient; the weight decay is added to the gradient as an L2 penalty.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct Adam<A = f64> {
+    pub(crate) config: OptimizerConfig,
+    pub(crate) m: Vec<Option<ArrayD<A>>>,
+    pub(crate) steps: Vec<usize>,
+    pub(crate) v: Vec<Option<ArrayD<A>>>,
+}
+
+/// [AdamW] is a variant of [Adam] whose weight decay is decoupled from the gradient, as
+/// described in [Decoupled Weight Decay Regularization](https://arxiv.org/abs/1711.05101).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct AdamW<A = f64> {
+    pub(crate) config: OptimizerConfig,
+    pub(crate) m: Vec<Option<ArrayD<A>>>,
+    pub(crate) steps: Vec<usize>,
+    pub(crate) v: Vec<Option<ArrayD<A>>>,
+}
+
+/// [AmsGrad] is a variant of [Adam] normalizing each update by the maximum of the second moment
+/// estimates observed so far, as described in
+/// [On the Convergence of Adam and Beyond](https://openreview.net/forum?id=ryQu7f-RZ).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct AmsGrad<A = f64> {
+    pub(crate) config: OptimizerConfig,
+    pub(crate) m: Vec<Option<ArrayD<A>>>,
+    pub(crate) steps: Vec<usize>,
+    pub(crate) v: Vec<Option<ArrayD<A>>>,
+    pub(crate) v_max: Vec<Option<ArrayD<A>>>,
+}
+
+/// [Adamax] is a variant of [Adam] based on the infinity norm; the second moment is replaced
+/// by an exponentially weighted maximum of the absolute gradient.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct Adamax<A = f64> {
+    pub(crate) config: OptimizerConfig,
+    pub(crate) m: Vec<Option<ArrayD<A>>>,
+    pub(crate) steps: Vec<usize>,
+    pub(crate) u: Vec<Option<ArrayD<A>>>,
+}
+
+optimizer!(Adam { m, steps, v });
+optimizer!(AdamW { m, steps, v });
+optimizer!(AmsGrad { m, steps, v, v_max });
+optimizer!(Adamax { m, steps, u });
+
+/*
+ ************* Implementations *************
+*/
+impl<A> Optimize<A> for Adam<A>
+where
+    A: Float + ScalarOperand,
+{
+    fn update(&mut self, slot: usize, param: ArrayViewMutD<'_, A>, grad: ArrayViewD<'_, A>) {
+        let t = tick(&mut self.steps, slot);
+        let m = buffer(&mut self.m, slot, grad.raw_dim());
+        let v = buffer(&mut self.v, slot, grad.raw_dim());
+        adam(&self.config, t, false, param, grad, m, v, None)
+    }
+}
+
+impl<A> Optimize<A> for AdamW<A>
+where
+    A: Float + ScalarOperand,
+{
+    fn update(&mut self, slot: usize, param: ArrayViewMutD<'_, A>, grad: ArrayViewD<'_, A>) {
+        let t = tick(&mut self.steps, slot);
+        let m = buffer(&mut self.m, slot, grad.raw_dim());
+        let v = buffer(&mut self.v, slot, grad.raw_dim());
+        adam(&self.config, t, true, param, grad, m, v, None)
+    }
+}
+
+impl<A> Optimize<A> for AmsGrad<A>
+where
+    A: Float + ScalarOperand,
+{
+    fn update(&mut self, slot: usize, param: ArrayViewMutD<'_, A>, grad: ArrayViewD<'_, A>) {
+        let t = tick(&mut self.steps, slot);
+        let m = buffer(&mut self.m, slot, grad.raw_dim());
+        let v = buffer(&mut self.v, slot, grad.raw_dim());
+        let v_max = buffer(&mut self.v_max, slot, grad.raw_dim());
+        adam(&self.config, t, false, param, grad, m, v, Some(v_max))
+    }
+}
+
+impl<A> Optimize<A> for Adamax<A>
+where
+    A: Float + ScalarOperand,
+{
+    fn update(&mut self, slot: usize, mut param: ArrayViewMutD<'_, A>, grad: ArrayViewD<'_, A>) {
+        let t = tick(&mut self.steps, slot);
+        let (b1, b2) = (
+            hyper::<A>(self.config.betas.0),
+            hyper::<A>(self.config.betas.1),
+        );
+        let (eps, wd) = (
+            hyper::<A>(self.config.eps),
+            hyper::<A>(self.config.weight_decay),
+        );
+        let mut lr = hyper::<A>(self.config.learning_rate);
+        if self.config.bias_correction {
+            lr = lr / (A::one() - b1.powi(t));
+        }
+        let m = buffer(&mut self.m, slot, grad.raw_dim());
+        let u = buffer(&mut self.u, slot, grad.raw_dim());
+        Zip::from(&mut param)
+            .and(&grad)
+            .and(m)
+            .and(u)
+            .for_each(|p, &g, m, u| {
+                let g = g + wd * *p;
+                *m = b1 * *m + (A::one() - b1) * g;
+                *u = (b2 * *u).max(g.abs() + eps);
+                *p = *p - lr * *m / *u;
+            });
+    }
+}
+
+/// The shared update rule of [Adam], [AdamW] and [AmsGrad].
+#[allow(clippy::too_many_arguments)]
+fn adam<A>(
+    config: &OptimizerConfig,
+    t: i32,
+    decoupled: bool,
+    mut param: ArrayViewMutD<'_, A>,
+    grad: ArrayViewD<'_, A>,
+    m: &mut ArrayD<A>,
+    v: &mut ArrayD<A>,
+    v_max: Option<&mut ArrayD<A>>,
+) where
+    A: Float + ScalarOperand,
+{
+    let (b1, b2) = (hyper::<A>(config.betas.0), hyper::<A>(config.betas.1));
+    let (lr, eps) = (hyper::<A>(config.learning_rate), hyper::<A>(config.eps));
+    let wd = hyper::<A>(config.weight_decay);
+    let (c1, c2) = if config.bias_correction {
+        (A::one() - b1.powi(t), A::one() - b2.powi(t))
+    } else {
+        (A::one(), A::one())
+    };
+    if decoupled {
+        let decay = A::one() - lr * wd;
+        param.mapv_inplace(|p| p * decay);
+    }
+    Zip::from(&param)
+        .and(&grad)
+        .and(&mut *m)
+        .and(&mut *v)
+        .for_each(|&p, &g, m, v| {
+            let g = if decoupled { g } else { g + wd * p };
+            *m = b1 * *m + (A::one() - b1) * g;
+            *v = b2 * *v + (A::one() - b2) * g * g;
+        });
+    let v = match v_max {
+        Some(v_max) => {
+            v_max.zip_mut_with(v, |vm, &vi| *vm = vm.max(vi));
+            v_max
+        }
+        None => v,
+    };
+    Zip::from(&mut param)
+        .and(&*m)
+        .and(&*v)
+        .for_each(|p, &m, &v| *p = *p - lr * (m / c1) / ((v / c2).sqrt() + eps));
+}
@@ -5,13 +5,109 @@
 //! # Optimizers
 //!
 //! This module contains various optimizers used for training neural networks.
-pub use self::optimizer::*;
+//!
+//! Optimizers implement [Optimize], updating any [Optimizable] parameter store given its
+//! gradients. Stateful optimizers keep one buffer per tensor, associated with the order in
+//! which the store visits its tensors. Optimizers may be wrapped to [regularize](Regularized)
+//! the gradients or [constrain](Constrained) the parameters of each update.
+pub use self::scheduler::{LrScheduler, Schedule, Scheduler};
+pub use self::{adagrad::*, adam::*, optimizer::*, params::*, regularize::*, rmsprop::*, sgd::*};
+
+macro_rules! optimizer {
+    ($name:ident { $($state:ident),* $(,)? }) => {
+        impl<A> $name<A> {
+            pub fn new(learning_rate: f64) -> Self {
+                Self::from_config($crate::nn::optim::OptimizerConfig::new().with_learning_rate(learning_rate))
+            }
+
+            pub fn from_config(config: $crate::nn::optim::OptimizerConfig) -> Self {
+                Self {
+                    config,
+                    $($state: Default::default()),*
+                }
+            }
+
+            /// Clears the internal state of the optimizer.
+            pub fn reset(&mut self) {
+                $(self.$state.clear();)*
+            }
+        }
+
+        impl<A> $crate::nn::optim::Hyperparams for $name<A> {
+            fn config(&self) -> &$crate::nn::optim::OptimizerConfig {
+                &self.config
+            }
+
+            fn config_mut(&mut self) -> &mut $crate::nn::optim::OptimizerConfig {
+                &mut self.config
+            }
+        }
+    };
+}
 
 pub(crate) mod optimizer;
+pub(crate) mod params;
+
+pub mod adagrad;
+pub mod adam;
+pub mod regularize;
+pub mod rmsprop;
+pub mod scheduler;
+pub mod sgd;
 
 pub(crate) mod prelude {
+    pub use super::adagrad::*;
+    pub use super::adam::*;
     pub use super::optimizer::*;
-    pub use super::Optimize;
+    pub use super::params::*;
+    pub use super::regularize::*;
+    pub use super::rmsprop::*;
+    pub use super::scheduler::prelude::*;
+    pub use super::sgd::*;
+    pub use super::{Hyperparams, Optimize};
 }
 
-pub trait Optimize {}
+use nd::{ArrayViewD, ArrayViewMutD};
+
+/// [Hyperparams] provides uniform access to the [OptimizerConfig] of an optimizer, allowing
+/// schedulers and training loops to adjust it between steps.
+pub trait Hyperparams {
+    fn config(&self) -> &OptimizerConfig;
+
+    fn config_mut(&mut self) -> &mut OptimizerConfig;
+
+    fn learning_rate(&self) -> f64 {
+        self.config().learning_rate
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f64) {
+        self.config_mut().learning_rate = learning_rate;
+    }
+    /// Validates the hyperparameters of the optimizer; see [OptimizerConfig::validate].
+    fn validate(&self) -> Result<(), OptimizerError> {
+        self.config().validate()
+    }
+}
+
+/// [Optimize] describes an algorithm for updating learnable parameters given their gradients.
+pub trait Optimize<A>: Hyperparams {
+    /// Updates a single tensor in-place; `slot` is the position of the tensor within the
+    /// parameter store and is used to look up any state associated with it.
+    fn update(&mut self, slot: usize, param: ArrayViewMutD<'_, A>, grad: ArrayViewD<'_, A>);
+    /// Performs a single optimization step, updating every tensor within the store; the
+    /// parameters are left untouched should the hyperparameters be
+    /// [invalid](Hyperparams::validate).
+    fn step<P, G>(&mut self, params: &mut P, grads: &G) -> Result<(), OptimizerError>
+    where
+        G: ?Sized,
+        P: Optimizable<A, G> + ?Sized,
+    {
+        self.validate()?;
+        let mut slot = 0;
+        params.zip_grad(grads, &mut |param, grad| {
+            self.update(slot, param, grad);
+            slot += 1;
+        });
+        Ok(())
+    }
+}
@@ -2,13 +2,184 @@
     Appellation: optimizer <module>
     Contrib: FL03 <jo3mccain@icloud.com>
 */
+use crate::rust::Vec;
+use nd::{ArrayD, IxDyn};
+use num::traits::{Float, Zero};
+use smart_default::SmartDefault;
 
+err! {
+    OptimizerError {
+        /// Nesterov momentum was enabled without momentum or with a non-zero dampening.
+        InvalidNesterov,
+    }
+}
+
+impl_err!(OptimizerError);
+
+/// [OptimizerConfig] contains the hyperparameters shared by the optimizers of this module;
+/// each optimizer only reads the fields relevant to it.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, SmartDefault)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(default, rename_all = "snake_case")
+)]
 pub struct OptimizerConfig {
-    pub learning_rate: f32,
-    pub momentum: f32,
-    pub weight_decay: f32,
+    /// The smoothing constant of the running average of the squared gradient (RMSprop)
+    #[default(0.99)]
+    pub alpha: f64,
+    /// The coefficients used to compute the running averages of the gradient and its square
+    #[default((0.9, 0.999))]
+    pub betas: (f64, f64),
+    /// Toggles the bias correction of the moment estimates
+    #[default(true)]
+    pub bias_correction: bool,
+    /// Normalizes the gradient by an estimate of its variance rather than its second moment (RMSprop)
+    pub centered: bool,
+    /// The dampening applied to the momentum buffer
+    pub dampening: f64,
+    /// A small constant added to the denominator for numerical stability
+    #[default(1e-8)]
+    pub eps: f64,
+    /// The starting value of the accumulated squared gradient (Adagrad)
+    pub initial_accumulator_value: f64,
+    #[default(1e-3)]
+    pub learning_rate: f64,
+    /// The decay applied to the learning rate after each step (Adagrad)
+    pub lr_decay: f64,
+    pub momentum: f64,
+    /// Enables Nesterov momentum; requires a positive momentum and zero dampening
+    pub nesterov: bool,
+    /// The coefficient of the running averages of the squared gradient and update (Adadelta)
+    #[default(0.9)]
+    pub rho: f64,
+    /// The weight decay; decoupled optimizers (SGD, AdamW) apply it directly to the
+    /// parameters while the others add it to the gradient as an L2 penalty.
+    pub weight_decay: f64,
+}
+
+impl OptimizerConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Rejects invalid combinations of hyperparameters, i.e. Nesterov momentum without a
+    /// positive momentum or with a non-zero dampening.
+    pub fn validate(&self) -> Result<(), OptimizerError> {
+        if self.nesterov && (self.momentum <= 0.0 || self.dampening != 0.0) {
+            return Err(OptimizerError::InvalidNesterov);
+        }
+        Ok(())
+    }
+
+    pub fn with_alpha(self, alpha: f64) -> Self {
+        Self { alpha, ..self }
+    }
+
+    pub fn with_betas(self, beta1: f64, beta2: f64) -> Self {
+        Self {
+            betas: (beta1, beta2),
+            ..self
+        }
+    }
+
+    pub fn with_bias_correction(self, bias_correction: bool) -> Self {
+        Self {
+            bias_correction,
+            ..self
+        }
+    }
+
+    pub fn with_centered(self, centered: bool) -> Self {
+        Self { centered, ..self }
+    }
+
+    pub fn with_dampening(self, dampening: f64) -> Self {
+        Self { dampening, ..self }
+    }
+
+    pub fn with_eps(self, eps: f64) -> Self {
+        Self { eps, ..self }
+    }
+
+    pub fn with_initial_accumulator_value(self, initial_accumulator_value: f64) -> Self {
+        Self {
+            initial_accumulator_value,
+            ..self
+        }
+    }
+
+    pub fn with_learning_rate(self, learning_rate: f64) -> Self {
+        Self {
+            learning_rate,
+            ..self
+        }
+    }
+
+    pub fn with_lr_decay(self, lr_decay: f64) -> Self {
+        Self { lr_decay, ..self }
+    }
+
+    pub fn with_momentum(self, momentum: f64) -> Self {
+        Self { momentum, ..self }
+    }
+
+    pub fn with_nesterov(self, nesterov: bool) -> Self {
+        Self { nesterov, ..self }
+    }
+
+    pub fn with_rho(self, rho: f64) -> Self {
+        Self { rho, ..self }
+    }
+
+    pub fn with_weight_decay(self, weight_decay: f64) -> Self {
+        Self {
+            weight_decay,
+            ..self
+        }
+    }
+}
+
+/// Casts a hyperparameter into the element type of the parameters.
+pub(crate) fn hyper<A>(value: f64) -> A
+where
+    A: Float,
+{
+    A::from(value).unwrap()
+}
+
+/// Increments and returns the number of steps taken for the given slot.
+pub(crate) fn tick(steps: &mut Vec<usize>, slot: usize) -> i32 {
+    if steps.len() <= slot {
+        steps.resize(slot + 1, 0);
+    }
+    steps[slot] += 1;
+    steps[slot] as i32
+}
+
+/// Returns the buffer stored at the given slot, initializing it with zeros if necessary.
+pub(crate) fn buffer<A>(
+    store: &mut Vec<Option<ArrayD<A>>>,
+    slot: usize,
+    dim: IxDyn,
+) -> &mut ArrayD<A>
+where
+    A: Clone + Zero,
+{
+    buffer_with(store, slot, dim, A::zero())
 }
 
-pub struct OptimizerBase<C> {
-    pub config: C,
+/// Returns the buffer stored at the given slot, filling it with `init` if necessary.
+pub(crate) fn buffer_with<A>(
+    store: &mut Vec<Option<ArrayD<A>>>,
+    slot: usize,
+    dim: IxDyn,
+    init: A,
+) -> &mut ArrayD<A>
+where
+    A: Clone,
+{
+    if store.len() <= slot {
+        store.resize(slot + 1, None);
+    }
+    store[slot].get_or_insert_with(|| ArrayD::from_elem(dim, init))
 }
@@ -0,0 +1,62 @@
+/*
+    Appellation: params <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use nd::{ArrayBase, ArrayViewD, ArrayViewMutD, Data, DataMut, Dimension};
+
+/// [Optimizable] describes a store of learnable parameters capable of being updated with
+/// a matching store of gradients, `G`.
+///
+/// Implementors visit each of their tensors alongside its gradient in a fixed order; optimizers
+/// rely upon this order to associate their internal state with a particular tensor.
+pub trait Optimizable<A, G: ?Sized = Self> {
+    fn zip_grad(&mut self, grads: &G, f: &mut dyn FnMut(ArrayViewMutD<'_, A>, ArrayViewD<'_, A>));
+}
+
+/*
+ ************* Implementations *************
+*/
+impl<A, S, T, D> Optimizable<A, ArrayBase<T, D>> for ArrayBase<S, D>
+where
+    D: Dimension,
+    S: DataMut<Elem = A>,
+    T: Data<Elem = A>,
+{
+    fn zip_grad(
+        &mut self,
+        grads: &ArrayBase<T, D>,
+        f: &mut dyn FnMut(ArrayViewMutD<'_, A>, ArrayViewD<'_, A>),
+    ) {
+        f(self.view_mut().into_dyn(), grads.view().into_dyn())
+    }
+}
+
+impl<A, P, G> Optimizable<A, Option<G>> for Option<P>
+where
+    P: Optimizable<A, G>,
+{
+    fn zip_grad(
+        &mut self,
+        grads: &Option<G>,
+        f: &mut dyn FnMut(ArrayViewMutD<'_, A>, ArrayViewD<'_, A>),
+    ) {
+        if let (Some(params), Some(grads)) = (self.as_mut(), grads.as_ref()) {
+            params.zip_grad(grads, f)
+        }
+    }
+}
+
+impl<A, P, G> Optimizable<A, [G]> for [P]
+where
+    P: Optimizable<A, G>,
+{
+    fn zip_grad(
+        &mut self,
+        grads: &[G],
+        f: &mut dyn FnMut(ArrayViewMutD<'_, A>, ArrayViewD<'_, A>),
+    ) {
+        for (params, grads) in self.iter_mut().zip(grads) {
+            params.zip_grad(grads, f)
+        }
+    }
+}
@@ -0,0 +1,189 @@
+/*
+    Appellation: regularize <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::{Hyperparams, Optimize, OptimizerConfig};
+use crate::func::loss::reg::penalty::Penalty;
+use nd::{ArrayViewD, ArrayViewMutD, Axis, ScalarOperand};
+use num::traits::Float;
+
+/// [Constraint] projects a tensor of parameters back onto a feasible set; constraints are
+/// applied by [Constrained] after each update of the wrapped optimizer.
+pub trait Constraint<A> {
+    fn constrain(&self, params: ArrayViewMutD<'_, A>);
+}
+
+/// [MaxNorm] rescales the weights associated with each index of the first axis, e.g. the
+/// incoming weights of each unit of a linear layer, whose norm exceeds `max_norm`.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct MaxNorm<A = f64> {
+    pub max_norm: A,
+}
+
+/// [Regularized] adds the gradient of a [Penalty] to that of each tensor before it is
+/// updated by the wrapped optimizer.
+///
+/// Tensors of fewer than two dimensions, such as biases or the scales of normalization
+/// layers, are exempt unless `include_bias` is set.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct Regularized<O, P> {
+    pub include_bias: bool,
+    pub(crate) optimizer: O,
+    pub penalty: P,
+}
+
+/// [Constrained] applies a [Constraint] to each tensor after it has been updated by the
+/// wrapped optimizer; as with [Regularized], tensors of fewer than two dimensions are exempt
+/// unless `include_bias` is set.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct Constrained<O, C> {
+    pub constraint: C,
+    pub include_bias: bool,
+    pub(crate) optimizer: O,
+}
+
+/*
+ ************* Implementations *************
+*/
+impl<A> MaxNorm<A> {
+    pub fn new(max_norm: A) -> Self {
+        Self { max_norm }
+    }
+}
+
+impl<A> Constraint<A> for MaxNorm<A>
+where
+    A: Float + ScalarOperand,
+{
+    fn constrain(&self, mut params: ArrayViewMutD<'_, A>) {
+        if params.ndim() == 0 {
+            return;
+        }
+        for mut row in params.axis_iter_mut(Axis(0)) {
+            let norm = row.fold(A::zero(), |acc, &w| acc + w * w).sqrt();
+            if norm > self.max_norm {
+                let scale = self.max_norm / norm;
+                row.mapv_inplace(|w| w * scale);
+            }
+        }
+    }
+}
+
+impl<O, P> Regularized<O, P> {
+    pub fn new(optimizer: O, penalty: P) -> Self {
+        Self {
+            include_bias: false,
+            optimizer,
+            penalty,
+        }
+    }
+
+    pub fn with_include_bias(self, include_bias: bool) -> Self {
+        Self {
+            include_bias,
+            ..self
+        }
+    }
+
+    pub const fn optimizer(&self) -> &O {
+        &self.optimizer
+    }
+
+    pub fn optimizer_mut(&mut self) -> &mut O {
+        &mut self.optimizer
+    }
+}
+
+impl<O, P> Hyperparams for Regularized<O, P>
+where
+    O: Hyperparams,
+{
+    fn config(&self) -> &OptimizerConfig {
+        self.optimizer.config()
+    }
+
+    fn config_mut(&mut self) -> &mut OptimizerConfig {
+        self.optimizer.config_mut()
+    }
+}
+
+impl<A, O, P> Optimize<A> for Regularized<O, P>
+where
+    A: Float + ScalarOperand,
+    O: Optimize<A>,
+    P: Penalty<A>,
+{
+    fn update(&mut self, slot: usize, param: ArrayViewMutD<'_, A>, grad: ArrayViewD<'_, A>) {
+        if param.ndim() < 2 && !self.include_bias {
+            return self.optimizer.update(slot, param, grad);
+        }
+        let grad = &grad + &self.penalty.penalty_grad(&param);
+        self.optimizer.update(slot, param, grad.view())
+    }
+}
+
+impl<O, C> Constrained<O, C> {
+    pub fn new(optimizer: O, constraint: C) -> Self {
+        Self {
+            constraint,
+            include_bias: false,
+            optimizer,
+        }
+    }
+
+    pub fn with_include_bias(self, include_bias: bool) -> Self {
+        Self {
+            include_bias,
+            ..self
+        }
+    }
+
+    pub const fn optimizer(&self) -> &O {
+        &self.optimizer
+    }
+
+    pub fn optimizer_mut(&mut self) -> &mut O {
+        &mut self.optimizer
+    }
+}
+
+impl<O, C> Hyperparams for Constrained<O, C>
+where
+    O: Hyperparams,
+{
+    fn config(&self) -> &OptimizerConfig {
+        self.optimizer.config()
+    }
+
+    fn config_mut(&mut self) -> &mut OptimizerConfig {
+        self.optimizer.config_mut()
+    }
+}
+
+impl<A, O, C> Optimize<A> for Constrained<O, C>
+where
+    O: Optimize<A>,
+    C: Constraint<A>,
+{
+    fn update(&mut self, slot: usize, mut param: ArrayViewMutD<'_, A>, grad: ArrayViewD<'_, A>) {
+        self.optimizer.update(slot, param.view_mut(), grad);
+        if param.ndim() >= 2 || self.include_bias {
+            self.constraint.constrain(param)
+        }
+    }
+}
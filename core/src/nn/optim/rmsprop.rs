@@ -0,0 +1,76 @@
+/*
+    Appellation: rmsprop <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::optimizer::{buffer, hyper};
+use super::{Optimize, OptimizerConfig};
+use crate::rust::Vec;
+use nd::{ArrayD, ArrayViewD, ArrayViewMutD, ScalarOperand, Zip};
+use num::traits::Float;
+
+/// [RmsProp] divides the gradient by a running average of its recent magnitude.
+///
+/// When `centered`, the gradient is instead normalized by an estimate of its variance;
+/// a positive `momentum` accumulates the normalized updates into a velocity buffer.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct RmsProp<A = f64> {
+    pub(crate) config: OptimizerConfig,
+    pub(crate) grad_avg: Vec<Option<ArrayD<A>>>,
+    pub(crate) momentum: Vec<Option<ArrayD<A>>>,
+    pub(crate) square_avg: Vec<Option<ArrayD<A>>>,
+}
+
+optimizer!(RmsProp {
+    grad_avg,
+    momentum,
+    square_avg
+});
+
+impl<A> Optimize<A> for RmsProp<A>
+where
+    A: Float + ScalarOperand,
+{
+    fn update(&mut self, slot: usize, mut param: ArrayViewMutD<'_, A>, grad: ArrayViewD<'_, A>) {
+        let alpha = hyper::<A>(self.config.alpha);
+        let (lr, eps) = (
+            hyper::<A>(self.config.learning_rate),
+            hyper::<A>(self.config.eps),
+        );
+        let (mu, wd) = (
+            hyper::<A>(self.config.momentum),
+            hyper::<A>(self.config.weight_decay),
+        );
+        let grad = Zip::from(&param)
+            .and(&grad)
+            .map_collect(|&p, &g| g + wd * p);
+
+        let square_avg = buffer(&mut self.square_avg, slot, grad.raw_dim());
+        square_avg.zip_mut_with(&grad, |v, &g| *v = alpha * *v + (A::one() - alpha) * g * g);
+        let mut denom = square_avg.clone();
+        if self.config.centered {
+            let grad_avg = buffer(&mut self.grad_avg, slot, grad.raw_dim());
+            grad_avg.zip_mut_with(&grad, |m, &g| *m = alpha * *m + (A::one() - alpha) * g);
+            denom.zip_mut_with(grad_avg, |v, &m| *v = *v - m * m);
+        }
+        denom.mapv_inplace(|v| v.sqrt() + eps);
+
+        if self.config.momentum > 0.0 {
+            let buf = buffer(&mut self.momentum, slot, grad.raw_dim());
+            Zip::from(&mut *buf)
+                .and(&grad)
+                .and(&denom)
+                .for_each(|b, &g, &d| *b = mu * *b + g / d);
+            param.zip_mut_with(&*buf, |p, &b| *p = *p - lr * b);
+        } else {
+            Zip::from(&mut param)
+                .and(&grad)
+                .and(&denom)
+                .for_each(|p, &g, &d| *p = *p - lr * g / d);
+        }
+    }
+}
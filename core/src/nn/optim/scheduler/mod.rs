@@ -0,0 +1,111 @@
+/*
+    Appellation: scheduler <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+//! # Learning Rate Schedulers
+//!
+//! A [Schedule] is a closed-form rule describing the learning rate at any given step relative
+//! to some base rate; schedules compose via [Schedule::then]. A [Scheduler] drives the
+//! learning rate of an optimizer, either by following a schedule ([LrScheduler]) or by
+//! reacting to a monitored metric ([ReduceOnPlateau]).
+pub use self::{plateau::*, schedules::*};
+
+pub(crate) mod plateau;
+pub(crate) mod schedules;
+
+pub(crate) mod prelude {
+    pub use super::plateau::*;
+    pub use super::schedules::*;
+    pub use super::{LrScheduler, Schedule, Scheduler};
+}
+
+use super::Hyperparams;
+
+/// [Schedule] describes the learning rate as a function of the number of steps taken.
+pub trait Schedule {
+    /// Returns the learning rate after `step` steps given the initial learning rate `base`.
+    fn lr(&self, base: f64, step: usize) -> f64;
+    /// Follows this schedule for the first `milestone` steps before switching to `next`;
+    /// the steps observed by `next` are counted from the milestone.
+    fn then<S>(self, milestone: usize, next: S) -> Chain<Self, S>
+    where
+        S: Schedule,
+        Self: Sized,
+    {
+        Chain::new(self, milestone, next)
+    }
+}
+
+/// [Scheduler] describes the common interface used to adjust the learning rate of an optimizer
+/// over the course of training; the `metric` is only consulted by reactive schedulers.
+pub trait Scheduler {
+    /// Advances the scheduler by one step, returning the learning rate assigned to the optimizer.
+    fn step(&mut self, optimizer: &mut dyn Hyperparams, metric: Option<f64>) -> f64;
+}
+
+/// [LrScheduler] drives an optimizer along a [Schedule].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct LrScheduler<S> {
+    pub(crate) base: f64,
+    pub(crate) schedule: S,
+    pub(crate) step: usize,
+}
+
+impl<S> LrScheduler<S>
+where
+    S: Schedule,
+{
+    pub fn new(base: f64, schedule: S) -> Self {
+        Self {
+            base,
+            schedule,
+            step: 0,
+        }
+    }
+    /// Creates a new scheduler using the current learning rate of the optimizer as the base.
+    pub fn from_optimizer(optimizer: &dyn Hyperparams, schedule: S) -> Self {
+        Self::new(optimizer.learning_rate(), schedule)
+    }
+
+    pub const fn base(&self) -> f64 {
+        self.base
+    }
+    /// Returns the learning rate prescribed for the current step.
+    pub fn lr(&self) -> f64 {
+        self.schedule.lr(self.base, self.step)
+    }
+
+    pub const fn schedule(&self) -> &S {
+        &self.schedule
+    }
+    /// Returns the number of steps taken.
+    pub const fn steps(&self) -> usize {
+        self.step
+    }
+}
+
+impl<S> Scheduler for LrScheduler<S>
+where
+    S: Schedule,
+{
+    fn step(&mut self, optimizer: &mut dyn Hyperparams, _metric: Option<f64>) -> f64 {
+        self.step += 1;
+        let lr = self.lr();
+        optimizer.set_learning_rate(lr);
+        lr
+    }
+}
+
+impl<F> Schedule for F
+where
+    F: Fn(f64, usize) -> f64,
+{
+    fn lr(&self, base: f64, step: usize) -> f64 {
+        self(base, step)
+    }
+}
@@ -0,0 +1,149 @@
+/*
+    Appellation: plateau <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::Scheduler;
+use crate::nn::optim::Hyperparams;
+use smart_default::SmartDefault;
+use strum::{AsRefStr, Display, EnumCount, EnumIs, EnumIter, EnumString, VariantNames};
+
+/// Determines whether the monitored metric is expected to decrease or increase.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumCount,
+    EnumIs,
+    EnumIter,
+    EnumString,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    VariantNames,
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum Monitor {
+    Max,
+    #[default]
+    Min,
+}
+
+impl Monitor {
+    /// Returns true if `value` improves upon `best` by more than the relative `threshold`.
+    pub fn is_better(&self, value: f64, best: f64, threshold: f64) -> bool {
+        match self {
+            Monitor::Max => value > best * (1.0 + threshold),
+            Monitor::Min => value < best * (1.0 - threshold),
+        }
+    }
+
+    /// Returns the least favourable value of the metric, used to seed the best value observed.
+    pub fn worst(&self) -> f64 {
+        match self {
+            Monitor::Max => f64::NEG_INFINITY,
+            Monitor::Min => f64::INFINITY,
+        }
+    }
+}
+
+/// [ReduceOnPlateau] multiplies the learning rate by `factor` once the monitored metric has
+/// failed to improve for more than `patience` consecutive steps.
+///
+/// After a reduction, the scheduler waits `cooldown` steps before resuming its count of
+/// unproductive steps; the learning rate is never reduced below `min_lr`.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, SmartDefault)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct ReduceOnPlateau {
+    #[default(f64::INFINITY)]
+    pub(crate) best: f64,
+    pub cooldown: usize,
+    pub(crate) cooldown_counter: usize,
+    #[default(0.1)]
+    pub factor: f64,
+    pub min_lr: f64,
+    pub mode: Monitor,
+    pub(crate) num_bad: usize,
+    #[default(10)]
+    pub patience: usize,
+    #[default(1e-4)]
+    pub threshold: f64,
+}
+
+impl ReduceOnPlateau {
+    pub fn new(mode: Monitor, factor: f64, patience: usize) -> Self {
+        Self {
+            best: mode.worst(),
+            factor,
+            mode,
+            patience,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_cooldown(self, cooldown: usize) -> Self {
+        Self { cooldown, ..self }
+    }
+
+    pub fn with_min_lr(self, min_lr: f64) -> Self {
+        Self { min_lr, ..self }
+    }
+
+    pub fn with_threshold(self, threshold: f64) -> Self {
+        Self { threshold, ..self }
+    }
+    /// Returns the best value of the metric observed so far.
+    pub const fn best(&self) -> f64 {
+        self.best
+    }
+    /// Returns the number of consecutive steps without improvement.
+    pub const fn num_bad(&self) -> usize {
+        self.num_bad
+    }
+    /// Records the metric, returning true if the learning rate should be reduced.
+    pub fn observe(&mut self, metric: f64) -> bool {
+        if self.mode.is_better(metric, self.best, self.threshold) {
+            self.best = metric;
+            self.num_bad = 0;
+        } else {
+            self.num_bad += 1;
+        }
+        if self.cooldown_counter > 0 {
+            self.cooldown_counter -= 1;
+            self.num_bad = 0;
+        }
+        if self.num_bad > self.patience {
+            self.cooldown_counter = self.cooldown;
+            self.num_bad = 0;
+            return true;
+        }
+        false
+    }
+}
+
+impl Scheduler for ReduceOnPlateau {
+    fn step(&mut self, optimizer: &mut dyn Hyperparams, metric: Option<f64>) -> f64 {
+        let lr = optimizer.learning_rate();
+        match metric {
+            Some(metric) if self.observe(metric) => {
+                let reduced = (lr * self.factor).max(self.min_lr);
+                optimizer.set_learning_rate(reduced);
+                reduced
+            }
+            _ => lr,
+        }
+    }
+}
@@ -0,0 +1,317 @@
+/*
+    Appellation: schedules <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::Schedule;
+use crate::rust::Vec;
+use core::f64::consts::PI;
+#[cfg(not(feature = "std"))]
+use num::traits::Float;
+
+/// Interpolates from `start` to `end` along half a cosine wave, where `pct` is the fraction of
+/// the interval already travelled.
+fn cosine(start: f64, end: f64, pct: f64) -> f64 {
+    end + (start - end) * (1.0 + (PI * pct).cos()) / 2.0
+}
+
+/// [Chain] follows the first schedule until the milestone, then the second.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct Chain<A, B> {
+    pub first: A,
+    pub milestone: usize,
+    pub next: B,
+}
+
+impl<A, B> Chain<A, B> {
+    pub fn new(first: A, milestone: usize, next: B) -> Self {
+        Self {
+            first,
+            milestone,
+            next,
+        }
+    }
+}
+
+impl<A, B> Schedule for Chain<A, B>
+where
+    A: Schedule,
+    B: Schedule,
+{
+    fn lr(&self, base: f64, step: usize) -> f64 {
+        if step < self.milestone {
+            self.first.lr(base, step)
+        } else {
+            self.next.lr(base, step - self.milestone)
+        }
+    }
+}
+
+/// [StepDecay] multiplies the learning rate by `gamma` every `step_size` steps.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct StepDecay {
+    pub gamma: f64,
+    pub step_size: usize,
+}
+
+impl StepDecay {
+    pub fn new(step_size: usize, gamma: f64) -> Self {
+        Self { gamma, step_size }
+    }
+}
+
+impl Schedule for StepDecay {
+    fn lr(&self, base: f64, step: usize) -> f64 {
+        base * self.gamma.powi((step / self.step_size.max(1)) as i32)
+    }
+}
+
+/// [MultiStep] multiplies the learning rate by `gamma` upon reaching each milestone.
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct MultiStep {
+    pub gamma: f64,
+    pub milestones: Vec<usize>,
+}
+
+impl MultiStep {
+    pub fn new(milestones: impl IntoIterator<Item = usize>, gamma: f64) -> Self {
+        Self {
+            gamma,
+            milestones: milestones.into_iter().collect(),
+        }
+    }
+}
+
+impl Schedule for MultiStep {
+    fn lr(&self, base: f64, step: usize) -> f64 {
+        let passed = self.milestones.iter().filter(|&&m| m <= step).count();
+        base * self.gamma.powi(passed as i32)
+    }
+}
+
+/// [Exponential] multiplies the learning rate by `gamma` every step.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct Exponential {
+    pub gamma: f64,
+}
+
+impl Exponential {
+    pub fn new(gamma: f64) -> Self {
+        Self { gamma }
+    }
+}
+
+impl Schedule for Exponential {
+    fn lr(&self, base: f64, step: usize) -> f64 {
+        base * self.gamma.powi(step as i32)
+    }
+}
+
+/// [CosineAnnealing] anneals the learning rate from its base down to `eta_min` along a cosine
+/// curve spanning `t_max` steps, as described in
+/// [SGDR: Stochastic Gradient Descent with Warm Restarts](https://arxiv.org/abs/1608.03983).
+///
+/// Without restarts, the learning rate remains at `eta_min` after `t_max` steps; with restarts,
+/// the schedule starts over, with each period `t_mult` times longer than the last.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct CosineAnnealing {
+    pub eta_min: f64,
+    pub restarts: bool,
+    pub t_max: usize,
+    pub t_mult: usize,
+}
+
+impl CosineAnnealing {
+    pub fn new(t_max: usize, eta_min: f64) -> Self {
+        Self {
+            eta_min,
+            restarts: false,
+            t_max,
+            t_mult: 1,
+        }
+    }
+    /// Enables warm restarts, multiplying the length of each period by `t_mult`.
+    pub fn with_restarts(self, t_mult: usize) -> Self {
+        Self {
+            restarts: true,
+            t_mult: t_mult.max(1),
+            ..self
+        }
+    }
+}
+
+impl Schedule for CosineAnnealing {
+    fn lr(&self, base: f64, step: usize) -> f64 {
+        let t_max = self.t_max.max(1);
+        let (t_cur, t_i) = if !self.restarts {
+            (step.min(t_max), t_max)
+        } else {
+            let (mut t_cur, mut t_i) = (step, t_max);
+            while t_cur >= t_i {
+                t_cur -= t_i;
+                t_i *= self.t_mult;
+            }
+            (t_cur, t_i)
+        };
+        cosine(base, self.eta_min, t_cur as f64 / t_i as f64)
+    }
+}
+
+/// [LinearWarmup] scales the learning rate linearly from `start_factor * base` up to the base
+/// over the first `steps` steps.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct LinearWarmup {
+    pub start_factor: f64,
+    pub steps: usize,
+}
+
+impl LinearWarmup {
+    pub fn new(steps: usize) -> Self {
+        Self {
+            start_factor: 0.0,
+            steps,
+        }
+    }
+
+    pub fn with_start_factor(self, start_factor: f64) -> Self {
+        Self {
+            start_factor,
+            ..self
+        }
+    }
+}
+
+impl Schedule for LinearWarmup {
+    fn lr(&self, base: f64, step: usize) -> f64 {
+        if step >= self.steps {
+            return base;
+        }
+        let pct = step as f64 / self.steps as f64;
+        base * (self.start_factor + (1.0 - self.start_factor) * pct)
+    }
+}
+
+/// [Polynomial] decays the learning rate from its base down to `end_lr` over `total_steps`
+/// steps, following a polynomial of the given `power`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct Polynomial {
+    pub end_lr: f64,
+    pub power: f64,
+    pub total_steps: usize,
+}
+
+impl Polynomial {
+    pub fn new(total_steps: usize, power: f64) -> Self {
+        Self {
+            end_lr: 0.0,
+            power,
+            total_steps,
+        }
+    }
+
+    pub fn with_end_lr(self, end_lr: f64) -> Self {
+        Self { end_lr, ..self }
+    }
+}
+
+impl Schedule for Polynomial {
+    fn lr(&self, base: f64, step: usize) -> f64 {
+        let total = self.total_steps.max(1);
+        let remaining = 1.0 - step.min(total) as f64 / total as f64;
+        (base - self.end_lr) * remaining.powf(self.power) + self.end_lr
+    }
+}
+
+/// [OneCycle] implements the one-cycle policy described in
+/// [Super-Convergence](https://arxiv.org/abs/1708.07120): the learning rate rises from
+/// `max_lr / div_factor` to `max_lr` over the first `pct_start` of the cycle before annealing
+/// down to `max_lr / (div_factor * final_div_factor)`. The base learning rate is ignored.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct OneCycle {
+    pub div_factor: f64,
+    pub final_div_factor: f64,
+    pub max_lr: f64,
+    pub pct_start: f64,
+    pub total_steps: usize,
+}
+
+impl OneCycle {
+    pub fn new(max_lr: f64, total_steps: usize) -> Self {
+        Self {
+            div_factor: 25.0,
+            final_div_factor: 1e4,
+            max_lr,
+            pct_start: 0.3,
+            total_steps,
+        }
+    }
+
+    pub fn with_div_factor(self, div_factor: f64) -> Self {
+        Self { div_factor, ..self }
+    }
+
+    pub fn with_final_div_factor(self, final_div_factor: f64) -> Self {
+        Self {
+            final_div_factor,
+            ..self
+        }
+    }
+
+    pub fn with_pct_start(self, pct_start: f64) -> Self {
+        Self { pct_start, ..self }
+    }
+}
+
+impl Schedule for OneCycle {
+    fn lr(&self, _base: f64, step: usize) -> f64 {
+        let initial = self.max_lr / self.div_factor;
+        let min_lr = initial / self.final_div_factor;
+        let total = self.total_steps.max(1) as f64;
+        let peak = (self.pct_start * total).max(1.0);
+        let step = (step as f64).min(total);
+        if step <= peak {
+            cosine(initial, self.max_lr, step / peak)
+        } else {
+            cosine(self.max_lr, min_lr, (step - peak) / (total - peak).max(1.0))
+        }
+    }
+}
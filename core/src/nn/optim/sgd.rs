@@ -0,0 +1,114 @@
+/*
+    Appellation: sgd <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::optimizer::{buffer, hyper};
+use super::{Optimize, OptimizerConfig, OptimizerError};
+use crate::rust::Vec;
+use nd::{ArrayD, ArrayViewD, ArrayViewMutD, ScalarOperand};
+use num::traits::Float;
+
+/// Stochastic gradient descent, optionally with (Nesterov) momentum and decoupled weight decay.
+///
+/// For each parameter `p` with gradient `g`, the update proceeds as follows:
+///
+/// ```text
+/// p = p - lr * weight_decay * p
+/// b = momentum * b + (1 - dampening) * g
+/// g = g + momentum * b  (nesterov) | b  (otherwise)
+/// p = p - lr * g
+/// ```
+///
+/// The momentum buffer `b` is initialized with the first gradient observed. As Nesterov
+/// momentum requires a zero dampening, configurations combining the two are rejected by
+/// [try_from_config](Sgd::try_from_config) and by every [step](Optimize::step).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct Sgd<A = f64> {
+    pub(crate) config: OptimizerConfig,
+    pub(crate) momentum: Vec<Option<ArrayD<A>>>,
+}
+
+optimizer!(Sgd { momentum });
+
+impl<A> Sgd<A> {
+    /// Creates a new instance from the given config, rejecting Nesterov momentum with a
+    /// non-zero dampening.
+    pub fn try_from_config(config: OptimizerConfig) -> Result<Self, OptimizerError> {
+        config.validate()?;
+        Ok(Self::from_config(config))
+    }
+
+    pub fn with_momentum(self, momentum: f64) -> Self {
+        Self {
+            config: self.config.with_momentum(momentum),
+            ..self
+        }
+    }
+
+    pub fn with_nesterov(self, nesterov: bool) -> Self {
+        Self {
+            config: self.config.with_nesterov(nesterov),
+            ..self
+        }
+    }
+
+    pub fn with_dampening(self, dampening: f64) -> Self {
+        Self {
+            config: self.config.with_dampening(dampening),
+            ..self
+        }
+    }
+
+    pub fn with_weight_decay(self, weight_decay: f64) -> Self {
+        Self {
+            config: self.config.with_weight_decay(weight_decay),
+            ..self
+        }
+    }
+}
+
+impl<A> Optimize<A> for Sgd<A>
+where
+    A: Float + ScalarOperand,
+{
+    fn update(&mut self, slot: usize, mut param: ArrayViewMutD<'_, A>, grad: ArrayViewD<'_, A>) {
+        let OptimizerConfig {
+            dampening,
+            learning_rate,
+            momentum,
+            nesterov,
+            weight_decay,
+            ..
+        } = self.config;
+        let lr = hyper::<A>(learning_rate);
+        if weight_decay != 0.0 {
+            let decay = A::one() - lr * hyper(weight_decay);
+            param.mapv_inplace(|p| p * decay);
+        }
+        if momentum == 0.0 {
+            param.zip_mut_with(&grad, |p, &g| *p = *p - lr * g);
+            return;
+        }
+        let (mu, damp) = (hyper::<A>(momentum), A::one() - hyper(dampening));
+        let fresh = self.momentum.get(slot).and_then(Option::as_ref).is_none();
+        let buf = buffer(&mut self.momentum, slot, grad.raw_dim());
+        if fresh {
+            buf.assign(&grad);
+        } else {
+            buf.zip_mut_with(&grad, |b, &g| *b = mu * *b + damp * g);
+        }
+        if nesterov {
+            nd::Zip::from(&mut param)
+                .and(&grad)
+                .and(&*buf)
+                .for_each(|p, &g, &b| *p = *p - lr * (g + mu * b));
+        } else {
+            param.zip_mut_with(&*buf, |p, &b| *p = *p - lr * b);
+        }
+    }
+}
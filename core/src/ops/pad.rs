@@ -10,28 +10,39 @@ pub(crate) mod error;
 pub(crate) mod mode;
 pub(crate) mod utils;
 
-use nd::{Array, ArrayBase, DataOwned, Dimension};
+use nd::{Array, ArrayBase, Data, Dimension};
 use num::traits::{FromPrimitive, Num};
 
+/// [Pad] extends the edges of an array according to a [PadMode], given the `[before, after]`
+/// widths of each axis; see [pad](utils::pad) for the details.
 pub trait Pad<T> {
     type Output;
 
-    fn pad(&self, mode: PadMode<T>, pad: &[[usize; 2]]) -> Self::Output;
+    fn pad(&self, mode: PadMode<T>, pad: &[[usize; 2]]) -> PadResult<Self::Output>;
+    /// Removes the given widths from each axis, inverting [pad](Pad::pad).
+    fn unpad(&self, pad: &[[usize; 2]]) -> PadResult<Self::Output>;
 }
 
 impl<A, S, D> Pad<A> for ArrayBase<S, D>
 where
-    A: Copy + FromPrimitive + Num,
+    A: Copy + FromPrimitive + Num + PartialOrd,
     D: Dimension,
-    S: DataOwned<Elem = A>,
+    S: Data<Elem = A>,
 {
     type Output = Array<A, D>;
 
-    fn pad(&self, mode: PadMode<A>, pad: &[[usize; 2]]) -> Self::Output {
-        utils::pad(self, pad, mode).unwrap()
+    fn pad(&self, mode: PadMode<A>, pad: &[[usize; 2]]) -> PadResult<Self::Output> {
+        utils::pad(self, pad, mode)
+    }
+
+    fn unpad(&self, pad: &[[usize; 2]]) -> PadResult<Self::Output> {
+        utils::unpad(self, pad)
     }
 }
 
+/// [Padding] stores the configuration of a padding operation, enabling layers to pad, and
+/// later crop, their inputs in a consistent manner. Unless the widths of each axis are given
+/// using [with_pad](Padding::with_pad), every axis is padded by `padding` on either side.
 pub struct Padding<T> {
     pub(crate) action: PadAction,
     pub(crate) mode: PadMode<T>,
@@ -59,12 +70,57 @@ impl<T> Padding<T> {
     }
 
     pub fn with_mode(mut self, mode: PadMode<T>) -> Self {
+        self.action = mode.action();
         self.mode = mode;
         self
     }
 
+    pub fn with_pad(mut self, pad: Vec<[usize; 2]>) -> Self {
+        self.pad = pad;
+        self
+    }
+
     pub fn with_padding(mut self, padding: usize) -> Self {
         self.padding = padding;
         self
     }
+
+    pub const fn action(&self) -> PadAction {
+        self.action
+    }
+
+    pub const fn mode(&self) -> &PadMode<T> {
+        &self.mode
+    }
+    /// Returns the `[before, after]` widths of each axis, falling back onto a uniform padding.
+    pub fn widths(&self) -> Vec<[usize; 2]> {
+        match self.pad.is_empty() {
+            true => vec![[self.padding; 2]],
+            false => self.pad.clone(),
+        }
+    }
+    /// Pads the given array according to the configuration.
+    pub fn apply<S, D>(&self, data: &ArrayBase<S, D>) -> PadResult<Array<T, D>>
+    where
+        T: Copy + FromPrimitive + Num + PartialOrd,
+        D: Dimension,
+        S: Data<Elem = T>,
+    {
+        utils::pad(data, &self.widths(), self.mode)
+    }
+    /// Crops the padding from an array previously padded using [apply](Padding::apply).
+    pub fn crop<S, D>(&self, data: &ArrayBase<S, D>) -> PadResult<Array<T, D>>
+    where
+        T: Clone,
+        D: Dimension,
+        S: Data<Elem = T>,
+    {
+        utils::unpad(data, &self.widths())
+    }
+}
+
+impl<T> Default for Padding<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
@@ -37,8 +37,12 @@ pub type PadResult<T = ()> = Result<T, PadError>;
 #[strum(serialize_all = "snake_case")]
 #[repr(u8)]
 pub enum PadError {
+    /// Only constant padding may be applied to an axis of length zero.
+    EmptyAxis,
     #[default]
     InconsistentDimensions,
+    /// The widths to be removed exceed the length of an axis.
+    OutOfBounds,
 }
 
 impl_err!(PadError);
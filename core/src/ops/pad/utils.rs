@@ -3,8 +3,8 @@
     Contrib: FL03 <jo3mccain@icloud.com>
 */
 use super::{PadAction, PadError, PadMode};
-use crate::traits::ArrayLike;
-use nd::{Array, ArrayBase, AxisDescription, Data, DataOwned, Dimension, Slice};
+use nd::{s, Array, ArrayBase, ArrayView, ArrayView1, ArrayViewMut1, Axis, Data, Dimension};
+use nd::{AxisDescription, Slice};
 use num::{FromPrimitive, Num};
 
 fn reader(nb_dim: usize, pad: &[[usize; 2]]) -> Result<Vec<[usize; 2]>, PadError> {
@@ -18,27 +18,185 @@ fn reader(nb_dim: usize, pad: &[[usize; 2]]) -> Result<Vec<[usize; 2]>, PadError
     }
 }
 
+/// Maps an offset relative to the start of a lane of length `n` onto an index of the lane
+/// according to the given action; offsets beyond either end are folded back periodically.
+fn position(offset: isize, n: usize, action: PadAction, symmetric: bool) -> usize {
+    let n = n as isize;
+    let idx = match action {
+        PadAction::Reflecting if symmetric => {
+            let m = offset.rem_euclid(2 * n);
+            if m >= n {
+                2 * n - 1 - m
+            } else {
+                m
+            }
+        }
+        PadAction::Reflecting if n > 1 => {
+            let period = 2 * (n - 1);
+            let m = offset.rem_euclid(period);
+            if m >= n {
+                period - m
+            } else {
+                m
+            }
+        }
+        PadAction::Wrapping => offset.rem_euclid(n),
+        _ => offset.clamp(0, n - 1),
+    };
+    idx as usize
+}
+
+/// Computes the value the padding of a lane is filled with for the statistical modes.
+fn statistic<A>(lane: &ArrayView1<A>, mode: PadMode<A>) -> Option<A>
+where
+    A: Copy + FromPrimitive + Num + PartialOrd,
+{
+    if mode.is_edge() || mode.is_reflect() || mode.is_symmetric() || mode.is_wrap() {
+        return None;
+    }
+    let mut values = lane.to_vec();
+    let n = values.len();
+    let res = match mode {
+        PadMode::Constant(value) => value,
+        PadMode::Maximum => values
+            .into_iter()
+            .reduce(|a, b| if b > a { b } else { a })?,
+        PadMode::Minimum => values
+            .into_iter()
+            .reduce(|a, b| if b < a { b } else { a })?,
+        PadMode::Mean => {
+            let sum = values.into_iter().fold(A::zero(), |acc, x| acc + x);
+            sum / A::from_usize(n)?
+        }
+        PadMode::Median => {
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+            match n {
+                0 => return None,
+                n if n % 2 == 1 => values[n / 2],
+                n => (values[n / 2 - 1] + values[n / 2]) / A::from_u8(2)?,
+            }
+        }
+        // the most frequent value, ties being resolved in favour of the first to occur
+        PadMode::Mode => {
+            // a stable sort of the positions by value places equal values in runs, each of
+            // which begins with the first occurrence of its value
+            let mut order = (0..n).collect::<Vec<_>>();
+            order.sort_by(|&i, &j| {
+                values[i]
+                    .partial_cmp(&values[j])
+                    .unwrap_or(core::cmp::Ordering::Equal)
+            });
+            let (mut best, mut freq, mut start) = (*order.first()?, 0, 0);
+            for end in 1..=n {
+                if end < n && values[order[end]] == values[order[start]] {
+                    continue;
+                }
+                let (count, first) = (end - start, order[start]);
+                if count > freq || (count == freq && first < best) {
+                    best = first;
+                    freq = count;
+                }
+                start = end;
+            }
+            values[best]
+        }
+        _ => return None,
+    };
+    Some(res)
+}
+
+/// Pads a single lane in place, whose `n` elements starting at `before` hold the original
+/// lane.
+fn pad_lane<A>(mut lane: ArrayViewMut1<A>, before: usize, n: usize, mode: PadMode<A>)
+where
+    A: Copy + FromPrimitive + Num + PartialOrd,
+{
+    let fill = statistic(&lane.slice(s![before..before + n]), mode);
+    for i in (0..before).chain(before + n..lane.len()) {
+        lane[i] = match fill {
+            Some(value) => value,
+            None => {
+                let offset = i as isize - before as isize;
+                lane[before + position(offset, n, mode.action(), mode.is_symmetric())]
+            }
+        };
+    }
+}
+
+/// Computes the shape of the array once padded by the given widths.
+fn padded_dim<D>(mut dim: D, pad: &[[usize; 2]]) -> D
+where
+    D: Dimension,
+{
+    for (ax, &[before, after]) in pad.iter().enumerate() {
+        dim[ax] += before + after;
+    }
+    dim
+}
+
+/// Writes the data into the middle of the output before padding each axis in place, in order;
+/// the output is expected to have the padded shape.
+fn pad_into<A, S, D>(
+    data: &ArrayBase<S, D>,
+    pad: &[[usize; 2]],
+    mode: PadMode<A>,
+    output: &mut Array<A, D>,
+) -> super::PadResult
+where
+    A: Copy + FromPrimitive + Num + PartialOrd,
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    let padded = |ax: usize| pad[ax][0] + pad[ax][1] > 0;
+    if !mode.is_constant() && (0..data.ndim()).any(|ax| padded(ax) && data.len_of(Axis(ax)) == 0) {
+        return Err(PadError::EmptyAxis);
+    }
+    // the original data, i.e. the region preceded by the given widths
+    let inner = |ax: usize| {
+        let before = pad[ax][0];
+        Slice::from(before..before + data.len_of(Axis(ax)))
+    };
+    output
+        .slice_each_axis_mut(|ad| inner(ad.axis.index()))
+        .assign(data);
+    for ax in (0..data.ndim()).filter(|&ax| padded(ax)) {
+        // the axes preceding this one have already been padded in full
+        let mut region = output.slice_each_axis_mut(|ad| match ad.axis.index() {
+            i if i <= ax => Slice::from(..),
+            i => inner(i),
+        });
+        let n = data.len_of(Axis(ax));
+        for lane in region.lanes_mut(Axis(ax)) {
+            pad_lane(lane, pad[ax][0], n, mode);
+        }
+    }
+    Ok(())
+}
+
+/// Pads the array according to the given mode, where `pad` holds the `[before, after]` widths
+/// of each axis, or a single pair used for every axis.
+///
+/// Much like NumPy's `pad`, axes are padded in order, each one operating on the result of the
+/// previous, such that the corners are consistent with the edges. The reflecting and wrapping
+/// modes support widths exceeding the length of an axis by repeating the pattern, while
+/// every mode other than [constant](PadMode::Constant) fails on axes of length zero.
 pub fn pad<A, S, D>(
     data: &ArrayBase<S, D>,
     pad: &[[usize; 2]],
     mode: PadMode<A>,
 ) -> Result<Array<A, D>, PadError>
 where
-    A: Copy + FromPrimitive + Num,
+    A: Copy + FromPrimitive + Num + PartialOrd,
     D: Dimension,
-    S: DataOwned<Elem = A>,
+    S: Data<Elem = A>,
 {
     let pad = reader(data.ndim(), pad)?;
-    let mut dim = data.raw_dim();
-    for (ax, (&ax_len, pad)) in data.shape().iter().zip(pad.iter()).enumerate() {
-        dim[ax] = ax_len + pad[0] + pad[1];
-    }
-
-    let mut padded = data.array_like(dim, mode.init()).to_owned();
-    let _ = pad_to(data, &pad, mode, &mut padded)?;
+    let mut padded = Array::from_elem(padded_dim(data.raw_dim(), &pad), mode.init());
+    pad_into(data, &pad, mode, &mut padded)?;
     Ok(padded)
 }
 
+/// Pads the array into the given output, whose shape must match that of the padded array.
 pub fn pad_to<A, S, D>(
     data: &ArrayBase<S, D>,
     pad: &[[usize; 2]],
@@ -46,26 +204,50 @@ pub fn pad_to<A, S, D>(
     output: &mut Array<A, D>,
 ) -> super::PadResult
 where
-    A: Copy + FromPrimitive + Num,
+    A: Copy + FromPrimitive + Num + PartialOrd,
     D: Dimension,
     S: Data<Elem = A>,
 {
     let pad = reader(data.ndim(), pad)?;
+    if output.raw_dim() != padded_dim(data.raw_dim(), &pad) {
+        return Err(PadError::InconsistentDimensions);
+    }
+    pad_into(data, &pad, mode, output)
+}
 
-    // Select portion of padded array that needs to be copied from the original array.
-    output
-        .slice_each_axis_mut(|ad| {
-            let AxisDescription { axis, len, .. } = ad;
-            let pad = pad[axis.index()];
-            Slice::from(pad[0]..len - pad[1])
-        })
-        .assign(data);
-
-    match mode.action() {
-        PadAction::StopAfterCopy => {
-            // Do nothing
-            return Ok(());
-        }
-        _ => unimplemented!(),
+/// Returns a view of the array with the given `[before, after]` widths removed from each
+/// axis; the inverse of [pad].
+pub fn crop<'a, A, S, D>(
+    data: &'a ArrayBase<S, D>,
+    pad: &[[usize; 2]],
+) -> Result<ArrayView<'a, A, D>, PadError>
+where
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    let pad = reader(data.ndim(), pad)?;
+    if data
+        .shape()
+        .iter()
+        .zip(pad.iter())
+        .any(|(&len, &[before, after])| before + after > len)
+    {
+        return Err(PadError::OutOfBounds);
     }
+    Ok(data.slice_each_axis(|ad| {
+        let AxisDescription { axis, len, .. } = ad;
+        let [before, after] = pad[axis.index()];
+        Slice::from(before..len - after)
+    }))
+}
+
+/// Removes the given `[before, after]` widths from each axis, returning an owned array; see
+/// [crop].
+pub fn unpad<A, S, D>(data: &ArrayBase<S, D>, pad: &[[usize; 2]]) -> Result<Array<A, D>, PadError>
+where
+    A: Clone,
+    D: Dimension,
+    S: Data<Elem = A>,
+{
+    crop(data, pad).map(|view| view.to_owned())
 }
@@ -0,0 +1,239 @@
+/*
+    Appellation: activate <test>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+extern crate concision_core as concision;
+
+use concision::func::activate::{gelu, gelu_tanh, Activate, PReLU, GELU};
+use concision::func::activate::{heavyside_derivative, linear_derivative, NdActivate};
+use concision::func::activate::{relu, relu_derivative, sigmoid, sigmoid_derivative};
+use concision::func::activate::{softmax, softmax_axis, softmax_axis_vjp, softmax_vjp};
+use concision::func::activate::{tanh, tanh_derivative};
+use ndarray::prelude::*;
+
+const TOL: f64 = 1e-6;
+
+/// Approximates the derivative of `f` at `x` using central differences.
+fn numeric<F>(f: F, x: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let h = 1e-6;
+    (f(x + h) - f(x - h)) / (2.0 * h)
+}
+
+#[test]
+fn test_derivatives() {
+    for x in [-2.5, -0.3, 0.4, 1.7] {
+        assert!((sigmoid_derivative(x) - numeric(sigmoid, x)).abs() < TOL);
+        assert!((tanh_derivative(x) - numeric(tanh, x)).abs() < TOL);
+        assert!((relu_derivative(x) - numeric(relu, x)).abs() < TOL);
+        assert_eq!(linear_derivative(x), 1.0);
+        assert_eq!(heavyside_derivative(x), 0.0);
+    }
+    assert_eq!(relu_derivative(0.0), 0.0);
+
+    let x = array![[-1.0, 0.0], [0.5, 2.0]];
+    assert_eq!(x.relu_derivative(), array![[0.0, 0.0], [1.0, 1.0]]);
+    assert_eq!(x.linear_derivative(), Array2::ones((2, 2)));
+    assert_eq!(x.heavyside_derivative(), Array2::zeros((2, 2)));
+    assert_eq!(x.sigmoid_derivative(), x.mapv(sigmoid_derivative));
+    assert_eq!(x.tanh_derivative(), x.mapv(tanh_derivative));
+}
+
+#[test]
+fn test_softmax_axis() {
+    let x: Array2<f64> = array![[1.0, 2.0, 3.0], [0.0, 0.0, 0.0]];
+    let rows = x.softmax_axis(1);
+    assert!(rows.sum_axis(Axis(1)).iter().all(|s| (s - 1.0).abs() < TOL));
+    assert!((rows[[1, 0]] - 1.0 / 3.0).abs() < TOL);
+    let cols = softmax_axis(&x, 0);
+    assert!(cols.sum_axis(Axis(0)).iter().all(|s| (s - 1.0).abs() < TOL));
+    assert!((cols[[0, 0]] - 1f64.exp() / (1.0 + 1f64.exp())).abs() < TOL);
+}
+
+#[test]
+fn test_softmax_vjp() {
+    let x = array![[0.2, -1.0, 0.5], [1.5, 0.3, -0.7]];
+    // an arbitrary upstream gradient, i.e. that of sum(w * softmax(x))
+    let w = array![[1.0, -2.0, 0.5], [0.3, 0.0, 2.0]];
+    let h = 1e-6;
+    let check = |f: &dyn Fn(&Array2<f64>) -> Array2<f64>, grad: Array2<f64>| {
+        for (idx, &g) in grad.indexed_iter() {
+            let (mut a, mut b) = (x.clone(), x.clone());
+            a[idx] += h;
+            b[idx] -= h;
+            let exp = ((&w * &f(&a)).sum() - (&w * &f(&b)).sum()) / (2.0 * h);
+            assert!((g - exp).abs() < TOL);
+        }
+    };
+    check(&|x| x.softmax(), x.softmax_vjp(&w));
+    check(&|x| softmax(x), softmax_vjp(&x, &w));
+    for axis in 0..2 {
+        check(&|x| x.softmax_axis(axis), x.softmax_axis_vjp(axis, &w));
+        check(&|x| softmax_axis(x, axis), softmax_axis_vjp(&x, axis, &w));
+    }
+}
+
+#[test]
+fn test_gelu() {
+    // reference values of x * Phi(x)
+    let exp: [(f64, f64); 3] = [
+        (-1.0, -0.15865525393145707),
+        (0.5, 0.34573123063700656),
+        (2.0, 1.9544997361036416),
+    ];
+    for (x, y) in exp {
+        assert!((gelu(x) - y).abs() < 1e-6);
+        assert!((gelu_tanh(x) - y).abs() < 1e-3);
+        assert!((GELU::gelu(x) - y).abs() < 1e-6);
+    }
+    let x = array![[-1.0, 0.5], [2.0, 0.0]];
+    assert_eq!(NdActivate::gelu(&x), x.mapv(gelu));
+    assert_eq!(NdActivate::gelu(&x)[[1, 1]], 0.0);
+    let z = num::Complex::new(0.5f64, 0.0);
+    assert!((gelu_tanh(z).re - gelu_tanh(0.5f64)).abs() < 1e-12);
+}
+
+#[test]
+fn test_activations() {
+    use concision::func::activate::*;
+
+    let x = array![-3.5, -1.0, 0.0, 0.5, 4.0];
+    assert_eq!(NdActivate::silu(&x), NdActivate::swish(&x, 1.0));
+    assert_eq!(NdActivate::silu(&x)[3], 0.5 * sigmoid(0.5));
+    assert!((elu(-1.0, 2.0) - 2.0 * (1f64.exp().recip() - 1.0)).abs() < TOL);
+    assert_eq!(NdActivate::elu(&x, 1.0)[4], 4.0);
+    assert!((SELU::selu(-100.0f64) + 1.0507009873554805 * 1.6732632423543772).abs() < TOL);
+    assert_eq!(NdActivate::leaky_relu(&x, 0.1)[1], -0.1);
+    assert_eq!(LeakyReLU::leaky_relu(&x)[1], -0.01);
+    // softplus neither overflows nor underflows
+    assert_eq!(softplus(1000.0), 1000.0);
+    assert!((softplus(0.0) - 2f64.ln()).abs() < TOL);
+    assert!(softplus(-1000.0) >= 0.0);
+    assert_eq!(softsign(-1.0), -0.5);
+    assert!((mish(1.0) - 1f64.exp().ln_1p().tanh()).abs() < TOL);
+    assert_eq!(
+        NdActivate::hard_sigmoid(&x),
+        array![0.0, 1.0 / 3.0, 0.5, 3.5 / 6.0, 1.0]
+    );
+    assert_eq!(HardSwish::hard_swish(&x)[4], 4.0);
+    assert_eq!(hard_swish(-1.0), -1.0 / 3.0);
+}
+
+#[test]
+fn test_prelu() {
+    let x = array![[-1.0, 2.0, -4.0], [3.0, -2.0, 0.5]];
+    let prelu = PReLU::new(array![0.1, 0.2, 0.3]);
+    let y = prelu.activate(&x).unwrap();
+    assert_eq!(y, array![[-0.1, 2.0, -1.2], [3.0, -0.4, 0.5]]);
+    let shared = PReLU::shared(0.5).forward(&x).unwrap();
+    assert_eq!(shared[[0, 2]], -2.0);
+    // the gradient of sum(y) with respect to the slopes sums the negative inputs of each channel
+    let (dx, dalpha) = prelu.grad(&x, &Array2::ones((2, 3))).unwrap();
+    assert_eq!(dalpha, array![-1.0, -2.0, -4.0]);
+    assert_eq!(dx, array![[0.1, 1.0, 0.3], [1.0, 0.2, 1.0]]);
+    let (_, dalpha) = PReLU::<f64>::default()
+        .grad(&x, &Array2::ones((2, 3)))
+        .unwrap();
+    assert_eq!(dalpha, array![-7.0]);
+}
+
+#[test]
+fn test_prelu_mismatch() {
+    use concision::PredictError;
+
+    let x = array![[-1.0, 2.0, -4.0], [3.0, -2.0, 0.5]];
+    // there must be a single slope or one for each channel
+    let prelu = PReLU::new(array![0.1, 0.2]);
+    assert_eq!(prelu.forward(&x), Err(PredictError::ShapeMismatch));
+    assert_eq!(
+        prelu.grad(&x, &Array2::ones((2, 3))),
+        Err(PredictError::ShapeMismatch)
+    );
+    assert!(prelu.forward(&x.t()).is_ok());
+}
+
+#[test]
+fn test_stable_softmax() {
+    use concision::func::activate::{log_softmax, log_softmax_axis, logsumexp, logsumexp_axis};
+
+    let x: Array2<f64> = array![[1000.0, 1000.0, 990.0], [-1000.0, 0.0, 1.0]];
+    let y = softmax_axis(&x, 1);
+    assert!(y.iter().all(|v| v.is_finite()));
+    assert!(y.sum_axis(Axis(1)).iter().all(|s| (s - 1.0).abs() < TOL));
+    assert!((y[[0, 0]] - 0.5 / (1.0 + 0.5 * (-10f64).exp())).abs() < TOL);
+    assert!(softmax(&x).iter().all(|v| v.is_finite()));
+    assert!(NdActivate::softmax(&x).sum().is_finite());
+    // f32 overflows far sooner
+    let x32 = array![100f32, 100.0];
+    assert_eq!(softmax(&x32), array![0.5f32, 0.5]);
+
+    let lse: Array1<f64> = logsumexp_axis(&x, 1);
+    assert!((lse[0] - (1000.0 + (2.0 + (-10f64).exp()).ln())).abs() < TOL);
+    assert!((logsumexp(&x) - lse[0]).abs() < TOL);
+    let ls = log_softmax_axis(&x, 1);
+    let (a, b) = (ls.row(0), y.row(0).mapv(f64::ln));
+    assert!((&a - &b).iter().all(|d| d.abs() < TOL));
+    // the probability underflows, though its logarithm remains finite
+    assert_eq!(y[[1, 0]], 0.0);
+    assert!((ls[[1, 0]] + 1001.0 + (1.0 + (-1f64).exp()).ln()).abs() < TOL);
+    let all = log_softmax(&x);
+    assert!((all.mapv(f64::exp).sum() - 1.0).abs() < TOL);
+    assert_eq!(NdActivate::log_softmax_axis(&x, 1), ls);
+}
+
+#[test]
+fn test_activation() {
+    use concision::func::activate::Activation;
+    use strum::IntoEnumIterator;
+
+    assert_eq!("relu".parse::<Activation>().unwrap(), Activation::ReLU);
+    assert_eq!(
+        "leaky_relu".parse::<Activation>().unwrap(),
+        Activation::LeakyReLU
+    );
+    assert_eq!(
+        "softmax".parse::<Activation>().unwrap(),
+        Activation::Softmax { axis: None }
+    );
+    assert_eq!(Activation::GELUTanh.to_string(), "gelu_tanh");
+    assert_eq!(Activation::default(), Activation::Linear);
+
+    let x: Array2<f64> = array![[-2.5, -0.3, 0.4], [1.7, 0.9, -1.2]];
+    assert_eq!(Activation::ReLU.activate(&x), x.relu());
+    assert_eq!(Activation::GELU.activate(x.clone()), x.mapv(gelu));
+    assert_eq!(Activation::Linear.activate(&x), x);
+    assert_eq!(
+        Activation::Softmax { axis: None }.activate(&x),
+        softmax_axis(&x, 1)
+    );
+    assert_eq!(
+        Activation::Softmax { axis: Some(0) }.activate(&x),
+        softmax_axis(&x, 0)
+    );
+
+    for rho in Activation::iter().filter(Activation::is_elementwise) {
+        for &v in x.iter() {
+            let df = numeric(|v| rho.eval(v), v);
+            assert!((rho.derivative(v) - df).abs() < TOL, "{rho}");
+        }
+    }
+    // compare the vector-Jacobian products against those of a weighted sum of the outputs
+    let g: Array2<f64> = array![[0.3, -1.0, 0.5], [2.0, 0.1, -0.4]];
+    for rho in [
+        Activation::Sigmoid,
+        Activation::Softmax { axis: None },
+        Activation::LogSoftmax { axis: Some(0) },
+    ] {
+        let dx = rho.vjp(&x, &g);
+        for (idx, &d) in dx.indexed_iter() {
+            let f = |v: f64| {
+                let mut x = x.clone();
+                x[idx] = v;
+                (rho.apply(&x) * &g).sum()
+            };
+            assert!((d - numeric(f, x[idx])).abs() < TOL, "{rho}");
+        }
+    }
+}
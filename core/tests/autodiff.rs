@@ -0,0 +1,221 @@
+/*
+   Appellation: autodiff <test>
+   Contrib: FL03 <jo3mccain@icloud.com>
+*/
+extern crate concision_core as cnc;
+
+use cnc::autodiff::Tape;
+use cnc::Backward;
+use ndarray::prelude::*;
+
+#[test]
+fn test_broadcast_add_mul() {
+    let tape = Tape::<f64>::new();
+    let x = tape.var(&array![[1.0, 2.0], [3.0, 4.0]]);
+    let b = tape.var(&array![10.0, 20.0]);
+    let y = (x * x + b).sum();
+    assert_eq!(y.value(), arr0(90.0).into_dyn());
+
+    let grads = y.backward();
+    assert_eq!(grads.wrt_as::<Ix2>(&x), array![[2.0, 4.0], [6.0, 8.0]]);
+    // the bias is broadcast across the rows, so its gradient sums over them
+    assert_eq!(grads.wrt_as::<Ix1>(&b), array![2.0, 2.0]);
+}
+
+#[test]
+fn test_matmul() {
+    let tape = Tape::<f64>::new();
+    let a = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+    let w = array![[1.0, 0.0], [0.0, 1.0], [1.0, 1.0]];
+    let (x, y) = (tape.var(&a), tape.var(&w));
+    let z = x.dot(&y).mean();
+
+    let grads = z.backward();
+    let ones = Array2::<f64>::from_elem((2, 2), 0.25);
+    assert_eq!(grads.wrt_as::<Ix2>(&x), ones.dot(&w.t()));
+    assert_eq!(grads.wrt_as::<Ix2>(&y), a.t().dot(&ones));
+}
+
+#[test]
+fn test_softmax() {
+    let tape = Tape::<f64>::new();
+    let x = tape.var(&array![[1.0, 2.0, 3.0], [1.0, 1.0, 1.0]]);
+    let y = x.softmax_axis(1);
+    assert!(y
+        .value()
+        .sum_axis(Axis(1))
+        .iter()
+        .all(|&s| (s - 1.0).abs() < 1e-12));
+    // the softmax sums to a constant along the axis, so its gradient vanishes
+    let grads = y.sum().backward();
+    assert!(grads.wrt(&x).unwrap().iter().all(|g| g.abs() < 1e-12));
+}
+
+#[test]
+fn test_log_softmax() {
+    let tape = Tape::<f64>::new();
+    let x = tape.var(&array![[1.0, 2.0, 3.0], [-800.0, 0.0, 800.0]]);
+    let y = x.log_softmax_axis(1);
+    let exp = y.value().mapv(f64::exp);
+    assert!(exp
+        .sum_axis(Axis(1))
+        .iter()
+        .all(|&s| (s - 1.0).abs() < 1e-12));
+    // the gradient of the sum is 1 - k * softmax, remaining finite for vanishing probabilities
+    let grads = y.sum().backward();
+    let dx = grads.wrt_as::<Ix2>(&x);
+    assert!(dx.iter().all(|g| g.is_finite()));
+    assert!((dx - (1.0 - exp * 3.0)).iter().all(|d| d.abs() < 1e-12));
+}
+
+#[test]
+fn test_activations() {
+    let tape = Tape::<f64>::new();
+    let x = tape.var(&array![-1.0, 0.0, 2.0]);
+    let grads = x.sigmoid().sum().backward();
+    let s = array![-1.0, 0.0, 2.0].mapv(|x: f64| 1.0 / (1.0 + (-x).exp()));
+    assert_eq!(grads.wrt_as::<Ix1>(&x), &s * &(1.0 - &s));
+
+    let grads = (x.relu() + x.tanh()).sum().backward();
+    let exp = array![0.0, 0.0, 1.0] + array![-1.0, 0.0, 2.0].mapv(|x: f64| 1.0 - x.tanh().powi(2));
+    assert_eq!(grads.wrt_as::<Ix1>(&x), exp);
+}
+
+#[test]
+fn test_activation() {
+    use cnc::func::activate::Activation;
+
+    let x: Array2<f64> = array![[-1.5, 0.2, 0.7], [1.1, -0.4, 2.0]];
+    for rho in [
+        Activation::GELU,
+        Activation::Softmax { axis: None },
+        Activation::LogSoftmax { axis: None },
+    ] {
+        let tape = Tape::new();
+        let v = tape.var(&x);
+        let y = v.activate(rho);
+        let expected = rho.apply(&x);
+        assert!((y.value() - &expected.into_dyn())
+            .iter()
+            .all(|d| d.abs() < 1e-12));
+        let grads = y.sum().backward();
+        let dx = rho.vjp(&x, &Array2::ones(x.dim()));
+        assert!((grads.wrt_as::<Ix2>(&v) - dx)
+            .iter()
+            .all(|d| d.abs() < 1e-10));
+
+        // scalars are accepted, matching the activation of a 0-d array
+        let s: Array0<f64> = arr0(0.7);
+        let tape = Tape::new();
+        let v = tape.var(&s);
+        let y = v.activate(rho);
+        assert!((y.value() - &rho.apply(&s).into_dyn())
+            .iter()
+            .all(|d| d.abs() < 1e-12));
+        let grads = y.backward();
+        let ds = rho.vjp(&s, &arr0(1.0));
+        assert!((grads.wrt_as::<Ix0>(&v) - ds)
+            .iter()
+            .all(|d| d.abs() < 1e-10));
+    }
+}
+
+#[test]
+fn test_grad_check() {
+    use cnc::autodiff::{GradCheck, Objective, TraceLoss};
+    use cnc::func::loss::{Loss, MSE};
+
+    let x: Array2<f64> = array![[0.5, -1.0], [2.0, 0.3], [-0.7, 1.2]];
+    let y: Array2<f64> = array![[1.0], [0.0], [0.5]];
+    let mut w: Array2<f64> = array![[0.2], [-0.4]];
+
+    let tape = Tape::new();
+    let v = tape.var(&w);
+    let loss = MSE.trace_loss(tape.var(&x).dot(&v), &y.view()).unwrap();
+    let grad = loss.backward().wrt_as::<Ix2>(&v);
+
+    let check = GradCheck::default().eps(1e-5);
+    let f = |w: &Array2<f64>| MSE.loss(&x.dot(w), &y).objective();
+    let errs = check.check(&mut w, &grad, f);
+    assert_eq!(errs.len(), 1);
+    assert!(errs[0].max_abs < 1e-8 && errs[0].is_close(1e-8, 1e-6));
+    #[cfg(feature = "approx")]
+    assert!(errs[0].abs_diff_eq(1e-8) && errs[0].relative_eq(1e-8, 1e-6));
+    // the parameters are restored once perturbed
+    assert_eq!(w, array![[0.2], [-0.4]]);
+    // an incorrect gradient is reported
+    let errs = check.check(&mut w, &(&grad * 2.0), f);
+    assert!((errs[0].max_rel - 0.5).abs() < 1e-6);
+    assert!(!errs[0].is_close(1e-6, 1e-6));
+    // the first error of a fallible objective is returned, restoring the parameters
+    let res = check.try_check(&mut w, &grad, |w| {
+        if w[[1, 0]] < -0.4 {
+            Err("out of range")
+        } else {
+            Ok(f(w))
+        }
+    });
+    assert_eq!(res, Err("out of range"));
+    assert_eq!(w, array![[0.2], [-0.4]]);
+}
+
+#[test]
+fn test_trace_loss() {
+    use cnc::autodiff::TraceLoss;
+    use cnc::func::loss::{CrossEntropy, Huber, Loss, LossGrad, MSE};
+
+    let pred: Array2<f64> = array![[0.2, 0.8], [0.6, 0.4], [0.9, 0.1]];
+    let target: Array2<f64> = array![[0.0, 1.0], [1.0, 0.0], [0.0, 1.0]];
+    let huber = Huber::new(0.5);
+    let entropy = CrossEntropy::new().with_label_smoothing(0.1);
+
+    let tape = Tape::new();
+    let v = tape.var(&pred);
+    let loss = huber.trace_loss(v, &target.view()).unwrap();
+    assert_eq!(
+        loss.value().sum(),
+        huber.loss(&pred, &target).unwrap().sum()
+    );
+    let grad = loss.backward().wrt_as::<Ix2>(&v);
+    assert_eq!(Some(grad), huber.loss_grad(&pred, &target));
+
+    let loss = entropy.trace_loss(v, &target.view()).unwrap();
+    assert_eq!(
+        loss.value().sum(),
+        entropy.loss(&pred, &target).unwrap().sum()
+    );
+    let grad = loss.backward().wrt_as::<Ix2>(&v);
+    assert_eq!(Some(grad), entropy.loss_grad(&pred, &target));
+    // unit weights leave the loss unchanged
+    let ones = Array1::ones(3);
+    let weighted = huber
+        .trace_weighted(v, &target.view(), ones.view())
+        .unwrap();
+    let expected = huber.loss(&pred, &target).unwrap();
+    assert!((weighted.value().sum() - expected.sum()).abs() < 1e-12);
+    // a sample of zero weight is excluded from the loss
+    let weights = array![1.0, 0.0, 1.0];
+    let rows = [0, 2];
+    let expected = MSE
+        .trace_loss(
+            tape.var(&pred.select(Axis(0), &rows)),
+            &target.select(Axis(0), &rows).view(),
+        )
+        .unwrap();
+    let weighted = MSE
+        .trace_weighted(v, &target.view(), weights.view())
+        .unwrap();
+    assert!((weighted.value().sum() - expected.value().sum()).abs() < 1e-12);
+    let weighted = entropy
+        .trace_weighted(v, &target.view(), weights.view())
+        .unwrap();
+    let expected = entropy.loss(&pred.select(Axis(0), &rows), &target.select(Axis(0), &rows));
+    assert!((weighted.value().sum() - expected.unwrap().sum()).abs() < 1e-12);
+    // weights which do not match the samples are rejected rather than truncated
+    let short = weights.slice(s![..2]);
+    assert!(huber.trace_weighted(v, &target.view(), short).is_none());
+    assert!(MSE.trace_weighted(v, &target.view(), short).is_none());
+    assert!(entropy.trace_weighted(v, &target.view(), short).is_none());
+    let mismatched = Huber::new(0.5).with_weights(array![1.0, 2.0]);
+    assert!(mismatched.trace_loss(v, &target.view()).is_none());
+}
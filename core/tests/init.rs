@@ -4,8 +4,9 @@
 */
 extern crate concision_core as cnc;
 
-use cnc::init::distr::LecunNormal;
+use cnc::init::distr::{FanMode, KaimingUniform, LecunNormal};
 use cnc::init::InitializeExt;
+use cnc::math::linalg::qr;
 use ndarray::prelude::*;
 
 #[test]
@@ -43,3 +44,141 @@ fn test_truncnorm() {
     let arr = Array::truncnorm(shape, mean, std).unwrap();
     assert!(arr.iter().all(|&x| x >= -bnd && x <= bnd));
 }
+
+#[test]
+fn test_kaiming() {
+    let shape = (4, 6);
+    let gain = 2f64.sqrt();
+    let bnd = KaimingUniform::new(6, gain).boundary();
+    assert_eq!(bnd, gain * 0.5f64.sqrt());
+
+    let arr = Array2::<f64>::kaiming_uniform(shape, FanMode::FanIn, gain);
+    assert!(arr.iter().all(|&x| x >= -bnd && x <= bnd));
+    let arr = Array2::<f64>::kaiming_normal(shape, FanMode::FanOut, gain);
+    assert_eq!(arr.dim(), shape);
+    assert_eq!(cnc::init::fans(&[4, 6, 3]), (18, 12));
+}
+
+#[test]
+fn test_qr() {
+    let a = Array2::<f64>::stdnorm((5, 3));
+    let (q, r) = qr(&a);
+    assert_eq!((q.dim(), r.dim()), ((5, 3), (3, 3)));
+    assert!((q.t().dot(&q) - Array2::<f64>::eye(3))
+        .iter()
+        .all(|x| x.abs() < 1e-10));
+    assert!((q.dot(&r) - &a).iter().all(|x| x.abs() < 1e-10));
+    assert!(r.indexed_iter().all(|((i, j), &x)| i <= j || x == 0.0));
+}
+
+#[test]
+fn test_orthogonal() {
+    for shape in [(3, 5), (5, 3)] {
+        let gain = 2f64;
+        let arr = Array2::<f64>::orthogonal(shape, gain) / gain;
+        let gram = if shape.0 < shape.1 {
+            arr.dot(&arr.t())
+        } else {
+            arr.t().dot(&arr)
+        };
+        let eye = Array2::<f64>::eye(shape.0.min(shape.1));
+        assert!((gram - eye).iter().all(|x| x.abs() < 1e-10));
+    }
+}
+
+#[test]
+fn test_sparse() {
+    let (rows, cols) = (10, 4);
+    let arr = Array2::<f64>::sparse((rows, cols), 0.25, 1.0).unwrap();
+    for col in arr.columns() {
+        assert!(col.iter().filter(|&&x| x == 0.0).count() >= 3);
+    }
+}
+
+#[test]
+fn test_identity_constant() {
+    let eye = Array2::<f64>::identity((3, 4));
+    assert_eq!(eye, Array2::eye(4).slice(s![..3, ..]));
+    let arr = Array2::<f64>::constant((2, 2), 0.5);
+    assert_eq!(arr, Array2::from_elem((2, 2), 0.5));
+}
+
+#[test]
+fn test_init() {
+    use cnc::init::Init;
+    use core::str::FromStr;
+
+    assert_eq!(
+        Init::from_str("trunc_normal").unwrap(),
+        Init::TruncNormal {
+            mean: 0.0,
+            std: 0.0
+        }
+    );
+    let shape = (3, 4);
+    let zeros = Init::Zeros.array::<f64, _, _>(shape).unwrap();
+    assert_eq!(zeros, Array2::zeros(shape));
+
+    let arr: Array2<f64> = Init::Uniform { bound: None }.array(shape).unwrap();
+    let bnd = 0.5;
+    assert!(arr.iter().all(|&x| x >= -bnd && x <= bnd));
+    // the fans of the weights scale the biases
+    let mut rng = rand::thread_rng();
+    let init = Init::Uniform { bound: None };
+    let bias: Array1<f64> = init.array_with(3, (100, 3), &mut rng).unwrap();
+    assert!(bias.iter().all(|&x| x.abs() <= 0.1));
+    // a bound which isn't positive is rejected rather than panicking
+    for bound in [0.0, -1.0, f64::NAN] {
+        let init = Init::Uniform { bound: Some(bound) };
+        let res = init.array::<f64, _, _>(shape);
+        assert_eq!(res.err(), Some(rand_distr::NormalError::BadVariance));
+    }
+
+    let arr: Array2<f64> = Init::Orthogonal { gain: None }.array(shape).unwrap();
+    assert!((arr.dot(&arr.t()) - Array2::<f64>::eye(3))
+        .iter()
+        .all(|x| x.abs() < 1e-10));
+    let init = Init::Normal {
+        mean: 0.0,
+        std: f64::NAN,
+    };
+    assert!(init.array::<f64, _, _>(shape).is_err());
+    let init = Init::TruncNormal {
+        mean: 0.0,
+        std: -1.0,
+    };
+    assert!(init.array::<f64, _, _>(shape).is_err());
+}
+
+#[test]
+fn test_rng_context() {
+    use cnc::init::{Initialize, RngContext};
+    use rand_distr::StandardNormal;
+
+    let (mut a, mut b) = (RngContext::new(42), RngContext::new(42));
+    let x = Array2::<f64>::rand_with((3, 3), StandardNormal, &mut a);
+    assert_eq!(x, Array2::<f64>::rand_with((3, 3), StandardNormal, &mut b));
+    // the forks are independent of the values drawn by their parent
+    let mut c = RngContext::new(42);
+    let (fa, fc) = (a.fork(), c.fork());
+    assert_eq!(fa.seed(), fc.seed());
+    assert_ne!(fa.seed(), c.fork().seed());
+    assert_eq!(c.forks(), 2);
+}
+
+#[test]
+fn test_init_ext_rng() {
+    use cnc::init::RngContext;
+
+    let seeded = |seed: u64| {
+        let mut rng = RngContext::new(seed);
+        (
+            Array2::<f64>::kaiming_normal_with((4, 3), FanMode::FanIn, 1.0, &mut rng),
+            Array2::<f64>::kaiming_uniform_with((4, 3), FanMode::FanOut, 1.0, &mut rng),
+            Array2::<f64>::orthogonal_with((4, 3), 1.0, &mut rng),
+            Array2::<f64>::sparse_with((4, 3), 0.5, 0.01, &mut rng).unwrap(),
+        )
+    };
+    assert_eq!(seeded(0), seeded(0));
+    assert_ne!(seeded(0).2, seeded(1).2);
+}
@@ -0,0 +1,476 @@
+/*
+    Appellation: loss <test>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+extern crate concision_core as concision;
+
+use concision::func::loss::*;
+use ndarray::prelude::*;
+
+const TOL: f64 = 1e-10;
+
+fn scalar(x: ArrayD<f64>) -> f64 {
+    assert_eq!(x.ndim(), 0);
+    x.sum()
+}
+
+#[test]
+fn test_binary_cross_entropy() {
+    let pred = array![0.9, 0.2, 0.6];
+    let target = array![1.0, 0.0, 1.0];
+    let exp = -(0.9f64.ln() + 0.8f64.ln() + 0.6f64.ln()) / 3.0;
+    let bce = BinaryCrossEntropy::new();
+    assert!((bce.loss(&pred, &target).map(scalar).unwrap() - exp).abs() < TOL);
+    let sum = bce.clone().with_reduction(Reduction::Sum);
+    assert!((sum.loss(&pred, &target).map(scalar).unwrap() - 3.0 * exp).abs() < TOL);
+    let none = bce.losses(&pred, &target).unwrap();
+    assert_eq!(none.shape(), &[3]);
+    assert!((none[1] + 0.8f64.ln()).abs() < TOL);
+    // the positive class is weighted by the second weight
+    let weighted = bce
+        .clone()
+        .with_weights(array![1.0, 2.0])
+        .with_reduction(Reduction::Sum);
+    let exp_w = -(2.0 * 0.9f64.ln() + 0.8f64.ln() + 2.0 * 0.6f64.ln());
+    assert!((weighted.loss(&pred, &target).map(scalar).unwrap() - exp_w).abs() < TOL);
+    // the class weights must hold exactly two classes
+    let mismatched = BinaryCrossEntropy::new().with_weights(array![1.0, 2.0, 3.0]);
+    assert_eq!(mismatched.loss(&pred, &target), None);
+    assert_eq!(mismatched.loss_grad(&pred, &target), None);
+    // label smoothing pulls the targets towards one half
+    let smooth = BinaryCrossEntropy::new().with_label_smoothing(0.2);
+    let y = array![0.9, 0.1, 0.9];
+    let exp_s = BinaryCrossEntropy::new()
+        .loss(&pred, &y)
+        .map(scalar)
+        .unwrap();
+    assert!((smooth.loss(&pred, &target).map(scalar).unwrap() - exp_s).abs() < TOL);
+}
+
+#[test]
+fn test_binary_cross_entropy_with_logits() {
+    let logits = array![[2.0, -1.0], [0.5, 40.0]];
+    let target = array![[1.0, 0.0], [0.0, 1.0]];
+    let probs = logits.mapv(|x: f64| 1.0 / (1.0 + (-x).exp()));
+    let bce = BinaryCrossEntropy::new().with_weights(array![0.5, 1.5]);
+    let fused = BinaryCrossEntropyWithLogits::new().with_weights(array![0.5, 1.5]);
+    let (a, b) = (
+        bce.loss(&probs, &target).map(scalar).unwrap(),
+        fused.loss(&logits, &target).map(scalar).unwrap(),
+    );
+    assert!((a - b).abs() < 1e-8);
+    // remains finite for extreme logits
+    let extreme = fused.loss(&array![1000.0, -1000.0], &array![0.0, 1.0]);
+    assert!(extreme.map(scalar).unwrap().is_finite());
+}
+
+#[test]
+fn test_cross_entropy() {
+    let probs = array![[0.7, 0.2, 0.1], [0.1, 0.3, 0.6]];
+    let target = array![[1.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+    let labels = array![0usize, 2];
+    let exp = -(0.7f64.ln() + 0.6f64.ln()) / 2.0;
+
+    let ce = CrossEntropy::new();
+    assert!((ce.loss(&probs, &target).map(scalar).unwrap() - exp).abs() < TOL);
+    let sparse = SparseCrossEntropy::new();
+    assert!((sparse.loss(&probs, &labels).map(scalar).unwrap() - exp).abs() < TOL);
+    assert!((probs.cross_entropy(&target) - exp).abs() < TOL);
+
+    // the weighted mean is normalized by the total weight of the targets
+    let weighted = SparseCrossEntropy::new().with_weights(array![2.0, 1.0, 0.5]);
+    let exp_w = -(2.0 * 0.7f64.ln() + 0.5 * 0.6f64.ln()) / 2.5;
+    assert!((weighted.loss(&probs, &labels).map(scalar).unwrap() - exp_w).abs() < TOL);
+
+    let smooth = SparseCrossEntropy::new().with_label_smoothing(0.3);
+    let soft = target.mapv(|y| y * 0.7 + 0.1);
+    let exp_s = CrossEntropy::new().loss(&probs, &soft).map(scalar).unwrap();
+    assert!((smooth.loss(&probs, &labels).map(scalar).unwrap() - exp_s).abs() < TOL);
+
+    let none = ce.losses(&probs, &target).unwrap();
+    assert_eq!(none.shape(), &[2]);
+    // without a reduction the loss of each sample is returned
+    let unreduced = CrossEntropy::new().with_reduction(Reduction::None);
+    assert_eq!(
+        unreduced.loss(&probs, &target),
+        Some(none.clone().into_dyn())
+    );
+    let unreduced = SparseCrossEntropy::new().with_reduction(Reduction::None);
+    assert_eq!(unreduced.loss(&probs, &labels), Some(none.into_dyn()));
+    // labels beyond the classes, or weights not matching them, yield nothing
+    assert_eq!(sparse.loss(&probs, &array![0usize, 3]), None);
+    assert_eq!(sparse.loss_grad(&probs, &array![0usize, 3]), None);
+    assert_eq!(
+        weighted.loss(&probs.slice(s![.., ..2]), &array![0usize, 1]),
+        None
+    );
+}
+
+#[test]
+fn test_cross_entropy_with_logits() {
+    let logits = array![[2.0, 1.0, 0.1], [0.5, 2.5, -1.0]];
+    let labels = array![1usize, 1];
+    let probs =
+        logits.mapv(f64::exp) / logits.mapv(f64::exp).sum_axis(Axis(1)).insert_axis(Axis(1));
+    let a = SparseCrossEntropy::new()
+        .loss(&probs, &labels)
+        .map(scalar)
+        .unwrap();
+    let b = SparseCrossEntropyWithLogits::new()
+        .loss(&logits, &labels)
+        .map(scalar)
+        .unwrap();
+    assert!((a - b).abs() < TOL);
+    // large logits do not overflow
+    let big = logits.mapv(|x| x * 1000.0);
+    let res = SparseCrossEntropyWithLogits::new().loss(&big, &labels);
+    assert!(res.map(scalar).unwrap().is_finite());
+}
+
+#[test]
+fn test_huber() {
+    let pred = array![0.5, 3.0, -1.0];
+    let target = array![0.0, 0.0, 0.0];
+    let huber = Huber::new(1.0).with_reduction(Reduction::None);
+    let res = huber.loss(&pred, &target);
+    assert_eq!(res, Some(array![0.125, 2.5, 0.5].into_dyn()));
+    // smooth l1 is the huber loss scaled by the reciprocal of beta
+    let huber = Huber::new(2.0).with_reduction(Reduction::Sum);
+    let smooth = SmoothL1::new(2.0).with_reduction(Reduction::Sum);
+    let (a, b) = (
+        huber.loss(&pred, &target).map(scalar).unwrap(),
+        smooth.loss(&pred, &target).map(scalar).unwrap(),
+    );
+    assert!((a / 2.0 - b).abs() < TOL);
+}
+
+#[test]
+fn test_log_cosh() {
+    let pred = array![[0.5, -2.0], [1000.0, 0.0]];
+    let target = Array2::<f64>::zeros((2, 2));
+    let res = LogCosh::new()
+        .with_reduction(Reduction::None)
+        .loss(&pred, &target)
+        .unwrap();
+    assert!((res[[0, 0]] - 0.5f64.cosh().ln()).abs() < TOL);
+    assert!((res[[0, 1]] - 2f64.cosh().ln()).abs() < TOL);
+    // large residuals do not overflow
+    assert!((res[[1, 0]] - (1000.0 - 2f64.ln())).abs() < TOL);
+    assert_eq!(res[[1, 1]], 0.0);
+}
+
+#[test]
+fn test_quantile() {
+    let pred = array![1.0, 3.0];
+    let target = array![2.0, 2.0];
+    let loss = Pinball::new(0.9).with_reduction(Reduction::None);
+    let res: ArrayD<f64> = loss.loss(&pred, &target).unwrap();
+    // under-predictions are penalized by the quantile, over-predictions by its complement
+    assert!((res[[0]] - 0.9).abs() < TOL);
+    assert!((res[[1]] - 0.1).abs() < TOL);
+    // the median loss is half the mean absolute error
+    let median = Quantile::default()
+        .loss(&pred, &target)
+        .map(scalar)
+        .unwrap();
+    assert!((median - 0.5 * mae(&pred, &target).unwrap()).abs() < TOL);
+}
+
+#[test]
+fn test_msle() {
+    let pred = array![0.0, 3.0];
+    let target = array![1.0, 1.0];
+    let exp = (2f64.ln().powi(2) + 2f64.ln().powi(2)) / 2.0;
+    let res = MSLE::new().loss(&pred, &target).map(scalar).unwrap();
+    assert!((res - exp).abs() < TOL);
+}
+
+#[test]
+fn test_sample_weights() {
+    let pred = array![[1.0, 1.0], [2.0, 2.0], [0.0, 0.0]];
+    let target = Array2::<f64>::zeros((3, 2));
+    let weights = array![1.0, 0.0, 3.0];
+    let huber = Huber::new(10.0).with_weights(weights.clone());
+    // the weighted mean is normalized by the total weight of the samples
+    let loss = huber.loss(&pred, &target).map(scalar).unwrap();
+    assert!((loss - 0.125).abs() < TOL);
+    let sum = huber.clone().with_reduction(Reduction::Sum);
+    assert!((sum.loss(&pred, &target).map(scalar).unwrap() - 1.0).abs() < TOL);
+    let none = huber.clone().with_reduction(Reduction::None);
+    let losses = none.loss(&pred, &target).unwrap();
+    assert_eq!(losses.index_axis(Axis(0), 1).sum(), 0.0);
+    // weights given explicitly take precedence over those configured
+    let unweighted = huber.weighted_loss(&pred, &target, None).map(scalar);
+    assert!((unweighted.unwrap() - 5.0 / 6.0).abs() < TOL);
+    let ones = Array1::ones(3);
+    let res = MSLE::new().weighted_loss(&pred, &target, Some(&ones));
+    let exp = MSLE::new().loss(&pred, &target);
+    assert!((res.map(scalar).unwrap() - exp.map(scalar).unwrap()).abs() < TOL);
+    // mismatched weights yield nothing rather than panicking
+    let short = Huber::new(10.0).with_weights(array![1.0, 2.0]);
+    assert_eq!(short.loss(&pred, &target), None);
+    assert_eq!(short.loss_grad(&pred, &target), None);
+    assert_eq!(
+        huber.weighted_loss(&pred, &target, Some(&Array1::ones(4))),
+        None
+    );
+    // there must be a weight for each sample
+    let mean = Reduction::Mean;
+    assert_eq!(mean.reduce_weighted(&pred, Some(&array![1.0, 2.0])), None);
+    assert_eq!(
+        mean.grad_weighted(pred.clone(), Some(&Array1::ones(4))),
+        None
+    );
+}
+
+#[test]
+fn test_kl_divergence() {
+    let pred = array![[0.5, 0.5], [0.25, 0.75]];
+    let target = array![[0.5, 0.5], [1.0, 0.0]];
+    let res: ArrayD<f64> = KLDivergence::new()
+        .with_reduction(Reduction::None)
+        .loss(&pred, &target)
+        .unwrap();
+    assert!(res[[0]].abs() < TOL);
+    assert!((res[[1]] - 4f64.ln()).abs() < TOL);
+    let weighted = KLDivergence::new()
+        .with_weights(array![2.0, 1.0])
+        .loss(&pred, &target);
+    assert!((weighted.map(scalar).unwrap() - 4f64.ln()).abs() < TOL);
+    // the class weights must match the classes
+    let mismatched = KLDivergence::new().with_weights(array![1.0, 2.0, 3.0]);
+    assert_eq!(mismatched.loss(&pred, &target), None);
+    assert_eq!(mismatched.loss_grad(&pred, &target), None);
+}
+
+#[test]
+fn test_js_divergence() {
+    let p = array![[1.0, 0.0], [0.2, 0.8]];
+    let q = array![[0.0, 1.0], [0.6, 0.4]];
+    let js = JSDivergence::new().with_reduction(Reduction::None);
+    let (a, b) = (js.loss(&p, &q).unwrap(), js.loss(&q, &p).unwrap());
+    // disjoint distributions attain the upper bound of ln(2)
+    assert!((a[[0]] - 2f64.ln()).abs() < TOL);
+    assert!((&a - &b).iter().all(|x| x.abs() < TOL));
+}
+
+#[test]
+fn test_nll_loss() {
+    let logits = array![[2.0, 1.0, 0.1], [0.5, 2.5, 0.3]];
+    let labels = array![0usize, 2];
+    let weights = array![1.0, 2.0, 3.0];
+    let lse = logits.mapv(f64::exp).sum_axis(Axis(1)).mapv(f64::ln);
+    let log_probs = &logits - &lse.insert_axis(Axis(1));
+    let nll = NLLLoss::new().with_weights(weights.clone());
+    let ce = SparseCrossEntropyWithLogits::new().with_weights(weights);
+    let (a, b) = (
+        nll.loss(&log_probs, &labels).map(scalar).unwrap(),
+        ce.loss(&logits, &labels).map(scalar).unwrap(),
+    );
+    assert!((a - b).abs() < TOL);
+    let exp = -(log_probs[[0, 0]] + 3.0 * log_probs[[1, 2]]) / 4.0;
+    assert!((a - exp).abs() < TOL);
+}
+
+#[test]
+fn test_focal_loss() {
+    let pred = array![[0.9, 0.1], [0.4, 0.6]];
+    let labels = array![0usize, 0];
+    // a gamma of zero recovers the cross-entropy
+    let focal = FocalLoss::new().with_gamma(0.0);
+    let ce = SparseCrossEntropy::<f64>::new();
+    let (a, b) = (
+        focal.loss(&pred, &labels).map(scalar).unwrap(),
+        ce.loss(&pred, &labels).map(scalar).unwrap(),
+    );
+    assert!((a - b).abs() < TOL);
+    // well-classified samples are down-weighted
+    let target = array![[1.0, 0.0], [1.0, 0.0]];
+    let res = FocalLoss::new().losses(&pred, &target).unwrap();
+    assert!((res[0] + 0.01 * 0.9f64.ln()).abs() < TOL);
+    assert!((res[1] + 0.36 * 0.4f64.ln()).abs() < TOL);
+}
+
+#[test]
+fn test_hinge() {
+    let scores = array![[0.5], [-2.0], [2.0]];
+    let target = array![[1.0], [0.0], [-1.0]];
+    let hinge = Hinge::new().with_reduction(Reduction::None);
+    assert_eq!(
+        hinge.loss(&scores, &target),
+        Some(array![0.5, 0.0, 3.0].into_dyn())
+    );
+    let sq = SquaredHinge::new().with_reduction(Reduction::Sum);
+    assert_eq!(sq.loss(&scores, &target).map(scalar), Some(9.25));
+    // multi-class hinge penalizes each class within the margin of the target
+    let scores = array![[3.0, 2.5, 0.0], [1.0, 0.0, 1.5]];
+    let labels = array![0usize, 1];
+    let res = Hinge::new()
+        .with_reduction(Reduction::None)
+        .loss(&scores, &labels);
+    assert_eq!(res, Some(array![0.5, 4.5].into_dyn()));
+    let weighted = SquaredHinge::new()
+        .with_weights(array![1.0, 3.0, 1.0])
+        .loss(&scores, &labels);
+    assert!((weighted.map(scalar).unwrap() - (0.25 + 3.0 * 10.25) / 4.0).abs() < TOL);
+    // labels beyond the classes, or weights not matching them, yield nothing
+    let hinge = Hinge::new();
+    assert_eq!(hinge.loss(&scores, &array![0usize, 3]), None);
+    assert_eq!(hinge.loss_grad(&scores, &array![0usize, 3]), None);
+    let mismatched = SquaredHinge::new().with_weights(array![1.0, 3.0]);
+    assert_eq!(mismatched.loss(&scores, &labels), None);
+    assert_eq!(mismatched.loss_grad(&scores, &labels), None);
+    assert_eq!(mismatched.loss(&scores, &Array2::ones((2, 3))), None);
+}
+
+/// Approximates the gradient of `f` at `x` using central differences.
+fn numeric_grad<F>(f: F, x: &Array2<f64>) -> Array2<f64>
+where
+    F: Fn(&Array2<f64>) -> f64,
+{
+    let h = 1e-6;
+    let mut grad = Array2::zeros(x.raw_dim());
+    for (idx, g) in grad.indexed_iter_mut() {
+        let (mut a, mut b) = (x.clone(), x.clone());
+        a[idx] += h;
+        b[idx] -= h;
+        *g = (f(&a) - f(&b)) / (2.0 * h);
+    }
+    grad
+}
+
+fn check_grad<L, T>(loss: &L, pred: &Array2<f64>, target: &T)
+where
+    L: LossGrad<Array2<f64>, T, Output = Option<ArrayD<f64>>, Grad = Option<Array2<f64>>>,
+{
+    let exp = numeric_grad(|x| loss.loss(x, target).unwrap().sum(), pred);
+    assert_close(&loss.loss_grad(pred, target).unwrap(), &exp);
+}
+
+fn assert_close(grad: &Array2<f64>, exp: &Array2<f64>) {
+    assert_eq!(grad.shape(), exp.shape());
+    let err = (grad - exp).mapv(f64::abs).fold(0.0, |m: f64, &x| m.max(x));
+    assert!(err < 1e-5, "{grad:?} != {exp:?}");
+}
+
+#[test]
+fn test_loss_grad() {
+    let pred = array![[0.3, 1.2, -0.4], [2.1, -0.7, 0.9]];
+    let target = array![[0.1, 1.0, 0.5], [0.0, 0.2, 0.8]];
+    let mse = MSE.loss_grad(&pred, &target);
+    assert_eq!(mse, (&pred - &target) * (2.0 / 6.0));
+    let mae: Array2<f64> = MAE.loss_grad(&pred, &target);
+    assert!((mae[[0, 0]] - 1.0 / 6.0).abs() < TOL);
+
+    let weights = array![0.5, 2.0];
+    for reduction in [Reduction::Mean, Reduction::Sum, Reduction::None] {
+        check_grad(&Huber::new(0.5).with_reduction(reduction), &pred, &target);
+        check_grad(
+            &SmoothL1::new(0.8)
+                .with_reduction(reduction)
+                .with_weights(weights.clone()),
+            &pred,
+            &target,
+        );
+        check_grad(&LogCosh::new().with_reduction(reduction), &pred, &target);
+        check_grad(
+            &Quantile::new(0.3).with_reduction(reduction),
+            &pred,
+            &target,
+        );
+        check_grad(
+            &MSLE::new().with_reduction(reduction),
+            &pred.mapv(f64::abs),
+            &target,
+        );
+    }
+}
+
+#[test]
+fn test_classification_loss_grad() {
+    let probs = array![[0.7, 0.2, 0.1], [0.3, 0.3, 0.4]];
+    let logits = array![[1.5, -0.3, 0.2], [0.1, 2.0, -1.0]];
+    let soft = array![[0.8, 0.1, 0.1], [0.0, 0.5, 0.5]];
+    let labels = array![0usize, 2];
+    let weights = array![1.0, 2.0, 0.5];
+    for reduction in [Reduction::Mean, Reduction::Sum, Reduction::None] {
+        let bce = BinaryCrossEntropy::new()
+            .with_reduction(reduction)
+            .with_weights(array![0.5, 1.5])
+            .with_label_smoothing(0.1);
+        check_grad(&bce, &probs, &soft);
+        let bce = BinaryCrossEntropyWithLogits::new().with_reduction(reduction);
+        check_grad(&bce, &logits, &soft);
+        let ce = CrossEntropy::new()
+            .with_reduction(reduction)
+            .with_weights(weights.clone());
+        check_grad(&ce, &probs, &soft);
+        let sparse = SparseCrossEntropy::new()
+            .with_reduction(reduction)
+            .with_weights(weights.clone());
+        check_grad(&sparse, &probs, &labels);
+        let ce = CrossEntropyWithLogits::new()
+            .with_reduction(reduction)
+            .with_weights(weights.clone())
+            .with_label_smoothing(0.2);
+        check_grad(&ce, &logits, &soft);
+        let sparse = SparseCrossEntropyWithLogits::new()
+            .with_reduction(reduction)
+            .with_weights(weights.clone())
+            .with_label_smoothing(0.2);
+        check_grad(&sparse, &logits, &labels);
+        let nll = NLLLoss::new()
+            .with_reduction(reduction)
+            .with_weights(weights.clone());
+        check_grad(&nll, &probs.mapv(f64::ln), &labels);
+        let focal = FocalLoss::new()
+            .with_reduction(reduction)
+            .with_weights(weights.clone());
+        check_grad(&focal, &probs, &labels);
+        check_grad(
+            &KLDivergence::new().with_reduction(reduction),
+            &probs,
+            &soft,
+        );
+        check_grad(
+            &JSDivergence::new().with_reduction(reduction),
+            &probs,
+            &soft,
+        );
+        let hinge = Hinge::new().with_reduction(reduction);
+        check_grad(&hinge, &logits, &soft.mapv(|y| y - 0.3));
+        check_grad(&hinge, &logits, &labels);
+        let hinge = SquaredHinge::new()
+            .with_reduction(reduction)
+            .with_weights(weights.clone());
+        check_grad(&hinge, &logits, &soft.mapv(|y| y - 0.3));
+        check_grad(&hinge, &logits, &labels);
+    }
+}
+
+#[test]
+fn test_penalties() {
+    let w: Array2<f64> = array![[0.5, -1.0, 0.0], [2.0, 0.3, -0.7]];
+    assert!((L1::new(0.1).penalty(&w) - 0.45).abs() < TOL);
+    assert!((L2::new(0.2).penalty(&w) - 0.1 * 5.83).abs() < TOL);
+    let net = ElasticNet::new(0.1, 0.2);
+    assert!((net.penalty(&w) - (0.45 + 0.583)).abs() < TOL);
+    assert_eq!(L2::new(0.2).penalty_grad(&w), &w * 0.2);
+    assert_eq!(L1::new(1.0).penalty_grad(&w)[[0, 2]], 0.0);
+    // orthonormal rows are not penalized
+    let ortho = Orthogonal::new(0.5);
+    let q: Array2<f64> = array![[0.6, 0.8, 0.0], [-0.8, 0.6, 0.0]];
+    assert!(ortho.penalty(&q).abs() < TOL);
+    assert_eq!(ortho.penalty(&array![1.0, 2.0]), 0.0);
+
+    fn check<P: Penalty<f64>>(p: &P, w: &Array2<f64>) {
+        let exp = numeric_grad(|x| p.penalty(x), w);
+        let err = (&p.penalty_grad(w) - &exp)
+            .mapv(f64::abs)
+            .fold(0.0, |m: f64, &x| m.max(x));
+        assert!(err < 1e-5);
+    }
+    check(&L2::new(0.3), &w);
+    check(&net, &w);
+    check(&ortho, &w);
+}
@@ -0,0 +1,155 @@
+/*
+    Appellation: metrics <test>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+extern crate concision_core as concision;
+
+use concision::metrics::*;
+use ndarray::prelude::*;
+
+const TOL: f64 = 1e-10;
+
+lazy_static::lazy_static! {
+    // argmax: [0, 1, 2, 1, 0, 2]
+    static ref SCORES: Array2<f64> = array![
+        [0.7, 0.2, 0.1],
+        [0.1, 0.8, 0.1],
+        [0.2, 0.2, 0.6],
+        [0.3, 0.4, 0.3],
+        [0.5, 0.1, 0.4],
+        [0.1, 0.3, 0.6],
+    ];
+    static ref LABELS: Array1<usize> = array![0, 1, 2, 2, 1, 2];
+}
+
+#[test]
+fn test_accuracy() {
+    assert!((accuracy(&*SCORES, &*LABELS).unwrap() - 4.0 / 6.0).abs() < TOL);
+    assert!((top_k_accuracy(&*SCORES, &*LABELS, 2).unwrap() - 5.0 / 6.0).abs() < TOL);
+    assert_eq!(top_k_accuracy(&*SCORES, &*LABELS, 3).unwrap(), 1.0);
+    // streaming over batches matches the functional form
+    let mut acc = Accuracy::new();
+    for (x, y) in SCORES
+        .axis_chunks_iter(Axis(0), 4)
+        .zip(LABELS.axis_chunks_iter(Axis(0), 4))
+    {
+        acc.update(&x, &y).unwrap();
+    }
+    assert!((acc.compute() - 4.0 / 6.0).abs() < TOL);
+    acc.reset();
+    assert!(acc.compute().is_nan());
+}
+
+#[test]
+fn test_invalid_labels() {
+    let labels = array![0usize, 1, 3, 2, 1, 2];
+    assert_eq!(accuracy(&*SCORES, &labels), Err(MetricError::InvalidLabel));
+    assert_eq!(
+        top_k_accuracy(&*SCORES, &labels, 2),
+        Err(MetricError::InvalidLabel)
+    );
+    assert!(confusion_matrix(&*SCORES, &labels).is_err());
+    // a rejected batch leaves the statistics untouched
+    let mut cm = ConfusionMatrix::new(3);
+    assert_eq!(cm.add(3, 0), Err(MetricError::InvalidLabel));
+    assert_eq!(cm.update(&*SCORES, &labels), Err(MetricError::InvalidLabel));
+    assert_eq!(cm.total(), 0);
+    assert_eq!(cm.add(2, 0), Ok(()));
+}
+
+#[test]
+fn test_mismatched_samples() {
+    let (pred, target) = (array![0usize, 1, 2], array![0usize, 1]);
+    let mut acc = Accuracy::new();
+    assert_eq!(acc.update(&pred, &target), Err(MetricError::ShapeMismatch));
+    assert_eq!(
+        acc.update(&*SCORES, &target),
+        Err(MetricError::ShapeMismatch)
+    );
+    assert!(acc.compute().is_nan());
+    let mut cm = ConfusionMatrix::new(3);
+    assert_eq!(cm.update(&pred, &target), Err(MetricError::ShapeMismatch));
+    assert_eq!(cm.update(&target, &pred), Err(MetricError::ShapeMismatch));
+    assert_eq!(cm.total(), 0);
+}
+
+#[test]
+fn test_confusion_matrix() {
+    let cm = confusion_matrix(&*SCORES, &*LABELS).unwrap();
+    let exp = array![[1, 0, 0], [1, 1, 0], [0, 1, 2]];
+    assert_eq!(cm.matrix(), &exp);
+    assert_eq!(cm.support(), array![1, 2, 3]);
+
+    let precision = cm.precision_per_class();
+    let recall = cm.recall_per_class();
+    assert!((&precision - &array![0.5, 0.5, 1.0])
+        .iter()
+        .all(|d| d.abs() < TOL));
+    assert!((&recall - &array![1.0, 0.5, 2.0 / 3.0])
+        .iter()
+        .all(|d| d.abs() < TOL));
+
+    assert!((cm.precision(Average::Macro) - 2.0 / 3.0).abs() < TOL);
+    assert!((cm.recall(Average::Weighted) - 4.0 / 6.0).abs() < TOL);
+    assert!((cm.f1(Average::Micro) - 4.0 / 6.0).abs() < TOL);
+    let f1 = [2.0 / 3.0, 0.5, 0.8];
+    let macro_f1 = f1.iter().sum::<f64>() / 3.0;
+    assert!((f1_score(&*SCORES, &*LABELS, Average::Macro).unwrap() - macro_f1).abs() < TOL);
+    let weighted = (f1[0] + 2.0 * f1[1] + 3.0 * f1[2]) / 6.0;
+    assert!((f1_score(&*SCORES, &*LABELS, Average::Weighted).unwrap() - weighted).abs() < TOL);
+
+    let mut metric = Precision::new(3, Average::Macro);
+    metric.update(&*SCORES, &*LABELS).unwrap();
+    assert!((metric.compute() - 2.0 / 3.0).abs() < TOL);
+}
+
+#[test]
+fn test_ranking() {
+    let scores = array![0.1, 0.4, 0.35, 0.8];
+    let targets = array![0.0, 0.0, 1.0, 1.0];
+    assert!((roc_auc(&scores, &targets) - 0.75).abs() < TOL);
+    assert!((pr_auc(&scores, &targets) - (0.5 + 0.5 * 2.0 / 3.0)).abs() < TOL);
+    // ties are assigned the average rank
+    let scores = array![0.5, 0.5, 0.5, 0.5];
+    assert!((roc_auc(&scores, &targets) - 0.5).abs() < TOL);
+    assert!(roc_auc(&scores, &Array1::<f64>::zeros(4)).is_nan());
+
+    let mut auc = RocAuc::new();
+    auc.update(&array![0.1, 0.4], &array![0.0, 0.0]).unwrap();
+    auc.update(&array![0.35, 0.8], &array![1.0, 1.0]).unwrap();
+    assert!((auc.compute() - 0.75).abs() < TOL);
+}
+
+#[test]
+fn test_log_loss() {
+    let pred = array![0.9, 0.2];
+    let target = array![1.0, 0.0];
+    let exp = -(0.9f64.ln() + 0.8f64.ln()) / 2.0;
+    assert!((log_loss(&pred, &target) - exp).abs() < TOL);
+    // multi-class probabilities against integer labels
+    let mut metric = LogLoss::new();
+    metric
+        .update(&array![[0.9, 0.1], [0.2, 0.8]], &array![0usize, 1])
+        .unwrap();
+    assert!((metric.compute() - exp).abs() < TOL);
+    // probabilities are clipped
+    assert!(log_loss(&array![0.0], &array![1.0]).is_finite());
+}
+
+#[test]
+fn test_regression() {
+    let target = array![3.0, -0.5, 2.0, 7.0];
+    let pred = array![2.5, 0.0, 2.0, 8.0];
+    assert!((r2_score(&pred, &target) - 0.948_608_137_044_967_9).abs() < 1e-12);
+    assert!((explained_variance(&pred, &target) - 0.957_173_447_537_473_2).abs() < 1e-12);
+    let exp = (0.5 / 3.0 + 0.5 / 0.5 + 0.0 + 1.0 / 7.0) / 4.0;
+    assert!((mape(&pred, &target) - exp).abs() < TOL);
+    assert_eq!(r2_score(&target, &target), 1.0);
+
+    let mut r2 = R2Score::new();
+    r2.update(&pred.slice(s![..2]), &target.slice(s![..2]))
+        .unwrap();
+    r2.update(&pred.slice(s![2..]), &target.slice(s![2..]))
+        .unwrap();
+    assert!((r2.compute() - 0.948_608_137_044_967_9).abs() < 1e-12);
+}
@@ -16,3 +16,125 @@ fn test_dropout() {
     assert!(arr.iter().all(|&x| x == 1.0));
     assert!(out.iter().any(|&x| x == 0.0));
 }
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_dropout_rng() {
+    use concision::init::RngContext;
+
+    let arr = Array2::<f64>::ones((16, 16));
+    let model = Dropout::new(0.5);
+    let (mut a, mut b) = (RngContext::new(0), RngContext::new(0));
+    let first = model.apply_with(&arr, &mut a);
+    assert_eq!(first, model.apply_with(&arr, &mut b));
+    // the generator advances with every application
+    assert_ne!(first, model.apply_with(&arr, &mut a));
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_dropout_mode() {
+    use concision::nn::{Mode, Training};
+
+    let arr = Array2::<f64>::ones((32, 32));
+    let mut dropout = Dropout::new(0.75);
+    assert!(dropout.is_training());
+    // the survivors are scaled by 1 / (1 - p)
+    let out = dropout.forward(&arr);
+    assert!(out.iter().all(|&x| x == 0.0 || x == 4.0));
+
+    dropout.eval_mode();
+    assert_eq!(dropout.mode(), Mode::Eval);
+    assert_eq!(dropout.forward(&arr), arr);
+    // layers are only equal when they share both their probability and mode
+    assert_ne!(dropout, Dropout::new(0.75));
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_channel_dropout() {
+    use concision::nn::{ChannelDropout, DropPath, Training};
+
+    let arr = Array4::<f64>::ones((8, 16, 3, 3));
+    let mut dropout = ChannelDropout::new(Axis(1), 0.5);
+    let out = dropout.forward(&arr);
+    // each channel is either dropped or kept as a whole
+    for channel in out.lanes(Axis(2)) {
+        assert!(channel.iter().all(|&x| x == channel[0]));
+    }
+    assert!(out.iter().any(|&x| x == 0.0));
+    assert!(out.iter().all(|&x| x == 0.0 || x == 2.0));
+
+    dropout.eval_mode();
+    assert_eq!(dropout.forward(&arr), arr);
+    // drop path treats every sample as a single channel
+    let out = DropPath::new(0.5).forward(&Array2::<f64>::ones((64, 4)));
+    for row in out.rows() {
+        assert!(row.iter().all(|&x| x == row[0]));
+    }
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_alpha_dropout() {
+    use concision::init::rand_distr::StandardNormal;
+    use concision::init::{Initialize, RngContext};
+    use concision::nn::AlphaDropout;
+
+    let mut rng = RngContext::new(0);
+    let arr = Array1::<f64>::rand_with(100_000, StandardNormal, &mut rng);
+    let out = AlphaDropout::new(0.2).apply_with(&arr, &mut rng);
+    assert!(out.iter().zip(arr.iter()).any(|(a, b)| a != b));
+    // the mean and variance of the inputs are preserved
+    assert!(out.mean().unwrap().abs() < 0.02);
+    assert!((out.var(0.0) - 1.0).abs() < 0.02);
+}
+
+#[test]
+fn test_mask() {
+    use concision::nn::mask::Mask;
+
+    let causal = Mask::causal(3);
+    assert_eq!(
+        causal.get(),
+        &array![
+            [false, true, true],
+            [false, false, true],
+            [false, false, false]
+        ]
+    );
+    let padding = Mask::padding(2, 3);
+    assert_eq!(
+        padding.get(),
+        &array![
+            [false, false, true],
+            [false, false, true],
+            [false, false, true]
+        ]
+    );
+    let batch = Mask::key_padding(&[1, 3], 3);
+    assert_eq!(batch.index_axis(Axis(0), 0), Mask::padding(1, 3).get());
+    assert!(batch.index_axis(Axis(0), 1).iter().all(|&m| !m));
+    let window = Mask::sliding_window(4, 1);
+    assert_eq!(window.row(0), array![false, false, true, true]);
+    // combinations
+    let either = causal.or(&padding);
+    assert_eq!(either.row(0), array![false, true, true]);
+    assert_eq!(either.row(2), array![false, false, true]);
+    assert_eq!(causal.and(&padding).row(0), array![false, false, true]);
+    assert_eq!((!&causal).row(0), array![true, false, false]);
+}
+
+#[test]
+fn test_mask_softmax() {
+    use concision::nn::mask::Mask;
+
+    let mask = Mask::from_arr(array![[false, true, false], [true, true, true]]);
+    let scores: Array2<f64> = array![[1.0, 100.0, 1.0], [1.0, 2.0, 3.0]];
+    let res = mask.softmax_axis(&scores, 1);
+    // masked positions are given exactly zero probability
+    assert_eq!(res.row(0), array![0.5, 0.0, 0.5]);
+    // rows whose every position is masked are zeroed rather than undefined
+    assert_eq!(res.row(1), array![0.0, 0.0, 0.0]);
+    assert!(mask.fill_scores(&scores)[[0, 1]].is_infinite());
+}
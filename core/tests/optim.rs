@@ -0,0 +1,233 @@
+/*
+   Appellation: optim <test>
+   Contrib: FL03 <jo3mccain@icloud.com>
+*/
+extern crate concision_core as cnc;
+
+use approx::assert_abs_diff_eq;
+use cnc::nn::optim::{Optimize, Sgd};
+use ndarray::prelude::*;
+
+#[test]
+fn test_sgd() {
+    let grad = Array1::from_elem(3, 0.5);
+    let mut params = Array1::<f64>::ones(3);
+    let mut opt = Sgd::new(0.1);
+    opt.step(&mut params, &grad).unwrap();
+    assert_abs_diff_eq!(params, Array1::from_elem(3, 0.95), epsilon = 1e-12);
+}
+
+#[test]
+fn test_sgd_momentum() {
+    let grad = Array2::from_elem((2, 2), 0.5);
+    let mut params = Array2::<f64>::ones((2, 2));
+    let mut opt = Sgd::new(0.1).with_momentum(0.9);
+    opt.step(&mut params, &grad).unwrap();
+    opt.step(&mut params, &grad).unwrap();
+    assert_abs_diff_eq!(params, Array2::from_elem((2, 2), 0.855), epsilon = 1e-12);
+
+    let mut params = Array2::<f64>::ones((2, 2));
+    let mut opt = Sgd::new(0.1).with_momentum(0.9).with_nesterov(true);
+    opt.step(&mut params, &grad).unwrap();
+    assert_abs_diff_eq!(params, Array2::from_elem((2, 2), 0.905), epsilon = 1e-12);
+}
+
+#[test]
+fn test_sgd_nesterov_dampening() {
+    use cnc::nn::optim::{Hyperparams, OptimizerConfig, OptimizerError};
+
+    let config = OptimizerConfig::new()
+        .with_momentum(0.9)
+        .with_nesterov(true)
+        .with_dampening(0.1);
+    assert_eq!(
+        Sgd::<f64>::try_from_config(config).err(),
+        Some(OptimizerError::InvalidNesterov)
+    );
+    let mut opt = Sgd::<f64>::new(0.1).with_nesterov(true);
+    assert_eq!(opt.validate(), Err(OptimizerError::InvalidNesterov));
+    // an invalid configuration is rejected before any parameter is updated
+    let mut params = Array1::<f64>::ones(2);
+    let res = opt.step(&mut params, &Array1::ones(2));
+    assert_eq!(res, Err(OptimizerError::InvalidNesterov));
+    assert_eq!(params, Array1::ones(2));
+    assert!(opt.with_momentum(0.9).validate().is_ok());
+}
+
+#[test]
+fn test_sgd_weight_decay() {
+    let grad = Array1::from_elem(2, 0.5);
+    let mut params = Array1::<f64>::ones(2);
+    let mut opt = Sgd::new(0.1).with_weight_decay(0.1);
+    opt.step(&mut params, &grad).unwrap();
+    assert_abs_diff_eq!(params, Array1::from_elem(2, 0.94), epsilon = 1e-12);
+}
+
+#[test]
+fn test_adam_family() {
+    use cnc::nn::optim::{Adam, AdamW, Adamax, AmsGrad, OptimizerConfig};
+
+    let grad = Array1::from_elem(3, 0.5);
+    let config = OptimizerConfig::new().with_learning_rate(0.1);
+    // with bias correction, the first step of each variant moves by the learning rate
+    let mut params = Array1::<f64>::ones(3);
+    Adam::from_config(config).step(&mut params, &grad).unwrap();
+    assert_abs_diff_eq!(params, Array1::from_elem(3, 0.9), epsilon = 1e-6);
+
+    let mut params = Array1::<f64>::ones(3);
+    AmsGrad::from_config(config)
+        .step(&mut params, &grad)
+        .unwrap();
+    assert_abs_diff_eq!(params, Array1::from_elem(3, 0.9), epsilon = 1e-6);
+
+    let mut params = Array1::<f64>::ones(3);
+    Adamax::from_config(config)
+        .step(&mut params, &grad)
+        .unwrap();
+    assert_abs_diff_eq!(params, Array1::from_elem(3, 0.9), epsilon = 1e-6);
+
+    let mut params = Array1::<f64>::ones(3);
+    AdamW::from_config(config.with_weight_decay(0.1))
+        .step(&mut params, &grad)
+        .unwrap();
+    assert_abs_diff_eq!(params, Array1::from_elem(3, 0.89), epsilon = 1e-6);
+}
+
+#[test]
+fn test_amsgrad() {
+    use cnc::nn::optim::{Adam, AmsGrad, OptimizerConfig};
+
+    let config = OptimizerConfig::new().with_betas(0.9, 0.5);
+    let (mut a, mut b) = (Array1::<f64>::ones(2), Array1::<f64>::ones(2));
+    let (mut adam, mut ams) = (Adam::from_config(config), AmsGrad::from_config(config));
+    for g in [1.0, 0.1, 0.1] {
+        let grad = Array1::from_elem(2, g);
+        adam.step(&mut a, &grad).unwrap();
+        ams.step(&mut b, &grad).unwrap();
+    }
+    // the maximum of the second moment shrinks the later steps
+    assert!(b.iter().zip(a.iter()).all(|(b, a)| b > a));
+}
+
+#[test]
+fn test_rmsprop() {
+    use cnc::nn::optim::{OptimizerConfig, RmsProp};
+
+    let grad = Array3::from_elem((2, 2, 2), 0.5);
+    let mut params = Array3::<f64>::ones((2, 2, 2));
+    RmsProp::new(0.01).step(&mut params, &grad).unwrap();
+    assert_abs_diff_eq!(params, Array3::from_elem((2, 2, 2), 0.9), epsilon = 1e-6);
+    // the centered variant subtracts the squared mean of the gradient from its second moment
+    let config = OptimizerConfig::new()
+        .with_learning_rate(0.01)
+        .with_centered(true)
+        .with_momentum(0.9);
+    let mut opt = RmsProp::from_config(config);
+    let mut params = Array3::<f64>::ones((2, 2, 2));
+    opt.step(&mut params, &grad).unwrap();
+    opt.step(&mut params, &grad).unwrap();
+    assert!(params.iter().all(|&p| p < 0.9));
+}
+
+#[test]
+fn test_adagrad() {
+    use cnc::nn::optim::{Adadelta, Adagrad, Hyperparams, OptimizerConfig};
+
+    let grad = Array1::from_elem(3, 0.5);
+    let mut params = Array2::<f64>::ones((2, 3));
+    let mut row = params.row_mut(0);
+    Adagrad::new(0.1).step(&mut row, &grad).unwrap();
+    assert_abs_diff_eq!(params.row(0), Array1::from_elem(3, 0.9), epsilon = 1e-6);
+    assert_eq!(params.row(1), Array1::ones(3));
+
+    let config = OptimizerConfig::new()
+        .with_learning_rate(0.1)
+        .with_initial_accumulator_value(0.75);
+    let mut params = Array1::<f64>::ones(3);
+    Adagrad::from_config(config)
+        .step(&mut params, &grad)
+        .unwrap();
+    assert_abs_diff_eq!(params, Array1::from_elem(3, 0.95), epsilon = 1e-6);
+
+    assert_eq!(Adadelta::<f64>::default().config().learning_rate, 1.0);
+    let config = OptimizerConfig::new()
+        .with_learning_rate(1.0)
+        .with_eps(1e-6);
+    let mut params = Array1::<f64>::ones(3);
+    Adadelta::from_config(config)
+        .step(&mut params, &grad)
+        .unwrap();
+    let delta = (1e-6f64).sqrt() / (0.025f64 + 1e-6).sqrt() * 0.5;
+    assert_abs_diff_eq!(params, Array1::from_elem(3, 1.0 - delta), epsilon = 1e-9);
+}
+
+#[test]
+fn test_schedules() {
+    use cnc::nn::optim::scheduler::*;
+
+    assert_abs_diff_eq!(StepDecay::new(2, 0.5).lr(1.0, 5), 0.25);
+    assert_abs_diff_eq!(MultiStep::new([2, 4], 0.1).lr(1.0, 3), 0.1);
+    assert_abs_diff_eq!(Exponential::new(0.5).lr(1.0, 3), 0.125);
+    assert_abs_diff_eq!(Polynomial::new(10, 1.0).lr(1.0, 5), 0.5);
+
+    let cosine = CosineAnnealing::new(10, 0.0);
+    assert_abs_diff_eq!(cosine.lr(1.0, 5), 0.5, epsilon = 1e-12);
+    assert_abs_diff_eq!(cosine.lr(1.0, 15), 0.0, epsilon = 1e-12);
+    // restarts double the length of each period
+    let sgdr = cosine.with_restarts(2);
+    assert_abs_diff_eq!(sgdr.lr(1.0, 10), 1.0, epsilon = 1e-12);
+    assert_abs_diff_eq!(sgdr.lr(1.0, 20), 0.5, epsilon = 1e-12);
+
+    let one_cycle = OneCycle::new(1.0, 100);
+    assert_abs_diff_eq!(one_cycle.lr(0.0, 0), 0.04, epsilon = 1e-12);
+    assert_abs_diff_eq!(one_cycle.lr(0.0, 30), 1.0, epsilon = 1e-12);
+    assert_abs_diff_eq!(one_cycle.lr(0.0, 100), 4e-6, epsilon = 1e-12);
+
+    let warmup = LinearWarmup::new(4).then(4, CosineAnnealing::new(10, 0.0));
+    assert_abs_diff_eq!(warmup.lr(1.0, 2), 0.5);
+    assert_abs_diff_eq!(warmup.lr(1.0, 4), 1.0);
+    assert_abs_diff_eq!(warmup.lr(1.0, 9), 0.5, epsilon = 1e-12);
+}
+
+#[test]
+fn test_scheduler() {
+    use cnc::nn::optim::scheduler::*;
+    use cnc::nn::optim::Hyperparams;
+
+    let mut opt = Sgd::<f64>::new(1.0);
+    let mut scheduler = LrScheduler::from_optimizer(&opt, StepDecay::new(1, 0.5));
+    scheduler.step(&mut opt, None);
+    scheduler.step(&mut opt, None);
+    assert_eq!(opt.learning_rate(), 0.25);
+
+    let mut plateau = ReduceOnPlateau::new(Monitor::Min, 0.5, 1);
+    for metric in [1.0, 0.5, 0.6, 0.6] {
+        plateau.step(&mut opt, Some(metric));
+    }
+    assert_eq!(opt.learning_rate(), 0.125);
+}
+
+#[test]
+fn test_regularized() {
+    use cnc::func::loss::L2;
+    use cnc::nn::optim::{Constrained, MaxNorm, Regularized};
+
+    // an L2 penalty behaves as coupled weight decay
+    let mut opt = Regularized::new(Sgd::new(0.1), L2::new(0.5));
+    let mut weights = Array2::<f64>::ones((2, 2));
+    opt.step(&mut weights, &Array2::from_elem((2, 2), 0.5))
+        .unwrap();
+    assert_abs_diff_eq!(weights, Array2::from_elem((2, 2), 0.9), epsilon = 1e-12);
+    // the bias is exempt unless included
+    let mut bias = Array1::<f64>::ones(2);
+    opt.step(&mut bias, &Array1::zeros(2)).unwrap();
+    assert_eq!(bias, Array1::ones(2));
+    let mut opt = opt.with_include_bias(true);
+    opt.step(&mut bias, &Array1::zeros(2)).unwrap();
+    assert_abs_diff_eq!(bias, Array1::from_elem(2, 0.95), epsilon = 1e-12);
+
+    let mut opt = Constrained::new(Sgd::new(1.0), MaxNorm::new(1.0));
+    let mut weights = array![[3.0, 4.0], [0.3, 0.4]];
+    opt.step(&mut weights, &Array2::zeros((2, 2))).unwrap();
+    assert_abs_diff_eq!(weights, array![[0.6, 0.8], [0.3, 0.4]], epsilon = 1e-12);
+}
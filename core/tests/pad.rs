@@ -0,0 +1,112 @@
+/*
+    Appellation: pad <test>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+extern crate concision_core as concision;
+
+use concision::ops::{pad, Pad, PadError, PadMode, Padding};
+use ndarray::prelude::*;
+
+#[test]
+fn test_pad_modes() {
+    let arr = array![1.0, 2.0, 3.0, 4.0, 5.0];
+    let widths = [[2, 3]];
+    let cases = [
+        (PadMode::Constant(0.0), [0.0, 0.0, 0.0, 0.0, 0.0]),
+        (PadMode::Edge, [1.0, 1.0, 5.0, 5.0, 5.0]),
+        (PadMode::Maximum, [5.0, 5.0, 5.0, 5.0, 5.0]),
+        (PadMode::Mean, [3.0, 3.0, 3.0, 3.0, 3.0]),
+        (PadMode::Median, [3.0, 3.0, 3.0, 3.0, 3.0]),
+        (PadMode::Minimum, [1.0, 1.0, 1.0, 1.0, 1.0]),
+        (PadMode::Reflect, [3.0, 2.0, 4.0, 3.0, 2.0]),
+        (PadMode::Symmetric, [2.0, 1.0, 5.0, 4.0, 3.0]),
+        (PadMode::Wrap, [4.0, 5.0, 1.0, 2.0, 3.0]),
+    ];
+    for (mode, [a, b, c, d, e]) in cases {
+        let exp = array![a, b, 1.0, 2.0, 3.0, 4.0, 5.0, c, d, e];
+        assert_eq!(arr.pad(mode, &widths).unwrap(), exp, "{mode}");
+    }
+    let arr = array![1.0, 2.0, 2.0, 3.0];
+    let res = arr.pad(PadMode::Mode, &[[1, 1]]).unwrap();
+    assert_eq!(res, array![2.0, 1.0, 2.0, 2.0, 3.0, 2.0]);
+    // ties are resolved in favour of the value occurring first
+    let arr = array![3.0, 1.0, 1.0, 3.0, 2.0];
+    let res = arr.pad(PadMode::Mode, &[[1, 0]]).unwrap();
+    assert_eq!(res[0], 3.0);
+    // widths exceeding the length of the axis repeat the pattern
+    let res = pad(&array![1.0, 2.0, 3.0], &[[4, 0]], PadMode::Reflect).unwrap();
+    assert_eq!(res, array![1.0, 2.0, 3.0, 2.0, 1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn test_pad_nd() {
+    let arr = array![[1.0, 2.0], [3.0, 4.0]];
+    let res = arr.pad(PadMode::Edge, &[[1, 1]]).unwrap();
+    let exp = array![
+        [1.0, 1.0, 2.0, 2.0],
+        [1.0, 1.0, 2.0, 2.0],
+        [3.0, 3.0, 4.0, 4.0],
+        [3.0, 3.0, 4.0, 4.0]
+    ];
+    assert_eq!(res, exp);
+    // each axis has its own widths
+    let res = arr.pad(PadMode::Wrap, &[[0, 1], [2, 0]]).unwrap();
+    let exp = array![
+        [1.0, 2.0, 1.0, 2.0],
+        [3.0, 4.0, 3.0, 4.0],
+        [1.0, 2.0, 1.0, 2.0]
+    ];
+    assert_eq!(res, exp);
+    assert_eq!(res.unpad(&[[0, 1], [2, 0]]).unwrap(), arr);
+
+    let arr = Array3::<f64>::ones((2, 3, 4));
+    let res = arr.pad(0.0.into(), &[[0, 0], [1, 1], [2, 2]]).unwrap();
+    assert_eq!(res.dim(), (2, 5, 8));
+    assert_eq!(res.sum(), arr.sum());
+}
+
+#[test]
+fn test_pad_errors() {
+    let arr = array![[1.0, 2.0], [3.0, 4.0]];
+    assert_eq!(
+        arr.pad(PadMode::Edge, &[[1, 1], [1, 1], [1, 1]]),
+        Err(PadError::InconsistentDimensions)
+    );
+    assert_eq!(arr.unpad(&[[1, 2]]), Err(PadError::OutOfBounds));
+    let empty = Array1::<f64>::zeros(0);
+    assert_eq!(
+        empty.pad(PadMode::Reflect, &[[1, 1]]),
+        Err(PadError::EmptyAxis)
+    );
+    assert_eq!(empty.pad(1.0.into(), &[[1, 1]]).unwrap(), array![1.0, 1.0]);
+}
+
+#[test]
+fn test_pad_to() {
+    use concision::ops::pad_to;
+
+    let arr = array![[1.0, 2.0], [3.0, 4.0]];
+    let widths = [[1, 0], [0, 2]];
+    let mut out = Array2::<f64>::zeros((3, 4));
+    pad_to(&arr, &widths, PadMode::Symmetric, &mut out).unwrap();
+    assert_eq!(out, arr.pad(PadMode::Symmetric, &widths).unwrap());
+
+    let mut out = Array2::<f64>::zeros((4, 4));
+    assert_eq!(
+        pad_to(&arr, &widths, PadMode::Edge, &mut out),
+        Err(PadError::InconsistentDimensions)
+    );
+    // the output is left untouched on failure
+    assert_eq!(out, Array2::zeros((4, 4)));
+}
+
+#[test]
+fn test_padding() {
+    let padding = Padding::new().with_mode(PadMode::Symmetric).with_padding(1);
+    assert!(padding.action().is_reflecting());
+    let arr = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+    let padded = padding.apply(&arr).unwrap();
+    assert_eq!(padded.dim(), (4, 5));
+    assert_eq!(padded.row(0), array![1.0, 1.0, 2.0, 3.0, 3.0]);
+    assert_eq!(padding.crop(&padded).unwrap(), arr);
+}
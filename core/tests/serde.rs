@@ -0,0 +1,57 @@
+/*
+    Appellation: serde <test>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+extern crate concision_core as concision;
+
+use concision::func::activate::Activation;
+use strum::IntoEnumIterator;
+
+#[test]
+fn test_activation_roundtrip() {
+    for rho in Activation::iter().chain([
+        Activation::Softmax { axis: Some(0) },
+        Activation::LogSoftmax { axis: Some(1) },
+    ]) {
+        let json = serde_json::to_string(&rho).unwrap();
+        assert_eq!(serde_json::from_str::<Activation>(&json).unwrap(), rho);
+    }
+}
+
+#[test]
+fn test_activation_names() {
+    let parse = |s: &str| serde_json::from_str::<Activation>(s);
+    // the (log) softmax may be given by name alone, defaulting to the last axis
+    assert_eq!(
+        parse(r#""softmax""#).unwrap(),
+        Activation::Softmax { axis: None }
+    );
+    assert_eq!(
+        parse(r#""log_softmax""#).unwrap(),
+        Activation::LogSoftmax { axis: None }
+    );
+    assert_eq!(
+        parse(r#"{ "softmax": { "axis": 0 } }"#).unwrap(),
+        Activation::Softmax { axis: Some(0) }
+    );
+    assert_eq!(
+        parse(r#"{ "log_softmax": {} }"#).unwrap(),
+        Activation::LogSoftmax { axis: None }
+    );
+    assert_eq!(parse(r#""leaky_relu""#).unwrap(), Activation::LeakyReLU);
+    assert!(parse(r#""unknown""#).is_err());
+}
+
+#[test]
+fn test_dropout_roundtrip() {
+    use concision::nn::{Dropout, Mode, Training};
+
+    let mut dropout = Dropout::new(0.25);
+    dropout.set_mode(Mode::Eval);
+    let json = serde_json::to_string(&dropout).unwrap();
+    assert_eq!(serde_json::from_str::<Dropout>(&json).unwrap(), dropout);
+    // the mode defaults to training when omitted
+    let parsed = serde_json::from_str::<Dropout>(r#"{ "p": 0.25 }"#).unwrap();
+    assert_eq!(parsed.mode(), Mode::Train);
+    assert_ne!(parsed, dropout);
+}
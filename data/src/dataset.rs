@@ -2,6 +2,11 @@
    Appellation: dataset <mod>
    Contrib: FL03 <jo3mccain@icloud.com>
 */
+#[cfg(all(feature = "alloc", feature = "rand"))]
+use concision::init::rand::{seq::SliceRandom, Rng};
+#[cfg(all(feature = "alloc", feature = "rand"))]
+use nd::{Array, Axis, RemoveAxis};
+use nd::{ArrayBase, ArrayView1, Data, Ix1};
 
 pub mod group;
 
@@ -40,6 +45,74 @@ impl<D, T, W> Dataset<D, T, W> {
     }
 }
 
+#[cfg(all(feature = "alloc", feature = "rand"))]
+impl<A, B, D, E, W> Dataset<Array<A, D>, Array<B, E>, W>
+where
+    A: Clone,
+    B: Clone,
+    D: RemoveAxis,
+    E: RemoveAxis,
+    W: SelectSamples,
+{
+    /// Shuffles the samples, i.e. the rows of the records and targets along with their
+    /// weights, using the given generator.
+    pub fn shuffle<R>(&mut self, rng: &mut R)
+    where
+        R: Rng + ?Sized,
+    {
+        let mut indices = (0..self.records.len_of(Axis(0))).collect::<alloc::vec::Vec<_>>();
+        indices.shuffle(rng);
+        self.records = self.records.select(Axis(0), &indices);
+        self.targets = self.targets.select(Axis(0), &indices);
+        self.weights = self.weights.select_samples(&indices);
+    }
+}
+
+/// [SampleWeights] describes the weights of a [Dataset], scaling the loss of each sample;
+/// datasets weighted by `()` are unweighted.
+pub trait SampleWeights<A> {
+    /// Returns the weight of each sample, if any.
+    fn sample_weights(&self) -> Option<ArrayView1<'_, A>>;
+}
+
+impl<A> SampleWeights<A> for () {
+    fn sample_weights(&self) -> Option<ArrayView1<'_, A>> {
+        None
+    }
+}
+
+impl<A, S> SampleWeights<A> for ArrayBase<S, Ix1>
+where
+    S: Data<Elem = A>,
+{
+    fn sample_weights(&self) -> Option<ArrayView1<'_, A>> {
+        Some(self.view())
+    }
+}
+
+/// [SelectSamples] gathers the given samples, in order, from the weights of a [Dataset];
+/// this keeps the weights aligned with the records and targets when they are reordered.
+#[cfg(feature = "alloc")]
+pub trait SelectSamples {
+    fn select_samples(&self, indices: &[usize]) -> Self;
+}
+
+#[cfg(feature = "alloc")]
+impl SelectSamples for () {
+    fn select_samples(&self, _indices: &[usize]) -> Self {}
+}
+
+#[cfg(feature = "alloc")]
+impl<A, D> SelectSamples for nd::Array<A, D>
+where
+    A: Clone,
+    D: nd::RemoveAxis,
+{
+    fn select_samples(&self, indices: &[usize]) -> Self {
+        self.select(nd::Axis(0), indices)
+    }
+}
+
 impl<D, T, W> core::fmt::Display for Dataset<D, T, W>
 where
     D: core::fmt::Display,
@@ -13,18 +13,26 @@ extern crate alloc;
 extern crate concision_core as concision;
 extern crate ndarray as nd;
 
-pub use self::dataset::Dataset;
+#[cfg(feature = "alloc")]
+pub use self::dataset::SelectSamples;
+pub use self::dataset::{Dataset, SampleWeights};
+#[cfg(feature = "alloc")]
+pub use self::train::{History, Trainer};
 pub use self::traits::prelude::*;
 
 pub mod dataset;
 #[doc(hidden)]
 pub mod preproc;
 pub mod tensor;
+#[cfg(feature = "alloc")]
+pub mod train;
 pub mod traits;
 pub mod types;
 
 pub mod prelude {
     pub use super::dataset::*;
+    #[cfg(feature = "alloc")]
+    pub use super::train::prelude::*;
     pub use super::traits::prelude::*;
     pub use super::types::prelude::*;
 }
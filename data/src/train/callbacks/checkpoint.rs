@@ -0,0 +1,155 @@
+/*
+    Appellation: checkpoint <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::{Callback, History, Logs, Signal};
+use alloc::string::{String, ToString};
+use concision::nn::optim::scheduler::Monitor;
+use num::traits::ToPrimitive;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// [Checkpoint] periodically writes the parameters of the model to disk, keeping both the
+/// most recent (`last`) and the best (`best`) state according to the monitored key.
+///
+/// The encoding is left to the caller, who supplies a function writing the model to the given
+/// path. Should a write fail, training is stopped and the error retained.
+pub struct Checkpoint<F> {
+    pub(crate) best: f64,
+    pub(crate) best_epoch: Option<usize>,
+    pub(crate) dir: PathBuf,
+    pub(crate) error: Option<io::Error>,
+    pub(crate) extension: String,
+    /// The epoch at which the `last` checkpoint was most recently written
+    pub(crate) last_epoch: Option<usize>,
+    pub(crate) mode: Monitor,
+    pub(crate) monitor: String,
+    pub(crate) period: usize,
+    pub(crate) save: F,
+    pub(crate) save_last: bool,
+}
+
+impl<F> Checkpoint<F> {
+    /// Creates a new instance writing into `dir`, monitoring the training loss.
+    pub fn new(dir: impl AsRef<Path>, save: F) -> Self {
+        Self {
+            best: Monitor::Min.worst(),
+            best_epoch: None,
+            dir: dir.as_ref().to_path_buf(),
+            error: None,
+            extension: "ckpt".to_string(),
+            last_epoch: None,
+            mode: Monitor::Min,
+            monitor: "loss".to_string(),
+            period: 1,
+            save,
+            save_last: true,
+        }
+    }
+
+    pub fn with_extension(self, extension: impl ToString) -> Self {
+        Self {
+            extension: extension.to_string(),
+            ..self
+        }
+    }
+    /// Monitors the given key, which is expected to either increase or decrease per `mode`.
+    pub fn with_monitor(self, monitor: impl ToString, mode: Monitor) -> Self {
+        Self {
+            best: mode.worst(),
+            mode,
+            monitor: monitor.to_string(),
+            ..self
+        }
+    }
+    /// Writes the `last` checkpoint every `period` epochs.
+    pub fn with_period(self, period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            ..self
+        }
+    }
+    /// Toggles the `last` checkpoint; when disabled, only improvements are written.
+    pub fn with_save_last(self, save_last: bool) -> Self {
+        Self { save_last, ..self }
+    }
+
+    pub const fn best(&self) -> f64 {
+        self.best
+    }
+
+    pub const fn best_epoch(&self) -> Option<usize> {
+        self.best_epoch
+    }
+
+    pub fn best_path(&self) -> PathBuf {
+        self.dir.join("best").with_extension(&self.extension)
+    }
+
+    pub fn last_path(&self) -> PathBuf {
+        self.dir.join("last").with_extension(&self.extension)
+    }
+    /// Returns the error which interrupted training, if any.
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    fn write<M>(&mut self, model: &M, path: PathBuf) -> Signal
+    where
+        F: FnMut(&M, &Path) -> io::Result<()>,
+    {
+        let res = std::fs::create_dir_all(&self.dir).and_then(|_| (self.save)(model, &path));
+        match res {
+            Ok(_) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(path = %path.display(), "saved checkpoint");
+                Signal::Continue
+            }
+            Err(err) => {
+                #[cfg(feature = "tracing")]
+                tracing::error!(path = %path.display(), error = %err, "failed to save checkpoint");
+                self.error = Some(err);
+                Signal::Stop
+            }
+        }
+    }
+}
+
+impl<M, A, F> Callback<M, A> for Checkpoint<F>
+where
+    A: Copy + ToPrimitive,
+    F: FnMut(&M, &Path) -> io::Result<()>,
+{
+    fn on_train_begin(&mut self, _model: &M) {
+        self.best = self.mode.worst();
+        self.best_epoch = None;
+        self.error = None;
+        self.last_epoch = None;
+    }
+
+    fn on_epoch_end(&mut self, model: &M, logs: &Logs<'_, A>) -> Signal {
+        if let Some(value) = logs.get(&self.monitor) {
+            if self.mode.is_better(value, self.best, 0.0) {
+                self.best = value;
+                self.best_epoch = Some(logs.epoch);
+                if self.write(model, self.best_path()).is_stop() {
+                    return Signal::Stop;
+                }
+            }
+        }
+        // written upon completing every `period` epochs
+        if self.save_last && logs.epoch % self.period + 1 == self.period {
+            self.last_epoch = Some(logs.epoch);
+            return self.write(model, self.last_path());
+        }
+        Signal::Continue
+    }
+
+    fn on_train_end(&mut self, model: &M, history: &History<A>) {
+        // the final state was already written should the last epoch fall on the period
+        let saved = self.last_epoch.map(|epoch| epoch + 1) == Some(history.epochs());
+        if self.save_last && self.error.is_none() && !saved {
+            let _ = self.write(model, self.last_path());
+        }
+    }
+}
@@ -0,0 +1,123 @@
+/*
+    Appellation: early_stopping <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::{Callback, Logs, Signal};
+use alloc::string::{String, ToString};
+use concision::nn::optim::scheduler::Monitor;
+use num::traits::ToPrimitive;
+
+/// [EarlyStopping] stops training once the monitored value has failed to improve by more than
+/// `min_delta` for `patience` consecutive epochs.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct EarlyStopping {
+    pub(crate) best: f64,
+    pub(crate) best_epoch: Option<usize>,
+    pub min_delta: f64,
+    pub mode: Monitor,
+    pub monitor: String,
+    pub patience: usize,
+    pub(crate) stopped_epoch: Option<usize>,
+    pub(crate) wait: usize,
+}
+
+impl EarlyStopping {
+    /// Creates a new instance monitoring the training loss.
+    pub fn new(patience: usize) -> Self {
+        Self {
+            best: Monitor::Min.worst(),
+            best_epoch: None,
+            min_delta: 0.0,
+            mode: Monitor::Min,
+            monitor: "loss".to_string(),
+            patience,
+            stopped_epoch: None,
+            wait: 0,
+        }
+    }
+    /// Monitors the given key, which is expected to either increase or decrease per `mode`.
+    pub fn with_monitor(self, monitor: impl ToString, mode: Monitor) -> Self {
+        Self {
+            best: mode.worst(),
+            mode,
+            monitor: monitor.to_string(),
+            ..self
+        }
+    }
+
+    pub fn with_min_delta(self, min_delta: f64) -> Self {
+        Self {
+            min_delta: min_delta.abs(),
+            ..self
+        }
+    }
+    /// Returns the best value of the monitored key observed so far.
+    pub const fn best(&self) -> f64 {
+        self.best
+    }
+    /// Returns the epoch at which the best value was observed.
+    pub const fn best_epoch(&self) -> Option<usize> {
+        self.best_epoch
+    }
+    /// Returns the epoch at which training was stopped, if any.
+    pub const fn stopped_epoch(&self) -> Option<usize> {
+        self.stopped_epoch
+    }
+    /// Returns the number of consecutive epochs without improvement.
+    pub const fn wait(&self) -> usize {
+        self.wait
+    }
+    /// Records the value observed at the given epoch, returning true if training should stop.
+    pub fn observe(&mut self, epoch: usize, value: f64) -> bool {
+        let improved = match self.mode {
+            Monitor::Max => value - self.min_delta > self.best,
+            Monitor::Min => value + self.min_delta < self.best,
+        };
+        if improved {
+            self.best = value;
+            self.best_epoch = Some(epoch);
+            self.wait = 0;
+            return false;
+        }
+        self.wait += 1;
+        if self.wait >= self.patience {
+            self.stopped_epoch = Some(epoch);
+            return true;
+        }
+        false
+    }
+}
+
+impl<M, A> Callback<M, A> for EarlyStopping
+where
+    A: Copy + ToPrimitive,
+{
+    fn on_train_begin(&mut self, _model: &M) {
+        self.best = self.mode.worst();
+        self.best_epoch = None;
+        self.stopped_epoch = None;
+        self.wait = 0;
+    }
+
+    fn on_epoch_end(&mut self, _model: &M, logs: &Logs<'_, A>) -> Signal {
+        let Some(value) = logs.get(&self.monitor) else {
+            return Signal::Continue;
+        };
+        if self.observe(logs.epoch, value) {
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                epoch = logs.epoch,
+                monitor = %self.monitor,
+                best = self.best,
+                "early stopping"
+            );
+            return Signal::Stop;
+        }
+        Signal::Continue
+    }
+}
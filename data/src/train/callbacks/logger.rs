@@ -0,0 +1,70 @@
+/*
+    Appellation: logger <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::{Callback, Logs, Signal};
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+/// [ProgressLogger] records the learning rate at the end of every epoch; with the `tracing`
+/// feature enabled, the loss, learning rate, and metrics are emitted as structured events,
+/// optionally for every batch as well.
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct ProgressLogger {
+    pub batches: bool,
+    pub(crate) lr: Vec<f64>,
+}
+
+impl ProgressLogger {
+    pub fn new() -> Self {
+        Self {
+            batches: false,
+            lr: Vec::new(),
+        }
+    }
+    /// Toggles the logging of each individual batch.
+    pub fn with_batches(self, batches: bool) -> Self {
+        Self { batches, ..self }
+    }
+    /// Returns the learning rate recorded at the end of each epoch.
+    pub fn lr(&self) -> &[f64] {
+        &self.lr
+    }
+}
+
+impl<M, A> Callback<M, A> for ProgressLogger
+where
+    A: Debug,
+{
+    fn on_epoch_end(&mut self, _model: &M, logs: &Logs<'_, A>) -> Signal {
+        self.lr.push(logs.lr);
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            epoch = logs.epoch,
+            loss = ?logs.loss,
+            lr = logs.lr,
+            metrics = ?logs.metrics,
+            "epoch complete"
+        );
+        Signal::Continue
+    }
+
+    #[cfg(feature = "tracing")]
+    fn on_batch_end(&mut self, _model: &M, logs: &Logs<'_, A>) -> Signal {
+        if self.batches {
+            tracing::debug!(
+                epoch = logs.epoch,
+                batch = logs.batch,
+                loss = ?logs.loss,
+                lr = logs.lr,
+                "batch complete"
+            );
+        }
+        Signal::Continue
+    }
+}
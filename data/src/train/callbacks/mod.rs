@@ -0,0 +1,113 @@
+/*
+    Appellation: callbacks <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+//! # Callbacks
+//!
+//! Callbacks hook into the training loop of a [Trainer](super::Trainer), which invokes them at
+//! the boundaries of every batch and epoch. Each hook receives the model alongside a snapshot of
+//! the current [Logs] and may request that training stop by returning [Signal::Stop].
+#[cfg(feature = "std")]
+pub use self::checkpoint::Checkpoint;
+pub use self::{early_stopping::EarlyStopping, logger::ProgressLogger, nan::TerminateOnNaN};
+
+#[cfg(feature = "std")]
+pub(crate) mod checkpoint;
+pub(crate) mod early_stopping;
+pub(crate) mod logger;
+pub(crate) mod nan;
+
+pub(crate) mod prelude {
+    #[cfg(feature = "std")]
+    pub use super::checkpoint::Checkpoint;
+    pub use super::early_stopping::EarlyStopping;
+    pub use super::logger::ProgressLogger;
+    pub use super::nan::TerminateOnNaN;
+    pub use super::{Callback, Logs, Signal};
+}
+
+use super::History;
+use alloc::string::String;
+use num::traits::ToPrimitive;
+use strum::{AsRefStr, Display, EnumCount, EnumIs, EnumIter, EnumString, VariantNames};
+
+/// [Signal] is returned by a [Callback] to indicate whether training should proceed.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumCount,
+    EnumIs,
+    EnumIter,
+    EnumString,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    VariantNames,
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum Signal {
+    #[default]
+    Continue,
+    Stop,
+}
+
+/// [Logs] is a snapshot of the training loop passed to each [Callback]; `batch` is only set
+/// for batch-level hooks, in which case `loss` refers to the loss of that batch rather than the
+/// mean loss of the epoch. Metrics are only available at the end of an epoch.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Logs<'a, A = f64> {
+    pub batch: Option<usize>,
+    pub epoch: usize,
+    pub loss: A,
+    pub lr: f64,
+    pub metrics: &'a [(String, A)],
+}
+
+impl<'a, A> Logs<'a, A>
+where
+    A: Copy + ToPrimitive,
+{
+    /// Returns the value of the given key, where `"loss"` refers to the training loss and any
+    /// other key to a registered metric.
+    pub fn get(&self, key: &str) -> Option<f64> {
+        if key == "loss" {
+            return self.loss.to_f64();
+        }
+        self.metrics
+            .iter()
+            .find(|(name, _)| name == key)
+            .and_then(|(_, value)| value.to_f64())
+    }
+}
+
+/// [Callback] describes a hook into the training loop; every method is optional.
+#[allow(unused_variables)]
+pub trait Callback<M, A = f64> {
+    /// Invoked once before the first epoch.
+    fn on_train_begin(&mut self, model: &M) {}
+    /// Invoked once after the final epoch, or as soon as training is stopped.
+    fn on_train_end(&mut self, model: &M, history: &History<A>) {}
+
+    fn on_epoch_begin(&mut self, model: &M, epoch: usize) {}
+
+    fn on_epoch_end(&mut self, model: &M, logs: &Logs<'_, A>) -> Signal {
+        Signal::Continue
+    }
+
+    fn on_batch_begin(&mut self, model: &M, epoch: usize, batch: usize) {}
+
+    fn on_batch_end(&mut self, model: &M, logs: &Logs<'_, A>) -> Signal {
+        Signal::Continue
+    }
+}
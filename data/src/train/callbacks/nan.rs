@@ -0,0 +1,48 @@
+/*
+    Appellation: nan <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::{Callback, Logs, Signal};
+use num::traits::Float;
+
+/// [TerminateOnNaN] stops training as soon as a batch produces a loss that is either NaN or
+/// infinite.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct TerminateOnNaN {
+    pub(crate) terminated: Option<(usize, usize)>,
+}
+
+impl TerminateOnNaN {
+    pub fn new() -> Self {
+        Self { terminated: None }
+    }
+    /// Returns the epoch and batch at which training was terminated, if any.
+    pub const fn terminated(&self) -> Option<(usize, usize)> {
+        self.terminated
+    }
+}
+
+impl<M, A> Callback<M, A> for TerminateOnNaN
+where
+    A: Float,
+{
+    fn on_train_begin(&mut self, _model: &M) {
+        self.terminated = None;
+    }
+
+    fn on_batch_end(&mut self, _model: &M, logs: &Logs<'_, A>) -> Signal {
+        if logs.loss.is_finite() {
+            return Signal::Continue;
+        }
+        let batch = logs.batch.unwrap_or_default();
+        #[cfg(feature = "tracing")]
+        tracing::warn!(epoch = logs.epoch, batch, "non-finite loss; terminating");
+        self.terminated = Some((logs.epoch, batch));
+        Signal::Stop
+    }
+}
@@ -0,0 +1,57 @@
+/*
+    Appellation: error <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use concision::error::PredictError;
+use concision::nn::optim::OptimizerError;
+use strum::{AsRefStr, Display, EnumCount, EnumIs, VariantNames};
+
+/// [TrainError] enumerates the ways in which a training run may fail.
+#[derive(
+    AsRefStr,
+    Clone,
+    Copy,
+    Debug,
+    Display,
+    EnumCount,
+    EnumIs,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    VariantNames,
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum TrainError {
+    /// The dataset contains no records.
+    EmptyDataset,
+    /// The trainer was asked to train before being compiled with a dataset.
+    NotCompiled,
+    /// The sample weights differ in number from the records, or the weights of the loss do
+    /// not match the targets.
+    InvalidWeights,
+    /// The hyperparameters of the optimizer are invalid.
+    Optimizer(OptimizerError),
+    Predict(PredictError),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TrainError {}
+
+impl From<PredictError> for TrainError {
+    fn from(err: PredictError) -> Self {
+        TrainError::Predict(err)
+    }
+}
+
+impl From<OptimizerError> for TrainError {
+    fn from(err: OptimizerError) -> Self {
+        TrainError::Optimizer(err)
+    }
+}
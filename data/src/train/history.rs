@@ -0,0 +1,63 @@
+/*
+    Appellation: history <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// [History] records the mean training loss and the value of every registered metric at the
+/// end of each epoch.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub struct History<A = f64> {
+    pub(crate) loss: Vec<A>,
+    pub(crate) metrics: BTreeMap<String, Vec<A>>,
+}
+
+impl<A> History<A> {
+    pub fn new() -> Self {
+        Self {
+            loss: Vec::new(),
+            metrics: BTreeMap::new(),
+        }
+    }
+    /// Returns the number of epochs recorded.
+    pub fn epochs(&self) -> usize {
+        self.loss.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.loss.is_empty()
+    }
+    /// Returns the mean training loss of each epoch.
+    pub fn loss(&self) -> &[A] {
+        &self.loss
+    }
+    /// Returns the value of the named metric at the end of each epoch.
+    pub fn metric(&self, name: &str) -> Option<&[A]> {
+        self.metrics.get(name).map(Vec::as_slice)
+    }
+
+    pub const fn metrics(&self) -> &BTreeMap<String, Vec<A>> {
+        &self.metrics
+    }
+    /// Returns the mean training loss of the most recent epoch.
+    pub fn last_loss(&self) -> Option<&A> {
+        self.loss.last()
+    }
+    /// Records the loss and metrics of a completed epoch.
+    pub fn push<I>(&mut self, loss: A, metrics: I)
+    where
+        I: IntoIterator<Item = (String, A)>,
+    {
+        self.loss.push(loss);
+        for (name, value) in metrics {
+            self.metrics.entry(name).or_default().push(value);
+        }
+    }
+}
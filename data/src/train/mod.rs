@@ -0,0 +1,28 @@
+/*
+    Appellation: train <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+//! # Training
+//!
+//! This module provides a generic [Trainer] implementing the [Compile](concision::Compile) and
+//! [Train](concision::Train) traits. Once compiled with a [Dataset](crate::Dataset), the
+//! trainer iterates over the records in mini-batches, recording the forward pass of the model
+//! and the loss onto a [Tape](concision::autodiff::Tape) before handing the gradients to the
+//! optimizer. The mean loss of each epoch, along with any registered metrics, is collected
+//! into a [History]. [Callbacks](callbacks::Callback) may be registered to observe, checkpoint,
+//! or stop the training loop.
+pub use self::callbacks::{Callback, Logs, Signal};
+pub use self::{error::TrainError, history::History, trainer::Trainer};
+
+pub mod callbacks;
+
+pub(crate) mod error;
+pub(crate) mod history;
+pub(crate) mod trainer;
+
+pub(crate) mod prelude {
+    pub use super::callbacks::prelude::*;
+    pub use super::error::TrainError;
+    pub use super::history::History;
+    pub use super::trainer::Trainer;
+}
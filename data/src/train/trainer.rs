@@ -0,0 +1,369 @@
+/*
+    Appellation: trainer <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use super::{Callback, History, Logs, Signal, TrainError};
+use crate::dataset::{Dataset, SampleWeights, SelectSamples};
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use concision::autodiff::{Tape, Trace, TraceLoss, Var};
+#[cfg(feature = "rand")]
+use concision::init::RngContext;
+use concision::nn::optim::{Optimizable, Optimize};
+use concision::nn::Training;
+use concision::{Backward, Compile, Predict, Train};
+use nd::prelude::*;
+use nd::ScalarOperand;
+use num::traits::Float;
+
+/// A named metric evaluated on the predictions and targets of the dataset.
+pub(crate) type MetricFn<A> = Box<dyn Fn(&Array2<A>, &Array2<A>) -> A>;
+
+/// [Trainer] fits a model to a [Dataset] using an optimizer and a loss function.
+///
+/// The model records its forward pass via [Trace], the loss is recorded via [TraceLoss], and
+/// the resulting gradients are applied by an optimizer implementing [Optimize]. Metrics are
+/// evaluated on the predictions of the entire dataset at the end of every epoch, made with the
+/// model temporarily in evaluation mode (see [Training]), after which the registered
+/// [callbacks](Callback) are invoked. Should the dataset be weighted, i.e. its weights
+/// implement [SampleWeights], the loss of each sample is scaled by its weight.
+///
+/// Given an [RngContext], through [with_shuffle](Trainer::with_shuffle), the samples are
+/// shuffled at the start of every epoch.
+pub struct Trainer<M, O, L, A = f64, W = ()> {
+    pub(crate) batch_size: usize,
+    pub(crate) callbacks: Vec<Box<dyn Callback<M, A>>>,
+    pub(crate) dataset: Option<Dataset<Array2<A>, Array2<A>, W>>,
+    pub(crate) epochs: usize,
+    pub(crate) history: History<A>,
+    pub(crate) loss: L,
+    pub(crate) metrics: Vec<(String, MetricFn<A>)>,
+    pub(crate) model: M,
+    pub(crate) optimizer: O,
+    #[cfg(feature = "rand")]
+    pub(crate) rng: Option<RngContext>,
+    pub(crate) stopped: bool,
+}
+
+impl<M, O, L, A, W> Trainer<M, O, L, A, W> {
+    pub fn new(model: M, optimizer: O, loss: L) -> Self {
+        Self {
+            batch_size: 32,
+            callbacks: Vec::new(),
+            dataset: None,
+            epochs: 1,
+            history: History::new(),
+            loss,
+            metrics: Vec::new(),
+            model,
+            optimizer,
+            #[cfg(feature = "rand")]
+            rng: None,
+            stopped: false,
+        }
+    }
+
+    pub fn with_batch_size(self, batch_size: usize) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            ..self
+        }
+    }
+
+    /// Registers a callback, invoked in order of registration.
+    pub fn with_callback<C>(mut self, callback: C) -> Self
+    where
+        C: Callback<M, A> + 'static,
+    {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
+    pub fn with_epochs(self, epochs: usize) -> Self {
+        Self { epochs, ..self }
+    }
+    /// Shuffles the samples at the start of every epoch, drawing from the given context.
+    #[cfg(feature = "rand")]
+    pub fn with_shuffle(self, rng: RngContext) -> Self {
+        Self {
+            rng: Some(rng),
+            ..self
+        }
+    }
+    /// Registers a metric, computed from the predictions and targets at the end of each epoch.
+    pub fn with_metric<F>(mut self, name: impl ToString, metric: F) -> Self
+    where
+        F: Fn(&Array2<A>, &Array2<A>) -> A + 'static,
+    {
+        self.metrics.push((name.to_string(), Box::new(metric)));
+        self
+    }
+
+    pub const fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    pub fn dataset(&self) -> Option<&Dataset<Array2<A>, Array2<A>, W>> {
+        self.dataset.as_ref()
+    }
+
+    pub const fn epochs(&self) -> usize {
+        self.epochs
+    }
+
+    pub const fn history(&self) -> &History<A> {
+        &self.history
+    }
+
+    pub const fn loss(&self) -> &L {
+        &self.loss
+    }
+
+    pub const fn model(&self) -> &M {
+        &self.model
+    }
+
+    pub fn model_mut(&mut self) -> &mut M {
+        &mut self.model
+    }
+
+    pub const fn optimizer(&self) -> &O {
+        &self.optimizer
+    }
+
+    pub fn optimizer_mut(&mut self) -> &mut O {
+        &mut self.optimizer
+    }
+    /// Consumes the trainer, returning the model.
+    pub fn into_model(self) -> M {
+        self.model
+    }
+
+    pub fn is_compiled(&self) -> bool {
+        self.dataset.is_some()
+    }
+    /// Returns true if a callback stopped the most recent training run.
+    pub const fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+    /// Invokes `f` on every callback, returning [Signal::Stop] if any of them requested it.
+    fn signal<F>(&mut self, mut f: F) -> Signal
+    where
+        F: FnMut(&mut dyn Callback<M, A>, &M) -> Signal,
+    {
+        let mut signal = Signal::Continue;
+        for callback in self.callbacks.iter_mut() {
+            if f(callback.as_mut(), &self.model).is_stop() {
+                signal = Signal::Stop;
+            }
+        }
+        signal
+    }
+}
+
+impl<M, O, L, A, W, G> Trainer<M, O, L, A, W>
+where
+    A: Float + ScalarOperand,
+    L: for<'a> TraceLoss<A, ArrayView2<'a, A>>,
+    M: for<'t> Trace<'t, A, Var<'t, A>, Output = Var<'t, A>, Grad = G> + Optimizable<A, G>,
+    O: Optimize<A>,
+{
+    /// Performs a single optimization step on the given batch, returning the loss.
+    pub fn step(
+        &mut self,
+        records: ArrayView2<'_, A>,
+        targets: ArrayView2<'_, A>,
+    ) -> Result<A, TrainError> {
+        self.step_weighted(records, targets, None)
+    }
+    /// Performs a single optimization step on the given batch, scaling the loss of each sample
+    /// by the corresponding weight, if any; returns the loss.
+    ///
+    /// Fails with [TrainError::InvalidWeights] if the weights, either those given or those of
+    /// the loss, do not match the batch, or with [TrainError::Optimizer] should the
+    /// hyperparameters of the optimizer be invalid.
+    pub fn step_weighted(
+        &mut self,
+        records: ArrayView2<'_, A>,
+        targets: ArrayView2<'_, A>,
+        weights: Option<ArrayView1<'_, A>>,
+    ) -> Result<A, TrainError> {
+        let tape = Tape::new();
+        let (pred, vars) = self.model.trace(&tape, tape.var(&records));
+        let loss = match weights {
+            Some(weights) => self.loss.trace_weighted(pred, &targets, weights),
+            None => self.loss.trace_loss(pred, &targets),
+        }
+        .ok_or(TrainError::InvalidWeights)?;
+        let grads = self.model.grad(&vars, &loss.backward());
+        self.optimizer.step(&mut self.model, &grads)?;
+        Ok(loss.value().sum())
+    }
+    /// Runs a single epoch over the compiled dataset, returning the mean loss of its batches.
+    ///
+    /// The epoch ends early should a callback request that training stop.
+    pub fn train_epoch(&mut self) -> Result<A, TrainError>
+    where
+        W: SampleWeights<A> + SelectSamples,
+    {
+        #[allow(unused_mut)]
+        let mut dataset = self.dataset.take().ok_or(TrainError::NotCompiled)?;
+        #[cfg(feature = "rand")]
+        if let Some(rng) = self.rng.as_mut() {
+            dataset.shuffle(rng);
+        }
+        let epoch = self.history.epochs();
+        let batches = dataset
+            .records()
+            .axis_chunks_iter(Axis(0), self.batch_size)
+            .zip(dataset.targets().axis_chunks_iter(Axis(0), self.batch_size));
+        let (mut total, mut n) = (A::zero(), 0usize);
+        let mut error = None;
+        for (batch, (records, targets)) in batches.enumerate() {
+            self.signal(|cb, model| {
+                cb.on_batch_begin(model, epoch, batch);
+                Signal::Continue
+            });
+            let start = batch * self.batch_size;
+            let weights = dataset
+                .weights()
+                .sample_weights()
+                .map(|w| w.slice_move(s![start..start + records.nrows()]));
+            let loss = match self.step_weighted(records, targets, weights) {
+                Ok(loss) => loss,
+                Err(err) => {
+                    error = Some(err);
+                    break;
+                }
+            };
+            total = total + loss;
+            n += 1;
+            let logs = Logs {
+                batch: Some(batch),
+                epoch,
+                loss,
+                lr: self.optimizer.learning_rate(),
+                metrics: &[],
+            };
+            if self
+                .signal(|cb, model| cb.on_batch_end(model, &logs))
+                .is_stop()
+            {
+                self.stopped = true;
+                break;
+            }
+        }
+        self.dataset = Some(dataset);
+        match error {
+            Some(err) => Err(err),
+            None => Ok(total / A::from(n).unwrap()),
+        }
+    }
+    /// Runs a single epoch, evaluating the metrics and invoking the callbacks of the epoch.
+    fn fit_epoch(&mut self) -> Result<(), TrainError>
+    where
+        M: Predict<Array2<A>, Output = Array2<A>> + Training,
+        W: SampleWeights<A> + SelectSamples,
+    {
+        let epoch = self.history.epochs();
+        self.signal(|cb, model| {
+            cb.on_epoch_begin(model, epoch);
+            Signal::Continue
+        });
+        let loss = self.train_epoch()?;
+        let mut metrics = Vec::with_capacity(self.metrics.len());
+        if let Some(dataset) = self.dataset.as_ref().filter(|_| !self.metrics.is_empty()) {
+            let mode = self.model.mode();
+            self.model.eval_mode();
+            let pred = self.model.predict(dataset.records());
+            self.model.set_mode(mode);
+            let pred = pred?;
+            for (name, metric) in self.metrics.iter() {
+                metrics.push((name.clone(), metric(&pred, dataset.targets())));
+            }
+        }
+        let logs = Logs {
+            batch: None,
+            epoch,
+            loss,
+            lr: self.optimizer.learning_rate(),
+            metrics: &metrics,
+        };
+        if self
+            .signal(|cb, model| cb.on_epoch_end(model, &logs))
+            .is_stop()
+        {
+            self.stopped = true;
+        }
+        self.history.push(loss, metrics);
+        Ok(())
+    }
+}
+
+/*
+ ************* Implementations *************
+*/
+impl<M, O, L, A, W> Compile for Trainer<M, O, L, A, W>
+where
+    A: Clone,
+    W: Clone,
+{
+    type Dataset = Dataset<Array2<A>, Array2<A>, W>;
+
+    fn compile(&mut self, dataset: &Self::Dataset) {
+        self.dataset = Some(dataset.clone());
+        self.history = History::new();
+    }
+}
+
+impl<M, O, L, A, W, G> Train for Trainer<M, O, L, A, W>
+where
+    A: Float + ScalarOperand,
+    L: for<'a> TraceLoss<A, ArrayView2<'a, A>>,
+    M: for<'t> Trace<'t, A, Var<'t, A>, Output = Var<'t, A>, Grad = G>
+        + Optimizable<A, G>
+        + Predict<Array2<A>, Output = Array2<A>>
+        + Training,
+    O: Optimize<A>,
+    W: Clone + SampleWeights<A> + SelectSamples,
+{
+    type Output = Result<History<A>, TrainError>;
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, level = "debug", name = "train", target = "data")
+    )]
+    fn train(&mut self) -> Self::Output {
+        match self.dataset.as_ref() {
+            None => return Err(TrainError::NotCompiled),
+            Some(dataset) if dataset.records().is_empty() => return Err(TrainError::EmptyDataset),
+            Some(dataset) => {
+                let samples = dataset.records().nrows();
+                if let Some(weights) = dataset.weights().sample_weights() {
+                    if weights.len() != samples {
+                        return Err(TrainError::InvalidWeights);
+                    }
+                }
+            }
+        }
+        self.optimizer.validate()?;
+        self.stopped = false;
+        self.signal(|cb, model| {
+            cb.on_train_begin(model);
+            Signal::Continue
+        });
+        let mut res = Ok(());
+        for _ in 0..self.epochs {
+            res = self.fit_epoch();
+            if res.is_err() || self.stopped {
+                break;
+            }
+        }
+        let history = &self.history;
+        for callback in self.callbacks.iter_mut() {
+            callback.on_train_end(&self.model, history);
+        }
+        res.map(|_| self.history.clone())
+    }
+}
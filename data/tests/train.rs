@@ -0,0 +1,298 @@
+/*
+    Appellation: train <test>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+extern crate concision_core as concision;
+extern crate concision_data as data;
+extern crate concision_linear as linear;
+
+use concision::func::loss::MSE;
+use concision::nn::optim::{Adam, Sgd};
+use concision::prelude::linarr;
+use concision::{Compile, Train};
+use data::train::callbacks::{EarlyStopping, ProgressLogger, TerminateOnNaN};
+use data::train::{Callback, Logs, Signal, TrainError};
+use data::{Dataset, Trainer};
+use linear::{Biased, Linear};
+use ndarray::prelude::*;
+
+const SAMPLES: usize = 20;
+const D_MODEL: usize = 4;
+const OUTPUTS: usize = 2;
+
+fn dataset() -> Dataset<Array2<f64>, Array2<f64>, ()> {
+    let records = linarr::<f64, Ix2>((SAMPLES, D_MODEL)).unwrap() / 100.0;
+    let targets =
+        records.sum_axis(Axis(1)).insert_axis(Axis(1)) * Array2::<f64>::ones((1, OUTPUTS));
+    Dataset::new(records, targets, ())
+}
+
+#[test]
+fn test_trainer() {
+    let model = Linear::<f64, Biased>::zeros((OUTPUTS, D_MODEL));
+    let mut trainer = Trainer::new(model, Sgd::new(0.1).with_momentum(0.9), MSE)
+        .with_batch_size(8)
+        .with_epochs(10)
+        .with_metric("mae", |p: &Array2<f64>, t: &Array2<f64>| {
+            (p - t).mapv(f64::abs).mean().unwrap()
+        });
+    assert_eq!(trainer.train(), Err(TrainError::NotCompiled));
+
+    trainer.compile(&dataset());
+    let history = trainer.train().unwrap();
+    assert_eq!(history.epochs(), 10);
+    assert!(history.loss().last() < history.loss().first());
+    let mae = history.metric("mae").unwrap();
+    assert_eq!(mae.len(), 10);
+    assert!(mae.last() < mae.first());
+    // training again resumes from the current state, extending the history
+    assert_eq!(trainer.train().unwrap().epochs(), 20);
+}
+
+#[test]
+fn test_trainer_step() {
+    let model = Linear::<f64, Biased>::zeros((OUTPUTS, D_MODEL));
+    let mut trainer = Trainer::<_, _, _, f64>::new(model, Adam::new(0.01), MSE);
+    let dataset = dataset();
+    let first = trainer.step(dataset.records().view(), dataset.targets().view());
+    let second = trainer.step(dataset.records().view(), dataset.targets().view());
+    assert!(second.unwrap() < first.unwrap());
+    assert!(trainer.history().is_empty());
+}
+
+#[test]
+fn test_trainer_weights() {
+    let model = Linear::<f64, Biased>::zeros((OUTPUTS, D_MODEL));
+    let Dataset {
+        records, targets, ..
+    } = dataset();
+    // unit weights are equivalent to an unweighted dataset
+    let weighted = Dataset::new(records.clone(), targets.clone(), Array1::ones(SAMPLES));
+    let mut a = Trainer::new(model.clone(), Sgd::new(0.1), MSE).with_batch_size(8);
+    let mut b = Trainer::new(model.clone(), Sgd::new(0.1), MSE).with_batch_size(8);
+    a.compile(&dataset());
+    b.compile(&weighted);
+    assert_eq!(a.train().unwrap().loss(), b.train().unwrap().loss());
+    // a sample of zero weight has no influence on the model
+    let mut weights = Array1::ones(SAMPLES);
+    weights[0] = 0.0;
+    let mut a = Trainer::<_, _, _, f64>::new(model.clone(), Sgd::new(0.1), MSE);
+    let mut b = Trainer::<_, _, _, f64>::new(model.clone(), Sgd::new(0.1), MSE);
+    let rest = s![1.., ..];
+    a.step(records.slice(rest), targets.slice(rest)).unwrap();
+    b.step_weighted(records.view(), targets.view(), Some(weights.view()))
+        .unwrap();
+    assert!((a.model().weights() - b.model().weights())
+        .iter()
+        .all(|d| d.abs() < 1e-12));
+    // weights which do not match the given batch are rejected
+    let step = b.step_weighted(records.view(), targets.view(), Some(weights.slice(s![1..])));
+    assert_eq!(step, Err(TrainError::InvalidWeights));
+
+    let mut trainer = Trainer::new(model, Sgd::new(0.1), MSE);
+    trainer.compile(&Dataset::new(records, targets, Array1::ones(SAMPLES - 1)));
+    assert_eq!(trainer.train(), Err(TrainError::InvalidWeights));
+}
+
+#[test]
+fn test_early_stopping() {
+    let model = Linear::<f64, Biased>::zeros((OUTPUTS, D_MODEL));
+    // the metric never improves, so training stops once the patience is exhausted
+    let mut trainer = Trainer::new(model, Sgd::new(0.1), MSE)
+        .with_epochs(10)
+        .with_metric("constant", |_: &Array2<f64>, _: &Array2<f64>| 1.0)
+        .with_callback(EarlyStopping::new(3).with_monitor("constant", Default::default()));
+    trainer.compile(&dataset());
+    let history = trainer.train().unwrap();
+    assert!(trainer.is_stopped());
+    assert_eq!(history.epochs(), 4);
+
+    let mut es = EarlyStopping::new(2).with_min_delta(0.5);
+    assert!(!es.observe(0, 10.0));
+    assert!(!es.observe(1, 9.8));
+    assert!(es.observe(2, 9.6));
+    assert_eq!(es.best(), 10.0);
+    assert_eq!(es.best_epoch(), Some(0));
+    assert_eq!(es.stopped_epoch(), Some(2));
+}
+
+#[test]
+fn test_terminate_on_nan() {
+    struct Batches(usize);
+
+    impl<M> Callback<M> for Batches {
+        fn on_batch_end(&mut self, _model: &M, _logs: &Logs<'_>) -> Signal {
+            self.0 += 1;
+            Signal::Continue
+        }
+    }
+
+    let model = Linear::<f64, Biased>::zeros((OUTPUTS, D_MODEL));
+    let mut dataset = dataset();
+    dataset.targets[[9, 0]] = f64::NAN;
+    let mut trainer = Trainer::new(model, Sgd::new(0.1), MSE)
+        .with_batch_size(5)
+        .with_epochs(3)
+        .with_callback(TerminateOnNaN::new())
+        .with_callback(Batches(0));
+    trainer.compile(&dataset);
+    let history = trainer.train().unwrap();
+    assert!(trainer.is_stopped());
+    assert_eq!(history.epochs(), 1);
+    assert!(history.loss()[0].is_nan());
+}
+
+#[test]
+fn test_progress_logger() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    // shares the logger with the trainer so its records may be inspected afterwards
+    struct Shared(Rc<RefCell<ProgressLogger>>);
+
+    impl<M> Callback<M> for Shared {
+        fn on_epoch_end(&mut self, model: &M, logs: &Logs<'_>) -> Signal {
+            self.0.borrow_mut().on_epoch_end(model, logs)
+        }
+    }
+
+    let logger = Rc::new(RefCell::new(ProgressLogger::new()));
+    let model = Linear::<f64, Biased>::zeros((OUTPUTS, D_MODEL));
+    let mut trainer = Trainer::new(model, Sgd::new(0.1), MSE)
+        .with_epochs(3)
+        .with_callback(Shared(logger.clone()));
+    trainer.compile(&dataset());
+    trainer.train().unwrap();
+    assert_eq!(logger.borrow().lr(), &[0.1, 0.1, 0.1]);
+}
+
+#[test]
+fn test_checkpoint() {
+    use data::train::callbacks::Checkpoint;
+    use std::path::Path;
+
+    let dir = std::env::temp_dir().join(format!("concision-ckpt-{}", std::process::id()));
+    let save = |model: &Linear<f64, Biased>, path: &Path| {
+        std::fs::write(path, format!("{:?}", model.weights().as_slice().unwrap()))
+    };
+    let model = Linear::<f64, Biased>::zeros((OUTPUTS, D_MODEL));
+    let mut trainer = Trainer::new(model, Sgd::new(0.1), MSE)
+        .with_epochs(3)
+        .with_callback(Checkpoint::new(&dir, save).with_period(2));
+    trainer.compile(&dataset());
+    trainer.train().unwrap();
+    let best = std::fs::read_to_string(dir.join("best.ckpt")).unwrap();
+    let last = std::fs::read_to_string(dir.join("last.ckpt")).unwrap();
+    let weights = format!("{:?}", trainer.model().weights().as_slice().unwrap());
+    assert_eq!(last, weights);
+    // the loss decreases every epoch, so the best checkpoint is the final state
+    assert_eq!(best, weights);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_checkpoint_reset() {
+    use data::train::callbacks::Checkpoint;
+    use std::path::Path;
+
+    let dir = std::env::temp_dir().join(format!("concision-ckpt-reset-{}", std::process::id()));
+    let mut ckpt = Checkpoint::new(&dir, |_: &(), _: &Path| Ok(()));
+    let logs = Logs {
+        batch: None,
+        epoch: 0,
+        loss: 1.0,
+        lr: 0.1,
+        metrics: &[],
+    };
+    Callback::<(), f64>::on_epoch_end(&mut ckpt, &(), &logs);
+    assert_eq!(ckpt.best_epoch(), Some(0));
+    // a new run does not compare against the best state of the previous one
+    Callback::<(), f64>::on_train_begin(&mut ckpt, &());
+    assert_eq!(ckpt.best_epoch(), None);
+    assert!(ckpt.best() > 1.0);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_trainer_metrics_eval() {
+    use concision::nn::Training;
+    use concision::Predict;
+
+    let model = Linear::<f64, Biased>::ones((OUTPUTS, D_MODEL)).with_drop_connect(1.0);
+    let dataset = dataset();
+    let mut trainer = Trainer::new(model, Sgd::new(0.1), MSE)
+        .with_metric("sum", |pred: &Array2<f64>, _: &Array2<f64>| pred.sum());
+    trainer.compile(&dataset);
+    let history = trainer.train().unwrap();
+    // the metrics are computed without dropping any weights, after which the mode is restored
+    assert!(trainer.model().is_training());
+    let mut model = trainer.model().clone();
+    model.eval_mode();
+    let exp = model.predict(&dataset.records).unwrap().sum();
+    assert_eq!(history.metric("sum").unwrap().last(), Some(&exp));
+}
+
+#[test]
+fn test_checkpoint_last_once() {
+    use data::train::callbacks::Checkpoint;
+    use std::cell::Cell;
+    use std::path::Path;
+    use std::rc::Rc;
+
+    let dir = std::env::temp_dir().join(format!("concision-ckpt-last-{}", std::process::id()));
+    let writes = Rc::new(Cell::new(0));
+    let counter = writes.clone();
+    let save = move |_: &Linear<f64, Biased>, path: &Path| {
+        if path.file_stem().is_some_and(|stem| stem == "last") {
+            counter.set(counter.get() + 1);
+        }
+        Ok(())
+    };
+    let model = Linear::<f64, Biased>::zeros((OUTPUTS, D_MODEL));
+    let mut trainer = Trainer::new(model, Sgd::new(0.1), MSE)
+        .with_epochs(2)
+        .with_callback(Checkpoint::new(&dir, save));
+    trainer.compile(&dataset());
+    trainer.train().unwrap();
+    // the final epoch falls on the period, so the end of training writes nothing more
+    assert_eq!(writes.get(), 2);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_shuffle() {
+    use concision::init::RngContext;
+
+    let mut a = dataset();
+    a.shuffle(&mut RngContext::new(7));
+    assert_ne!(a.records(), dataset().records());
+    // the records and targets are permuted together
+    let sums = a.records().sum_axis(Axis(1));
+    assert_eq!(sums, a.targets().column(0));
+    // as are the weights of a weighted dataset
+    let Dataset {
+        records, targets, ..
+    } = dataset();
+    let weights = Array1::from_iter((0..SAMPLES).map(|i| i as f64));
+    let mut b = Dataset::new(records.clone(), targets.clone(), weights);
+    b.shuffle(&mut RngContext::new(7));
+    assert_eq!(b.records(), a.records());
+    for (i, &w) in b.weights().iter().enumerate() {
+        let j = w as usize;
+        assert_eq!(b.records().row(i), records.row(j));
+        assert_eq!(b.targets().row(i), targets.row(j));
+    }
+
+    let train = |seed: u64| {
+        let model = Linear::<f64, Biased>::zeros((OUTPUTS, D_MODEL));
+        let mut trainer = Trainer::new(model, Sgd::new(0.1), MSE)
+            .with_batch_size(4)
+            .with_epochs(3)
+            .with_shuffle(RngContext::new(seed));
+        trainer.compile(&dataset());
+        trainer.train().unwrap().loss().to_vec()
+    };
+    assert_eq!(train(0), train(0));
+}
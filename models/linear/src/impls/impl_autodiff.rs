@@ -0,0 +1,121 @@
+/*
+    Appellation: impl_autodiff <impls>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use crate::{LayerNorm, Linear, LinearParams, ParamMode, ParamsBase};
+use concision::autodiff::{Gradients, Tape, Trace, Var};
+use core::marker::PhantomData;
+use nd::*;
+use num::traits::{Float, FloatConst};
+
+/// The variables of the weights and the bias, if any, of a traced linear layer.
+type LinearVars<'t, A> = (Var<'t, A>, Option<Var<'t, A>>);
+
+impl<A, S, K> ParamsBase<S, Ix2, K>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+{
+    /// Records the forward pass, multiplying the weights by the given mask, if any; the
+    /// masked weights receive no gradient.
+    pub(crate) fn trace_masked<'t>(
+        &self,
+        tape: &'t Tape<A>,
+        input: Var<'t, A>,
+        mask: Option<&Array2<A>>,
+    ) -> (Var<'t, A>, LinearVars<'t, A>) {
+        let weight = tape.var(self.weights());
+        let bias = self.bias.as_ref().map(|b| tape.var(b));
+        let masked = match mask {
+            Some(mask) => weight * tape.var(mask),
+            None => weight,
+        };
+        let mut res = input.dot(&masked.t());
+        if let Some(bias) = bias {
+            res = res + bias;
+        }
+        (res, (weight, bias))
+    }
+}
+
+impl<'t, A, S, K> Trace<'t, A, Var<'t, A>> for ParamsBase<S, Ix2, K>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+{
+    type Output = Var<'t, A>;
+    type Vars = (Var<'t, A>, Option<Var<'t, A>>);
+    type Grad = LinearParams<A, K, Ix2>;
+
+    fn trace(&self, tape: &'t Tape<A>, input: Var<'t, A>) -> (Self::Output, Self::Vars) {
+        self.trace_masked(tape, input, None)
+    }
+
+    fn grad(&self, (weight, bias): &Self::Vars, grads: &Gradients<A>) -> Self::Grad {
+        ParamsBase {
+            bias: bias.as_ref().map(|b| grads.wrt_as(b)),
+            weight: grads.wrt_as(weight),
+            _mode: PhantomData::<K>,
+        }
+    }
+}
+
+impl<'t, A, S, K> Trace<'t, A, Var<'t, A>> for Linear<A, K, Ix2, S>
+where
+    A: Float + FloatConst + ScalarOperand,
+    K: ParamMode,
+    S: Data<Elem = A>,
+{
+    type Output = Var<'t, A>;
+    type Vars = (Var<'t, A>, Option<Var<'t, A>>);
+    type Grad = LinearParams<A, K, Ix2>;
+
+    fn trace(&self, tape: &'t Tape<A>, input: Var<'t, A>) -> (Self::Output, Self::Vars) {
+        let mask = self.connect_mask();
+        let (y, vars) = self.params().trace_masked(tape, input, mask.as_ref());
+        (y.activate(self.config().activation()), vars)
+    }
+
+    fn grad(&self, vars: &Self::Vars, grads: &Gradients<A>) -> Self::Grad {
+        self.params().grad(vars, grads)
+    }
+}
+
+impl<'t, A, K, D> Trace<'t, A, Var<'t, A>> for LayerNorm<A, K, D>
+where
+    A: Float + ScalarOperand,
+    D: RemoveAxis,
+    K: ParamMode,
+{
+    type Output = Var<'t, A>;
+    type Vars = (Var<'t, A>, Option<Var<'t, A>>);
+    type Grad = LinearParams<A, K, D>;
+
+    fn trace(&self, tape: &'t Tape<A>, input: Var<'t, A>) -> (Self::Output, Self::Vars) {
+        let eps = A::from(self.eps()).unwrap();
+        let axis = self.config().axis().map(|axis| axis.index());
+        let mean = |x: Var<'t, A>| match axis {
+            Some(axis) => x.mean_axis(axis),
+            None => x.mean(),
+        };
+        let centered = input - mean(input);
+        let var = mean(centered * centered);
+        let norm = centered / var.add_scalar(eps).sqrt();
+
+        let weight = tape.var(self.params().weights());
+        let bias = self.params().bias.as_ref().map(|b| tape.var(b));
+        let mut res = norm * weight;
+        if let Some(bias) = bias {
+            res = res + bias;
+        }
+        (res, (weight, bias))
+    }
+
+    fn grad(&self, (weight, bias): &Self::Vars, grads: &Gradients<A>) -> Self::Grad {
+        ParamsBase {
+            bias: bias.as_ref().map(|b| grads.wrt_as(b)),
+            weight: grads.wrt_as(weight),
+            _mode: PhantomData::<K>,
+        }
+    }
+}
@@ -7,7 +7,12 @@
 use crate::params::{LinearParams, ParamMode, ParamsBase};
 use crate::{bias_dim, Linear};
 use concision::init::rand::Rng;
-use concision::init::rand_distr::{uniform::SampleUniform, Distribution, StandardNormal};
+use concision::init::rand_distr::{
+    uniform::{SampleUniform, Uniform},
+    Distribution, NormalError, StandardNormal,
+};
+use concision::init::{fans, rand, FanMode, Init};
+use concision::nn::DropConnect;
 use concision::{Initialize, InitializeExt};
 use nd::*;
 use num::Float;
@@ -24,12 +29,70 @@ where
     where
         A: SampleUniform,
         <A as SampleUniform>::Sampler: Clone,
+    {
+        self.uniform_with_rng(&mut rand::thread_rng())
+    }
+    /// Initializes the layer with values drawn uniformly from `u(-dk, dk)`, where `dk` is the
+    /// square root of the reciprocal of the input features, using the given generator.
+    pub fn uniform_with_rng<R>(self, rng: &mut R) -> Linear<A, K, D, OwnedRepr<A>>
+    where
+        A: SampleUniform,
+        R: Rng + ?Sized,
+        <A as SampleUniform>::Sampler: Clone,
     {
         Linear {
             config: self.config,
-            params: self.params.uniform(),
+            params: self.params.uniform_with_rng(rng),
+            mode: self.mode,
+            drop_connect: self.drop_connect,
         }
     }
+    /// Initializes the weights and the bias, if any, according to the given strategies.
+    pub fn init_with(self, weight: Init, bias: Init) -> Result<Linear<A, K, D>, NormalError>
+    where
+        A: SampleUniform + ScalarOperand,
+        <A as SampleUniform>::Sampler: Clone,
+    {
+        self.init_with_rng(weight, bias, &mut rand::thread_rng())
+    }
+    /// Initializes the layer according to the given strategies, drawing from `rng`.
+    pub fn init_with_rng<R>(
+        self,
+        weight: Init,
+        bias: Init,
+        rng: &mut R,
+    ) -> Result<Linear<A, K, D>, NormalError>
+    where
+        A: SampleUniform + ScalarOperand,
+        R: Rng + ?Sized,
+        <A as SampleUniform>::Sampler: Clone,
+    {
+        Ok(Linear {
+            config: self.config,
+            params: self.params.init_with_rng(weight, bias, rng)?,
+            mode: self.mode,
+            drop_connect: self.drop_connect,
+        })
+    }
+}
+
+impl<A, S, D, K> Linear<A, K, D, S>
+where
+    D: RemoveAxis,
+    S: RawData<Elem = A>,
+{
+    /// Masks the weights of the layer with a [DropConnect] layer of the given probability;
+    /// while training, a fresh mask is drawn for every forward pass, leaving the bias as is.
+    pub fn with_drop_connect(self, p: f64) -> Self {
+        Self {
+            drop_connect: Some(DropConnect::new(p).with_mode(self.mode)),
+            ..self
+        }
+    }
+
+    pub fn drop_connect(&self) -> Option<&DropConnect> {
+        self.drop_connect.as_ref()
+    }
 }
 
 impl<A, S, D, K> ParamsBase<S, D, K>
@@ -53,25 +116,42 @@ where
     pub fn uniform(self) -> LinearParams<A, K, D>
     where
         S: DataOwned,
+    {
+        self.uniform_with_rng(&mut rand::thread_rng())
+    }
+    /// Draws the parameters uniformly from `u(-dk, dk)` using the given generator.
+    pub fn uniform_with_rng<R>(self, rng: &mut R) -> LinearParams<A, K, D>
+    where
+        R: Rng + ?Sized,
+        S: DataOwned,
     {
         let dk = self.dk_sqrt();
-        self.uniform_between(-dk, dk)
+        self.uniform_between_with_rng(-dk, dk, rng)
     }
 
     pub fn uniform_between(self, low: A, high: A) -> LinearParams<A, K, D>
     where
         S: DataOwned,
     {
-        let weight = Array::uniform_between(self.raw_dim(), low, high);
+        self.uniform_between_with_rng(low, high, &mut rand::thread_rng())
+    }
+    /// Draws the parameters uniformly from `u(low, high)` using the given generator.
+    pub fn uniform_between_with_rng<R>(self, low: A, high: A, rng: &mut R) -> LinearParams<A, K, D>
+    where
+        R: Rng + ?Sized,
+        S: DataOwned,
+    {
+        let distr = Uniform::new(low, high);
+        let weight = Array::rand_with(self.raw_dim(), distr.clone(), rng);
         let bias = if self.is_biased() && !self.bias.is_some() {
             let b_dim = bias_dim(self.raw_dim());
-            Some(Array::uniform_between(b_dim, low, high))
+            Some(Array::rand_with(b_dim, distr, rng))
         } else if !self.is_biased() && self.bias.is_some() {
             None
         } else {
             self.bias
                 .as_ref()
-                .map(|b| Array::uniform_between(b.raw_dim(), low, high))
+                .map(|b| Array::rand_with(b.raw_dim(), distr, rng))
         };
         LinearParams {
             weight,
@@ -81,6 +161,101 @@ where
     }
 }
 
+impl<A, S, D, K> ParamsBase<S, D, K>
+where
+    A: Clone + Float,
+    D: RemoveAxis,
+    K: ParamMode,
+    S: RawData<Elem = A>,
+{
+    /// Creates a new store with the given weights and a bias, if any, filled with `bias`.
+    fn init_weight(&self, weight: Array<A, D>, bias: A) -> LinearParams<A, K, D> {
+        let bias = K::BIASED.then(|| Array::from_elem(bias_dim(weight.raw_dim()), bias));
+        LinearParams {
+            weight,
+            bias,
+            _mode: core::marker::PhantomData::<K>,
+        }
+    }
+    /// Initializes the weights and the bias, if any, according to the given strategies; the
+    /// bias is generated using the fans of the weights.
+    pub fn init_with(self, weight: Init, bias: Init) -> Result<LinearParams<A, K, D>, NormalError>
+    where
+        A: SampleUniform + ScalarOperand,
+        StandardNormal: Distribution<A>,
+        <A as SampleUniform>::Sampler: Clone,
+    {
+        self.init_with_rng(weight, bias, &mut rand::thread_rng())
+    }
+    /// Initializes the store according to the given strategies, drawing from `rng`.
+    pub fn init_with_rng<R>(
+        self,
+        weight: Init,
+        bias: Init,
+        rng: &mut R,
+    ) -> Result<LinearParams<A, K, D>, NormalError>
+    where
+        A: SampleUniform + ScalarOperand,
+        R: Rng + ?Sized,
+        StandardNormal: Distribution<A>,
+        <A as SampleUniform>::Sampler: Clone,
+    {
+        let dim = self.raw_dim();
+        let fans = fans(dim.slice());
+        let weight = weight.array_with(dim.clone(), fans, rng)?;
+        let bias = match K::BIASED {
+            true => Some(bias.array_with(bias_dim(dim), fans, rng)?),
+            false => None,
+        };
+        Ok(LinearParams {
+            weight,
+            bias,
+            _mode: core::marker::PhantomData::<K>,
+        })
+    }
+    /// Fills both the weights and the bias with the given value.
+    pub fn constant(self, value: A) -> LinearParams<A, K, D> {
+        self.init_weight(Array::constant(self.raw_dim(), value), value)
+    }
+    /// Initializes the weights with ones along their diagonal, zeroing the bias.
+    pub fn identity(self) -> LinearParams<A, K, D> {
+        self.init_weight(Array::identity(self.raw_dim()), A::zero())
+    }
+    /// Initializes the weights according to the normal Kaiming scheme, zeroing the bias.
+    pub fn kaiming_normal(self, mode: FanMode, gain: A) -> LinearParams<A, K, D>
+    where
+        StandardNormal: Distribution<A>,
+    {
+        let weight = Array::kaiming_normal(self.raw_dim(), mode, gain);
+        self.init_weight(weight, A::zero())
+    }
+    /// Initializes the weights according to the uniform Kaiming scheme, zeroing the bias.
+    pub fn kaiming_uniform(self, mode: FanMode, gain: A) -> LinearParams<A, K, D>
+    where
+        A: SampleUniform,
+        <A as SampleUniform>::Sampler: Clone,
+    {
+        let weight = Array::kaiming_uniform(self.raw_dim(), mode, gain);
+        self.init_weight(weight, A::zero())
+    }
+    /// Initializes the weights with a (semi-)orthogonal matrix, zeroing the bias.
+    pub fn orthogonal(self, gain: A) -> LinearParams<A, K, D>
+    where
+        A: ScalarOperand,
+        StandardNormal: Distribution<A>,
+    {
+        self.init_weight(Array::orthogonal(self.raw_dim(), gain), A::zero())
+    }
+    /// Initializes the weights with a sparse matrix, zeroing the bias.
+    pub fn sparse(self, sparsity: f64, std: A) -> Result<LinearParams<A, K, D>, NormalError>
+    where
+        StandardNormal: Distribution<A>,
+    {
+        let weight = Array::sparse(self.raw_dim(), sparsity, std)?;
+        Ok(self.init_weight(weight, A::zero()))
+    }
+}
+
 impl<A, S, D, K> Initialize<A, D> for Linear<A, K, D, S>
 where
     D: RemoveAxis,
@@ -3,8 +3,9 @@
     Contrib: FL03 <jo3mccain@icloud.com>
 */
 use crate::{Config, Linear, ParamMode, ParamsBase};
+use concision::nn::{Mode, Training};
 use core::borrow::{Borrow, BorrowMut};
-use nd::{DataOwned, Ix2, RawData, RawDataClone, RemoveAxis};
+use nd::{DataOwned, Dimension, Ix2, RawData, RawDataClone, RemoveAxis};
 
 impl<A, K, S> Linear<A, K, Ix2, S>
 where
@@ -18,7 +19,13 @@ where
     {
         let config = Config::std(inputs, outputs);
         let params = ParamsBase::new(config.dim());
-        Self { config, params }
+        Self {
+            config,
+            params,
+            mode: Mode::Train,
+            #[cfg(feature = "rand")]
+            drop_connect: None,
+        }
     }
 }
 
@@ -52,6 +59,26 @@ where
     }
 }
 
+/// Switching the mode of a linear layer also switches that of its
+/// [DropConnect](concision::nn::DropConnect) layer, if any.
+impl<A, S, D, K> Training for Linear<A, K, D, S>
+where
+    D: Dimension,
+    S: RawData<Elem = A>,
+{
+    fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+        #[cfg(feature = "rand")]
+        if let Some(layer) = self.drop_connect.as_mut() {
+            layer.set_mode(mode);
+        }
+    }
+}
+
 impl<A, S, D, K> Clone for Linear<A, K, D, S>
 where
     A: Clone,
@@ -64,6 +91,9 @@ where
         Self {
             config: self.config.clone(),
             params: self.params.clone(),
+            mode: self.mode,
+            #[cfg(feature = "rand")]
+            drop_connect: self.drop_connect,
         }
     }
 }
@@ -3,8 +3,14 @@
     Contrib: FL03 <jo3mccain@icloud.com>
 */
 use crate::{Config, Linear, ParamsBase};
-use concision::prelude::{Module, Predict, PredictError};
-use nd::{RawData, RemoveAxis};
+use concision::func::activate::Activation;
+use concision::nn::optim::Optimizable;
+use concision::prelude::{Activate, Module, Predict, PredictError};
+use core::ops::Add;
+use nd::linalg::Dot;
+use nd::ScalarOperand;
+use nd::{ArrayBase, ArrayView, ArrayViewD, ArrayViewMutD, Data, DataMut, RawData, RemoveAxis};
+use num::traits::{Num, NumCast};
 
 impl<A, D, S, K> Module for Linear<A, K, D, S>
 where
@@ -28,10 +34,32 @@ where
     }
 }
 
+impl<A, D, S, T, K> Optimizable<A, ParamsBase<T, D, K>> for Linear<A, K, D, S>
+where
+    D: RemoveAxis,
+    S: DataMut<Elem = A>,
+    T: Data<Elem = A>,
+{
+    fn zip_grad(
+        &mut self,
+        grads: &ParamsBase<T, D, K>,
+        f: &mut dyn FnMut(ArrayViewMutD<'_, A>, ArrayViewD<'_, A>),
+    ) {
+        self.params.zip_grad(grads, f)
+    }
+}
+
+/// Delegates to the parameters before applying the [Activation] configured for the layer;
+/// while training, the weights are first masked by the
+/// [DropConnect](concision::nn::DropConnect) layer, if any.
 impl<U, V, A, S, D, K> Predict<U> for Linear<A, K, D, S>
 where
+    A: Num + NumCast + ScalarOperand,
     D: RemoveAxis,
-    S: RawData<Elem = A>,
+    S: Data<Elem = A>,
+    U: for<'a> Dot<ArrayView<'a, A, D>, Output = V>,
+    V: for<'a> Add<&'a ArrayBase<S, D::Smaller>, Output = V>,
+    Activation: Activate<V, Output = V>,
     ParamsBase<S, D, K>: Predict<U, Output = V>,
 {
     type Output = V;
@@ -43,6 +71,20 @@ where
     fn predict(&self, input: &U) -> Result<Self::Output, PredictError> {
         #[cfg(feature = "tracing")]
         tracing::debug!("Predicting with linear model");
-        self.params().predict(input)
+        let res = match self.connect_mask() {
+            Some(mask) => {
+                let weight = &self.params().weight * &mask;
+                let mut res = input.dot(&weight.t());
+                if let Some(bias) = self.params().bias.as_ref() {
+                    res = res + bias;
+                }
+                res
+            }
+            None => self.params().predict(input)?,
+        };
+        match self.config().activation() {
+            Activation::Linear => Ok(res),
+            rho => Ok(rho.activate(res)),
+        }
     }
 }
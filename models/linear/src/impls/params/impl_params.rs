@@ -3,6 +3,8 @@
     Contrib: FL03 <jo3mccain@icloud.com>
 */
 use crate::params::ParamsBase;
+use concision::func::loss::reg::penalty::Penalty;
+use concision::nn::optim::Optimizable;
 use concision::prelude::{Parameters, Predict, PredictError};
 use core::ops::Add;
 use nd::linalg::Dot;
@@ -22,6 +24,28 @@ where
     {
         f(&self.predict(args).unwrap())
     }
+    /// Computes the given [Penalty] over the weights; the bias is not penalized.
+    pub fn penalty<P>(&self, penalty: &P) -> A
+    where
+        P: Penalty<A>,
+        S: Data,
+    {
+        penalty.penalty(&self.weight)
+    }
+    /// Computes the gradient of the [penalty](ParamsBase::penalty), laid out as a parameter
+    /// store whose bias, if any, is zero.
+    pub fn penalty_grad<P>(&self, penalty: &P) -> ParamsBase<OwnedRepr<A>, D, K>
+    where
+        A: Clone + num::Zero,
+        P: Penalty<A>,
+        S: Data,
+    {
+        ParamsBase {
+            bias: self.bias.as_ref().map(|b| Array::zeros(b.raw_dim())),
+            weight: penalty.penalty_grad(&self.weight),
+            _mode: core::marker::PhantomData::<K>,
+        }
+    }
 }
 
 impl<A, S, D, K> Parameters for ParamsBase<S, D, K>
@@ -32,6 +56,22 @@ where
     type Elem = A;
 }
 
+impl<A, S, T, D, K> Optimizable<A, ParamsBase<T, D, K>> for ParamsBase<S, D, K>
+where
+    D: RemoveAxis,
+    S: DataMut<Elem = A>,
+    T: Data<Elem = A>,
+{
+    fn zip_grad(
+        &mut self,
+        grads: &ParamsBase<T, D, K>,
+        f: &mut dyn FnMut(ArrayViewMutD<'_, A>, ArrayViewD<'_, A>),
+    ) {
+        self.weight.zip_grad(&grads.weight, f);
+        self.bias.zip_grad(&grads.bias, f);
+    }
+}
+
 impl<A, S, D> Clone for ParamsBase<S, D>
 where
     A: Clone,
@@ -41,6 +41,7 @@ pub mod params;
 pub mod traits;
 
 mod impls {
+    pub mod impl_autodiff;
     pub mod impl_rand;
 
     pub mod model {
@@ -2,8 +2,16 @@
     Appellation: model <module>
     Contrib: FL03 <jo3mccain@icloud.com>
 */
+use super::Perceptron;
+use crate::{Config, Linear, ParamMode};
+use concision::func::activate::Activation;
 use concision::{Predict, PredictError};
 use core::marker::PhantomData;
+use nd::{Array2, Ix2, ScalarOperand};
+use num::traits::{Float, FloatConst};
+
+/// A [Perceptron] whose layer is [Linear] and whose activation is selected by its [Config].
+pub type LinearPerceptron<A = f64, K = crate::Biased> = Perceptron<Linear<A, K, Ix2>, Activation>;
 
 // #92: Define the Multi-Layer Perceptron (MLP) model
 /// A multi-layer perceptron (MLP) model.
@@ -19,6 +27,56 @@ where
     _dtype: PhantomData<A>,
 }
 
+impl<A, I, H, O> Mlp<A, I, H, O>
+where
+    I: Predict<A>,
+    H: Predict<I::Output, Output = I::Output>,
+    O: Predict<H::Output>,
+{
+    pub fn new(input: I, hidden: Vec<H>, output: O) -> Self {
+        Self {
+            input,
+            hidden,
+            output,
+            _dtype: PhantomData::<A>,
+        }
+    }
+
+    pub const fn input(&self) -> &I {
+        &self.input
+    }
+
+    pub fn hidden(&self) -> &[H] {
+        &self.hidden
+    }
+
+    pub const fn output(&self) -> &O {
+        &self.output
+    }
+}
+
+impl<A, K> Mlp<Array2<A>, LinearPerceptron<A, K>, LinearPerceptron<A, K>, LinearPerceptron<A, K>>
+where
+    A: Clone + Default + Float + FloatConst + ScalarOperand,
+    K: ParamMode,
+    LinearPerceptron<A, K>: Predict<Array2<A>, Output = Array2<A>>,
+{
+    /// Builds a network from the [Config] of each layer, such that every layer applies the
+    /// [Activation] it was configured with; the parameters are initialized to their default.
+    pub fn from_configs(
+        input: Config<K, Ix2>,
+        hidden: Vec<Config<K, Ix2>>,
+        output: Config<K, Ix2>,
+    ) -> Self {
+        let layer = |config| Perceptron::from(Linear::from_config(config));
+        Self::new(
+            layer(input),
+            hidden.into_iter().map(layer).collect(),
+            layer(output),
+        )
+    }
+}
+
 impl<A, I, H, O> Predict<A> for Mlp<A, I, H, O>
 where
     I: Predict<A>,
@@ -2,8 +2,10 @@
    Appellation: perceptron <module>
    Contrib: FL03 <jo3mccain@icloud.com>
 */
+use crate::{Linear, ParamMode};
+use concision::func::activate::Activation;
 use concision::prelude::{Activate, Module, Predict, PredictError};
-use nd::{ArrayBase, Data, Dimension};
+use nd::{ArrayBase, Data, Dimension, RawData, RemoveAxis};
 
 // #91
 /// Perceptrons are the fundamental building block of multi-layer perceptrons (MLPs).
@@ -21,6 +23,32 @@ where
     pub fn new(module: M, rho: F) -> Self {
         Self { module, rho }
     }
+
+    pub const fn module(&self) -> &M {
+        &self.module
+    }
+
+    pub fn module_mut(&mut self) -> &mut M {
+        &mut self.module
+    }
+
+    pub const fn rho(&self) -> &F {
+        &self.rho
+    }
+}
+
+impl<A, K, D, S> From<Linear<A, K, D, S>> for Perceptron<Linear<A, K, D, S>, Activation>
+where
+    D: RemoveAxis,
+    K: ParamMode,
+    S: RawData<Elem = A>,
+{
+    /// Creates a perceptron applying the [Activation] configured for the layer, which is
+    /// moved out of the layer to avoid applying it twice.
+    fn from(mut module: Linear<A, K, D, S>) -> Self {
+        let rho = core::mem::take(&mut module.config.activation);
+        Self::new(module, rho)
+    }
 }
 
 impl<T, M, F> Activate<T> for Perceptron<M, F>
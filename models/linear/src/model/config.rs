@@ -4,6 +4,7 @@
 */
 use super::layout::{Features, Layout};
 use crate::params::{Biased, Unbiased};
+use concision::func::activate::Activation;
 use core::marker::PhantomData;
 use nd::prelude::*;
 use nd::{IntoDimension, RemoveAxis, ShapeError};
@@ -11,6 +12,8 @@ use nd::{IntoDimension, RemoveAxis, ShapeError};
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Config<K = Biased, D = Ix2> {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub activation: Activation,
     pub layout: Layout<D>,
     pub name: String,
     _biased: PhantomData<K>,
@@ -22,6 +25,7 @@ where
 {
     pub fn new() -> Self {
         Self {
+            activation: Activation::default(),
             layout: Layout::default(),
             name: String::new(),
             _biased: PhantomData::<K>,
@@ -48,6 +52,7 @@ where
 
     pub fn into_biased(self) -> Config<Biased, D> {
         Config {
+            activation: self.activation,
             layout: self.layout,
             name: self.name,
             _biased: PhantomData::<Biased>,
@@ -56,12 +61,18 @@ where
 
     pub fn into_unbiased(self) -> Config<Unbiased, D> {
         Config {
+            activation: self.activation,
             layout: self.layout,
             name: self.name,
             _biased: PhantomData::<Unbiased>,
         }
     }
 
+    /// Sets the [Activation] applied to the output of the layer.
+    pub fn with_activation(self, activation: Activation) -> Self {
+        Self { activation, ..self }
+    }
+
     pub fn with_name(self, name: impl ToString) -> Self {
         Self {
             name: name.to_string(),
@@ -74,6 +85,7 @@ where
         E: Dimension,
     {
         Config {
+            activation: self.activation,
             layout,
             name: self.name,
             _biased: self._biased,
@@ -86,6 +98,7 @@ where
         Sh: ShapeBuilder<Dim = E>,
     {
         Config {
+            activation: self.activation,
             layout: self.layout.with_shape(shape),
             name: self.name,
             _biased: self._biased,
@@ -98,6 +111,7 @@ where
         E: Dimension,
     {
         let tmp = Config {
+            activation: self.activation,
             layout: self.layout.into_dimensionality(dim)?,
             name: self.name,
             _biased: self._biased,
@@ -114,6 +128,10 @@ where
 
         TypeId::of::<K>() == TypeId::of::<Biased>()
     }
+    /// Returns the [Activation] applied to the output of the layer.
+    pub const fn activation(&self) -> Activation {
+        self.activation
+    }
     /// Returns an instance to the [Features] of the [Layout]
     pub fn features(&self) -> Features {
         self.layout().features()
@@ -144,6 +162,7 @@ where
 impl<K> Config<K, Ix2> {
     pub fn std(inputs: usize, outputs: usize) -> Self {
         Self {
+            activation: Activation::default(),
             layout: Layout::new((outputs, inputs).into_dimension()),
             name: String::new(),
             _biased: PhantomData::<K>,
@@ -4,9 +4,11 @@
 */
 use super::{Config, Layout};
 use crate::{Biased, LinearParams, ParamMode, ParamsBase, Unbiased};
+use concision::nn::Mode;
 use concision::prelude::{Predict, Result};
 use nd::prelude::*;
-use nd::{DataOwned, OwnedRepr, RawData, RemoveAxis};
+use nd::{DataOwned, OwnedRepr, RawData, RemoveAxis, ScalarOperand};
+use num::traits::{Num, NumCast};
 
 /// An implementation of a linear model.
 ///
@@ -20,6 +22,9 @@ where
 {
     pub(crate) config: Config<K, D>,
     pub(crate) params: ParamsBase<S, D, K>,
+    pub(crate) mode: Mode,
+    #[cfg(feature = "rand")]
+    pub(crate) drop_connect: Option<concision::nn::DropConnect>,
 }
 
 impl<A, K> Linear<A, K, Ix2, OwnedRepr<A>>
@@ -32,7 +37,13 @@ where
     {
         let config = Config::<K, Ix2>::new().with_shape((inputs, outputs));
         let params = ParamsBase::new(config.features());
-        Linear { config, params }
+        Linear {
+            config,
+            params,
+            mode: Mode::Train,
+            #[cfg(feature = "rand")]
+            drop_connect: None,
+        }
     }
 }
 
@@ -53,7 +64,13 @@ where
         S: DataOwned,
     {
         let params = ParamsBase::new(config.dim());
-        Self { config, params }
+        Self {
+            config,
+            params,
+            mode: Mode::Train,
+            #[cfg(feature = "rand")]
+            drop_connect: None,
+        }
     }
 
     pub fn from_layout(layout: Layout<D>) -> Self
@@ -64,12 +81,24 @@ where
     {
         let config = Config::<K, D>::new().with_layout(layout);
         let params = ParamsBase::new(config.dim());
-        Self { config, params }
+        Self {
+            config,
+            params,
+            mode: Mode::Train,
+            #[cfg(feature = "rand")]
+            drop_connect: None,
+        }
     }
 
     pub fn from_params(params: ParamsBase<S, D, K>) -> Self {
         let config = Config::<K, D>::new().with_shape(params.raw_dim());
-        Self { config, params }
+        Self {
+            config,
+            params,
+            mode: Mode::Train,
+            #[cfg(feature = "rand")]
+            drop_connect: None,
+        }
     }
 
     /// Applies an activcation function onto the prediction of the model.
@@ -110,6 +139,9 @@ where
         Linear {
             config: self.config.into_biased(),
             params: self.params.into_biased(),
+            mode: self.mode,
+            #[cfg(feature = "rand")]
+            drop_connect: self.drop_connect,
         }
     }
 
@@ -122,6 +154,9 @@ where
         Linear {
             config: self.config.into_unbiased(),
             params: self.params.into_unbiased(),
+            mode: self.mode,
+            #[cfg(feature = "rand")]
+            drop_connect: self.drop_connect,
         }
     }
 
@@ -137,7 +172,13 @@ where
         E: RemoveAxis,
     {
         let config = self.config.into_dimensionality(params.raw_dim()).unwrap();
-        Linear { config, params }
+        Linear {
+            config,
+            params,
+            mode: self.mode,
+            #[cfg(feature = "rand")]
+            drop_connect: self.drop_connect,
+        }
     }
 
     pub fn with_name(self, name: impl ToString) -> Self {
@@ -150,6 +191,25 @@ where
     concision::dimensional!(params());
 }
 
+impl<A, S, D, K> Linear<A, K, D, S>
+where
+    D: RemoveAxis,
+    S: RawData<Elem = A>,
+{
+    /// Draws the mask applied to the weights during the forward pass, if the layer has a
+    /// [DropConnect](concision::nn::DropConnect) layer in training mode.
+    pub(crate) fn connect_mask(&self) -> Option<Array<A, D>>
+    where
+        A: Num + NumCast + ScalarOperand,
+    {
+        #[cfg(feature = "rand")]
+        if let Some(layer) = self.drop_connect.as_ref() {
+            return layer.mask(self.params.weight.raw_dim());
+        }
+        None
+    }
+}
+
 impl<A, S, D> Linear<A, Biased, D, S>
 where
     D: RemoveAxis,
@@ -163,7 +223,13 @@ where
     {
         let config = Config::<Biased, D>::new().with_shape(shape);
         let params = ParamsBase::biased(config.dim());
-        Linear { config, params }
+        Linear {
+            config,
+            params,
+            mode: Mode::Train,
+            #[cfg(feature = "rand")]
+            drop_connect: None,
+        }
     }
 
     pub fn bias(&self) -> &ArrayBase<S, D::Smaller> {
@@ -188,6 +254,12 @@ where
     {
         let config = Config::<Unbiased, D>::new().with_shape(shape);
         let params = ParamsBase::unbiased(config.dim());
-        Linear { config, params }
+        Linear {
+            config,
+            params,
+            mode: Mode::Train,
+            #[cfg(feature = "rand")]
+            drop_connect: None,
+        }
     }
 }
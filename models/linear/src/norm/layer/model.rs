@@ -3,9 +3,18 @@
     Contrib: FL03 <jo3mccain@icloud.com>
 */
 use super::Config;
-use crate::{Biased, LinearParams, ParamMode, Unbiased};
+use crate::{Biased, LinearParams, ParamMode, ParamsBase, Unbiased};
+#[cfg(feature = "rand")]
+use concision::init::rand_distr::{
+    uniform::SampleUniform, Distribution, NormalError, StandardNormal,
+};
+#[cfg(feature = "rand")]
+use concision::init::{rand, rand::Rng, Init};
+use concision::nn::optim::Optimizable;
 use concision::Forward;
 use nd::prelude::*;
+#[cfg(feature = "rand")]
+use nd::ScalarOperand;
 use nd::{Data, RemoveAxis};
 use num::traits::{Float, FromPrimitive, One, Zero};
 
@@ -99,6 +108,51 @@ where
     concision::dimensional!(config());
 }
 
+#[cfg(feature = "rand")]
+impl<A, K, D> LayerNorm<A, K, D>
+where
+    A: Float + ScalarOperand + SampleUniform,
+    D: RemoveAxis,
+    K: ParamMode,
+    StandardNormal: Distribution<A>,
+    <A as SampleUniform>::Sampler: Clone,
+{
+    /// Initializes the scale and the shift, if any, of the layer according to the given
+    /// strategies; these are conventionally [ones](Init::Ones) and [zeros](Init::Zeros).
+    pub fn init_with(self, scale: Init, shift: Init) -> Result<Self, NormalError> {
+        self.init_with_rng(scale, shift, &mut rand::thread_rng())
+    }
+    /// Initializes the layer according to the given strategies, drawing from `rng`.
+    pub fn init_with_rng<R>(
+        self,
+        scale: Init,
+        shift: Init,
+        rng: &mut R,
+    ) -> Result<Self, NormalError>
+    where
+        R: Rng + ?Sized,
+    {
+        Ok(Self {
+            config: self.config,
+            params: self.params.init_with_rng(scale, shift, rng)?,
+        })
+    }
+}
+
+impl<A, K, D, T> Optimizable<A, ParamsBase<T, D, K>> for LayerNorm<A, K, D>
+where
+    D: RemoveAxis,
+    T: Data<Elem = A>,
+{
+    fn zip_grad(
+        &mut self,
+        grads: &ParamsBase<T, D, K>,
+        f: &mut dyn FnMut(ArrayViewMutD<'_, A>, ArrayViewD<'_, A>),
+    ) {
+        self.params.zip_grad(grads, f)
+    }
+}
+
 impl<A, D> Default for LayerNorm<A, Biased, D>
 where
     A: Default,
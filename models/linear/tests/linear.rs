@@ -55,3 +55,180 @@ fn test_linear() {
 
     assert_eq!(y.shape(), &[samples, outputs]);
 }
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_linear_uniform_rng() {
+    use concision::init::RngContext;
+
+    let (_, (outputs, inputs)) = SHAPE;
+    let init = |seed: u64| {
+        Linear::<f64, Biased>::from_features(inputs, outputs)
+            .uniform_with_rng(&mut RngContext::new(seed))
+            .weights()
+            .to_owned()
+    };
+    assert_eq!(init(0), init(0));
+    assert_ne!(init(0), init(1));
+    let dk = (inputs as f64).recip().sqrt();
+    assert!(init(0).iter().all(|w| w.abs() <= dk));
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_linear_init_with() {
+    use concision::init::{FanMode, Init};
+
+    let (outputs, inputs) = SHAPE.1;
+    let init = Init::Kaiming {
+        mode: FanMode::FanIn,
+        gain: Some(1.0),
+        uniform: true,
+    };
+    let model = Linear::<f64, Biased>::from_features(inputs, outputs)
+        .init_with(init, Init::Zeros)
+        .unwrap();
+    let bnd = (3.0 / inputs as f64).sqrt();
+    assert!(model.weights().iter().all(|&w| w.abs() <= bnd));
+    assert!(model.weights().iter().any(|&w| w != 0.0));
+    assert_eq!(model.bias(), &Array1::<f64>::zeros(outputs));
+}
+
+#[test]
+fn test_linear_trace() {
+    use concision::autodiff::{Tape, Trace};
+    use concision::{Backward, Predict};
+
+    let (samples, (outputs, inputs)) = SHAPE;
+    let model = Linear::<f64, Biased>::ones((outputs, inputs));
+    let data = linarr::<f64, Ix2>((samples, inputs)).unwrap();
+
+    let tape = Tape::new();
+    let x = tape.var(&data);
+    let (y, vars) = model.trace(&tape, x);
+    assert_eq!(y.value(), model.predict(&data).unwrap().into_dyn());
+
+    let grad = model.grad(&vars, &y.sum().backward());
+    let exp = Array2::from_shape_fn((outputs, inputs), |(_, j)| data.column(j).sum());
+    assert_eq!(grad.weights(), &exp);
+    assert_eq!(grad.bias(), &Array1::from_elem(outputs, samples as f64));
+}
+
+#[test]
+fn test_linear_activation() {
+    use concision::autodiff::{Tape, Trace};
+    use concision::func::activate::Activation;
+    use concision::Predict;
+
+    let (samples, (outputs, inputs)) = SHAPE;
+    let data = linarr::<f64, Ix2>((samples, inputs)).unwrap() / 100.0;
+    let model = Linear::<f64, Biased>::ones((outputs, inputs));
+    let z = model.predict(&data).unwrap();
+    // the configured activation is applied to the output of the layer
+    let config = model.config().clone().with_activation(Activation::Tanh);
+    let mut model = Linear::<f64, Biased>::from_config(config);
+    model.weights_mut().fill(1.0);
+    model.bias_mut().fill(1.0);
+    let y = model.predict(&data).unwrap();
+    assert_eq!(y, z.mapv(f64::tanh));
+
+    let tape = Tape::new();
+    let (y, _) = model.trace(&tape, tape.var(&data));
+    assert_eq!(y.value(), z.mapv(f64::tanh).into_dyn());
+}
+
+#[test]
+fn test_linear_sgd() {
+    use concision::autodiff::{Tape, Trace};
+    use concision::nn::optim::{Optimize, Sgd};
+    use concision::Backward;
+
+    let (samples, (outputs, inputs)) = SHAPE;
+    let mut model = Linear::<f64, Biased>::zeros((outputs, inputs));
+    let data = linarr::<f64, Ix2>((samples, inputs)).unwrap() / 100.0;
+    let target = Array2::<f64>::ones((samples, outputs));
+
+    let mut opt = Sgd::new(0.1).with_momentum(0.9);
+    let mut losses = Vec::new();
+    for _ in 0..10 {
+        let tape = Tape::new();
+        let (y, vars) = model.trace(&tape, tape.var(&data));
+        let diff = y - tape.var(&target);
+        let loss = (diff * diff).mean();
+        losses.push(loss.value().sum());
+        let grad = model.grad(&vars, &loss.backward());
+        opt.step(&mut model, &grad).unwrap();
+    }
+    assert!(losses.last().unwrap() < losses.first().unwrap());
+}
+
+#[test]
+fn test_linear_grad_check() {
+    use concision::autodiff::{GradCheck, Tape, Trace, TraceLoss};
+    use concision::func::loss::MSE;
+    use concision::Backward;
+
+    let (samples, (outputs, inputs)) = SHAPE;
+    let mut model = Linear::<f64, Biased>::zeros((outputs, inputs));
+    model.weights_mut().assign(
+        &linarr::<f64, Ix2>((outputs, inputs))
+            .unwrap()
+            .mapv(f64::cos),
+    );
+    let data = linarr::<f64, Ix2>((samples, inputs)).unwrap() / 10.0;
+    let target = data.sum_axis(Axis(1)).insert_axis(Axis(1)) * Array2::<f64>::ones((1, outputs));
+
+    let tape = Tape::new();
+    let (y, vars) = model.trace(&tape, tape.var(&data));
+    let loss = MSE.trace_loss(y, &target.view()).unwrap();
+    let grad = model.grad(&vars, &loss.backward());
+
+    let errs = GradCheck::new(1e-5).check_loss(&mut model, &grad, &data, &target, &MSE);
+    // the weights and the bias
+    assert_eq!(errs.len(), 2);
+    assert!(errs.iter().all(|e| e.is_close(1e-8, 1e-6)));
+    // models may also be checked through their predictions
+    let res = GradCheck::new(1e-5).check_predict(&mut model, &grad, &data, &target, &MSE);
+    assert_eq!(res.unwrap(), errs);
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_linear_drop_connect() {
+    use concision::autodiff::{Tape, Trace};
+    use concision::nn::Training;
+    use concision::{Backward, Predict};
+
+    let (outputs, inputs) = SHAPE.1;
+    let model = Linear::<f64, Biased>::from_features(inputs, outputs)
+        .init_with(concision::init::Init::Ones, concision::init::Init::Ones)
+        .unwrap()
+        .with_drop_connect(0.5);
+    assert!(model.is_training());
+    // given the identity, each row of the prediction is a column of the masked weights
+    let x = Array2::<f64>::eye(inputs);
+    let y = model.predict(&x).unwrap();
+    assert!(y.iter().all(|&v| v == 1.0 || v == 3.0));
+    // the dropped weights receive no gradient, while the survivors are scaled
+    let tape = Tape::new();
+    let (y, vars) = model.trace(&tape, tape.var(&x));
+    let grad = model.grad(&vars, &y.sum().backward());
+    let y = y.value().into_dimensionality::<Ix2>().unwrap();
+    assert_eq!(grad.weights(), &(&y.t() - 1.0));
+    assert_eq!(grad.bias(), &Array1::from_elem(outputs, inputs as f64));
+
+    let mut model = model.with_drop_connect(1.0);
+    assert_eq!(model.predict(&x).unwrap(), Array2::ones((inputs, outputs)));
+    model.eval_mode();
+    assert_eq!(model.drop_connect().unwrap().mode(), model.mode());
+    assert_eq!(
+        model.predict(&x).unwrap(),
+        Array2::from_elem((inputs, outputs), 2.0)
+    );
+    // a layer added while evaluating inherits the mode of the model
+    let mut model = Linear::<f64, Biased>::from_features(inputs, outputs);
+    model.eval_mode();
+    let model = model.with_drop_connect(0.5);
+    assert!(!model.is_training());
+    assert!(!model.drop_connect().unwrap().is_training());
+}
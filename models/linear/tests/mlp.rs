@@ -5,7 +5,7 @@
 extern crate concision_core as cnc;
 extern crate concision_linear as linear;
 
-use cnc::prelude::{linarr, Forward, ReLU};
+use cnc::prelude::{linarr, Forward, Predict, ReLU};
 use linear::mlp::Perceptron;
 use linear::{Biased, Features, Linear};
 use ndarray::prelude::*;
@@ -21,3 +21,27 @@ fn test_perceptron() {
     let mlp = Perceptron::new(layer.clone(), Box::new(ReLU::relu));
     assert_eq!(mlp.forward(&data), layer.forward(&data).relu());
 }
+
+#[test]
+fn test_mlp_from_configs() {
+    use cnc::func::activate::Activation;
+    use linear::mlp::Mlp;
+    use linear::Config;
+
+    let layer = |inputs, outputs, rho: &str| {
+        Config::<Biased>::std(inputs, outputs).with_activation(rho.parse().unwrap())
+    };
+    let mlp = Mlp::from_configs(
+        layer(3, 4, "gelu"),
+        vec![layer(4, 4, "tanh")],
+        layer(4, 2, "softmax"),
+    );
+    assert_eq!(mlp.input().rho(), &Activation::GELU);
+    assert_eq!(mlp.hidden()[0].rho(), &Activation::Tanh);
+
+    let data = linarr::<f64, Ix2>((5, 3)).unwrap();
+    let y = mlp.predict(&data).unwrap();
+    assert_eq!(y.dim(), (5, 2));
+    // the parameters are zero, hence the outputs are uniformly distributed
+    assert!(y.iter().all(|&p| (p - 0.5).abs() < 1e-12));
+}
@@ -33,3 +33,34 @@ fn test_layer_norm() {
     assert_eq!(y.dim(), shape);
     assert_abs_diff_eq!(y, *NORM, epsilon = 1e-4);
 }
+
+#[test]
+fn test_layer_norm_trace() {
+    use concision::autodiff::{Tape, Trace};
+    use concision::Backward;
+
+    let shape = SHAPE;
+    let x = linarr::<f64, Ix2>(shape).unwrap();
+    let ln = LayerNorm::<f64, Biased>::ones(shape);
+
+    let tape = Tape::new();
+    let (y, vars) = ln.trace(&tape, tape.var(&x));
+    assert_abs_diff_eq!(y.value(), ln.forward(&x).into_dyn(), epsilon = 1e-12);
+
+    let grad = ln.grad(&vars, &y.sum().backward());
+    assert_abs_diff_eq!(grad.weights(), &(&*NORM - 1.0), epsilon = 1e-4);
+    assert_eq!(grad.bias(), &Array1::from_elem(3, 3.0));
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_layer_norm_init_with() {
+    use concision::init::Init;
+
+    let shape = SHAPE;
+    let x = linarr::<f64, Ix2>(shape).unwrap();
+    let ln = LayerNorm::<f64, Biased>::zeros(shape)
+        .init_with(Init::Ones, Init::Ones)
+        .unwrap();
+    assert_abs_diff_eq!(ln.forward(&x), *NORM, epsilon = 1e-4);
+}
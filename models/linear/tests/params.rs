@@ -46,3 +46,37 @@ fn test_linear_params() {
     let y: Array2<f64> = params.predict(&data).unwrap();
     assert_eq!(y.dim(), (samples, outputs));
 }
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_params_init() {
+    use cnc::init::FanMode;
+
+    let features = Features::new(FEATURES, D_MODEL);
+    let params = LinearParams::<f64>::biased(features).orthogonal(1.0);
+    let gram = params.weights().dot(&params.weights().t());
+    assert!((gram - Array2::<f64>::eye(FEATURES))
+        .iter()
+        .all(|x| x.abs() < 1e-10));
+    assert!(params.bias().iter().all(|&b| b == 0.0));
+
+    let params = LinearParams::<f64>::biased(features).kaiming_uniform(FanMode::FanIn, 1.0);
+    let bnd = (3.0 / D_MODEL as f64).sqrt();
+    assert!(params.weights().iter().all(|&w| w.abs() <= bnd));
+
+    let params = LinearParams::<f64>::biased(features).constant(0.5);
+    assert!(params.bias().iter().all(|&b| b == 0.5));
+}
+
+#[test]
+fn test_params_penalty() {
+    use cnc::func::loss::L1;
+
+    let params = LinearParams::<f64>::ones((FEATURES, D_MODEL));
+    let l1 = L1::new(0.5);
+    // the bias is not penalized
+    assert_eq!(params.penalty(&l1), 0.5 * (FEATURES * D_MODEL) as f64);
+    let grad = params.penalty_grad(&l1);
+    assert_eq!(grad.weights(), &Array2::from_elem((FEATURES, D_MODEL), 0.5));
+    assert_eq!(grad.bias(), &Array1::zeros(FEATURES));
+}
@@ -2,9 +2,9 @@
     Appellation: head <module>
     Contrib: FL03 <jo3mccain@icloud.com>
 */
-use super::{Score, _attention};
+use super::{_attention, Score};
 use crate::params::QkvBase;
-use concision::nn::Dropout;
+use concision::nn::{Dropout, Mode};
 use nd::linalg::Dot;
 use nd::*;
 use num::complex::ComplexFloat;
@@ -18,7 +18,8 @@ use num::complex::ComplexFloat;
 /// ### `dropout`
 ///
 /// The [Dropout] layer is an optional, conditionally enabled layer (required the `rand` feature).
-/// If enabled, the dropout layer is invoked after the softmax function is applied to the score.
+/// If enabled, the dropout layer is invoked after the softmax function is applied to the score,
+/// though only while the head is in [training](Mode::Train) mode; see [Training](concision::nn::Training).
 /// The layer is used to prevent overfitting by randomly setting a fraction of the input
 /// units to zero at each update during training time.
 ///
@@ -36,6 +37,7 @@ where
     #[cfg(feature = "rand")]
     pub(crate) dropout: Option<Dropout>,
     pub(crate) mask: Option<Array<bool, D>>,
+    pub(crate) mode: Mode,
     pub(crate) params: QkvBase<S, D>,
 }
 
@@ -80,6 +82,7 @@ where
             #[cfg(feature = "rand")]
             dropout: None,
             mask: None,
+            mode: Mode::Train,
             params,
         }
     }
@@ -117,7 +120,7 @@ where
     /// Sets the dropout layer for the [AttentionHead]
     #[cfg(feature = "rand")]
     pub fn set_dropout(&mut self, dropout: Option<Dropout>) {
-        self.dropout = dropout;
+        self.dropout = dropout.map(|dropout| dropout.with_mode(self.mode));
     }
     /// Sets the mask for the [AttentionHead]
     pub fn set_mask(&mut self, mask: Option<Array<bool, D>>) {
@@ -127,14 +130,18 @@ where
     #[cfg(feature = "rand")]
     pub fn with_dropout(self, dropout: Dropout) -> Self {
         Self {
-            dropout: Some(dropout),
+            dropout: Some(dropout.with_mode(self.mode)),
             ..self
         }
     }
-    /// Consume and store a mask for the [AttentionHead]
-    pub fn with_mask(self, mask: Array<bool, D>) -> Self {
+    /// Consume and store a mask, e.g. a [Mask](concision::nn::mask::Mask), for the
+    /// [AttentionHead]
+    pub fn with_mask<M>(self, mask: M) -> Self
+    where
+        M: Into<Array<bool, D>>,
+    {
         Self {
-            mask: Some(mask),
+            mask: Some(mask.into()),
             ..self
         }
     }
@@ -60,7 +60,9 @@ pub(crate) mod utils {
 
 mod _impl_methods {
     use super::Score;
-    use concision::prelude::{Dropout, MaskFill, Softmax};
+    use concision::func::activate::softmax_axis;
+    use concision::nn::mask::Mask;
+    use concision::nn::Dropout;
     use nd::linalg::Dot;
     use nd::prelude::*;
     use num::complex::ComplexFloat;
@@ -82,10 +84,12 @@ mod _impl_methods {
         use concision::Forward;
         let dk = scale::<A>(k.len_of(nd::Axis(1)));
         let mut z = q.dot(&k.t()) * dk;
-        if let Some(mask) = mask {
-            z = z.masked_fill(mask, A::zero());
-        }
-        z = z.softmax();
+        // normalize the scores of each query, i.e. along the last axis, in a stable manner;
+        // masked keys are given a probability of exactly zero
+        z = match mask {
+            Some(mask) => Mask::from_arr(mask.view()).softmax_axis(&z, z.ndim() - 1),
+            None => softmax_axis(&z, z.ndim() - 1),
+        };
         #[cfg(feature = "rand")]
         if let Some(dropout) = dropout {
             z = dropout.forward(&z);
@@ -3,6 +3,7 @@
     Contrib: FL03 <jo3mccain@icloud.com>
 */
 use crate::{attention::AttentionConfig, AttentionHead};
+use concision::nn::{Mode, Training};
 use linear::{Biased, Linear};
 use nd::prelude::*;
 use nd::{DataOwned, OwnedRepr, RawData};
@@ -62,6 +63,23 @@ where
     }
 }
 
+impl<A, S, D> Training for MultiHeadAttention<A, D, S>
+where
+    D: Dimension,
+    S: RawData<Elem = A>,
+{
+    fn mode(&self) -> Mode {
+        self.head.mode()
+    }
+
+    fn set_mode(&mut self, mode: Mode) {
+        self.head.set_mode(mode);
+        for linear in self.linears.iter_mut() {
+            linear.set_mode(mode);
+        }
+    }
+}
+
 impl<A, S, D> Default for MultiHeadAttention<A, D, S>
 where
     A: Default,
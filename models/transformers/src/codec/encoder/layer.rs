@@ -4,6 +4,7 @@
 */
 use crate::attention::multi::MultiHeadAttention;
 use crate::model::ffn::FeedForwardNetwork;
+use concision::nn::{Mode, Training};
 use linear::Biased;
 use nd::prelude::*;
 
@@ -39,3 +40,17 @@ where
         &mut self.ffn
     }
 }
+
+impl<A, D, K> Training for EncoderLayer<A, K, D>
+where
+    D: Dimension,
+{
+    fn mode(&self) -> Mode {
+        self.ffn.mode()
+    }
+
+    fn set_mode(&mut self, mode: Mode) {
+        self.attention.set_mode(mode);
+        self.ffn.set_mode(mode);
+    }
+}
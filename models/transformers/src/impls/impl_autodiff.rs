@@ -0,0 +1,85 @@
+/*
+    Appellation: impl_autodiff <module>
+    Contrib: FL03 <jo3mccain@icloud.com>
+*/
+use crate::attention::AttentionHead;
+use crate::model::ffn::FeedForwardNetwork;
+use crate::params::QkvBase;
+use concision::autodiff::{Gradients, Tape, Trace, Var};
+use concision::nn::mask::Mask;
+use linear::{LinearParams, ParamMode};
+use nd::prelude::*;
+use nd::{Data, ScalarOperand};
+use num::traits::{Float, FloatConst};
+
+/// Records scaled dot-product attention onto the tape; the query, key, and value tensors are
+/// registered as leaves so the resulting gradient mirrors the layout of [QkvBase]. The dropout
+/// layer is not recorded.
+impl<'t, A, S> Trace<'t, A, ()> for AttentionHead<A, Ix2, S>
+where
+    A: Float + ScalarOperand,
+    S: Data<Elem = A>,
+{
+    type Output = Var<'t, A>;
+    type Vars = (Var<'t, A>, Var<'t, A>, Var<'t, A>);
+    type Grad = QkvBase<nd::OwnedRepr<A>, Ix2>;
+
+    fn trace(&self, tape: &'t Tape<A>, _input: ()) -> (Self::Output, Self::Vars) {
+        let (q, k, v) = self.qkv();
+        let (q, k, v) = (tape.var(q), tape.var(k), tape.var(v));
+        let dk = A::from(k.shape()[1]).unwrap().sqrt().recip();
+        let z = q.dot(&k.t()).scale(dk);
+        let z = match self.mask() {
+            // mirrors [Mask::softmax_axis], zeroing the queries whose every key is masked
+            Some(mask) => {
+                let mask = Mask::from_arr(mask.view());
+                let full = mask.full_lanes(Axis(1));
+                z.masked_fill(&mask.and(&!&full), A::neg_infinity())
+                    .masked_fill(&full, A::zero())
+                    .softmax_axis(1)
+                    .masked_fill(&mask, A::zero())
+            }
+            None => z.softmax_axis(1),
+        };
+        (z.dot(&v), (q, k, v))
+    }
+
+    fn grad(&self, (q, k, v): &Self::Vars, grads: &Gradients<A>) -> Self::Grad {
+        QkvBase {
+            q: grads.wrt_as(q),
+            k: grads.wrt_as(k),
+            v: grads.wrt_as(v),
+        }
+    }
+}
+
+/// Records both projections and the intermediate [activation](FeedForwardNetwork::activation)
+/// onto the tape; the gradient is returned as a pair of parameter stores ordered from input to
+/// output. The dropout layer is not recorded.
+impl<'t, A, K> Trace<'t, A, Var<'t, A>> for FeedForwardNetwork<A, K, Ix2>
+where
+    A: Float + FloatConst + ScalarOperand,
+    K: ParamMode,
+{
+    type Output = Var<'t, A>;
+    type Vars = (
+        (Var<'t, A>, Option<Var<'t, A>>),
+        (Var<'t, A>, Option<Var<'t, A>>),
+    );
+    type Grad = (LinearParams<A, K, Ix2>, LinearParams<A, K, Ix2>);
+
+    fn trace(&self, tape: &'t Tape<A>, input: Var<'t, A>) -> (Self::Output, Self::Vars) {
+        let (hidden, inner) = self.input().trace(tape, input);
+        let (res, outer) = self
+            .output()
+            .trace(tape, hidden.activate(self.activation()));
+        (res, (inner, outer))
+    }
+
+    fn grad(&self, (inner, outer): &Self::Vars, grads: &Gradients<A>) -> Self::Grad {
+        (
+            self.input().grad(inner, grads),
+            self.output().grad(outer, grads),
+        )
+    }
+}
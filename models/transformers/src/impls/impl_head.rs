@@ -4,6 +4,7 @@
 */
 use crate::attention::{Attention, AttentionHead, Score};
 use crate::params::QkvBase;
+use concision::nn::{Mode, Training};
 use core::borrow::{Borrow, BorrowMut};
 use nd::linalg::Dot;
 use nd::prelude::*;
@@ -56,6 +57,7 @@ where
             #[cfg(feature = "rand")]
             dropout: self.dropout.clone(),
             mask: self.mask.clone(),
+            mode: self.mode,
             params: self.params.clone(),
         }
     }
@@ -70,6 +72,24 @@ where
 {
 }
 
+impl<A, S, D> Training for AttentionHead<A, D, S>
+where
+    D: Dimension,
+    S: RawData<Elem = A>,
+{
+    fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+        #[cfg(feature = "rand")]
+        if let Some(dropout) = self.dropout.as_mut() {
+            dropout.set_mode(mode);
+        }
+    }
+}
+
 impl<A, S, D> Default for AttentionHead<A, D, S>
 where
     A: Default,
@@ -3,15 +3,128 @@
     Contrib: FL03 <jo3mccain@icloud.com>
 */
 #![cfg(feature = "rand")]
-use crate::QkvBase;
-use concision::Initialize;
+use crate::model::ffn::FeedForwardNetwork;
+use crate::{AttentionHead, MultiHeadAttention, QkvBase};
 use concision::init::rand::Rng;
-use concision::init::rand_distr::{Distribution, StandardNormal};
 use concision::init::rand_distr::uniform::SampleUniform;
-use nd::{ArrayBase, DataOwned, Dimension, ShapeBuilder};
+use concision::init::rand_distr::{Distribution, NormalError, StandardNormal};
+use concision::init::{fans, Init, RngContext};
+use concision::Initialize;
+use linear::ParamMode;
+use nd::{ArrayBase, DataOwned, Dimension, OwnedRepr, RemoveAxis, ScalarOperand, ShapeBuilder};
+use num::traits::Float;
+
+impl<A, D> QkvBase<OwnedRepr<A>, D>
+where
+    A: Float + SampleUniform + ScalarOperand,
+    D: Dimension,
+    StandardNormal: Distribution<A>,
+    <A as SampleUniform>::Sampler: Clone,
+{
+    /// Initializes the query, key and value arrays according to the given strategy; being
+    /// projections, they have no biases.
+    pub fn init_with(self, init: Init) -> Result<Self, NormalError> {
+        self.init_with_rng(init, &mut RngContext::from_entropy())
+    }
+    /// Initializes the arrays according to the given strategy, giving each its own
+    /// [fork](RngContext::fork) of the context.
+    pub fn init_with_rng(self, init: Init, rng: &mut RngContext) -> Result<Self, NormalError> {
+        let dim = self.q.raw_dim();
+        let fans = fans(dim.slice());
+        Ok(Self {
+            q: init.array_with(dim.clone(), fans, &mut rng.fork())?,
+            k: init.array_with(dim.clone(), fans, &mut rng.fork())?,
+            v: init.array_with(dim, fans, &mut rng.fork())?,
+        })
+    }
+}
 
-impl<A, S, D> Initialize for QkvBase<S, D> where
+impl<A, D> AttentionHead<A, D>
+where
+    A: Float + SampleUniform + ScalarOperand,
+    D: Dimension,
+    StandardNormal: Distribution<A>,
+    <A as SampleUniform>::Sampler: Clone,
+{
+    /// Initializes the parameters of the head according to the given strategy.
+    pub fn init_with(self, init: Init) -> Result<Self, NormalError> {
+        self.init_with_rng(init, &mut RngContext::from_entropy())
+    }
+    /// Initializes the parameters of the head according to the given strategy, drawing from
+    /// the given context.
+    pub fn init_with_rng(self, init: Init, rng: &mut RngContext) -> Result<Self, NormalError> {
+        Ok(Self {
+            params: self.params.init_with_rng(init, rng)?,
+            ..self
+        })
+    }
+}
+
+impl<A, D> MultiHeadAttention<A, D>
+where
+    A: Float + SampleUniform + ScalarOperand,
     D: RemoveAxis,
+    StandardNormal: Distribution<A>,
+    <A as SampleUniform>::Sampler: Clone,
+{
+    /// Initializes the weights of the head and of each linear layer according to `weight`,
+    /// and the biases of the linear layers according to `bias`.
+    pub fn init_with(self, weight: Init, bias: Init) -> Result<Self, NormalError> {
+        self.init_with_rng(weight, bias, &mut RngContext::from_entropy())
+    }
+    /// Initializes the model according to the given strategies, giving the head and each of
+    /// the linear layers its own [fork](RngContext::fork) of the context.
+    pub fn init_with_rng(
+        self,
+        weight: Init,
+        bias: Init,
+        rng: &mut RngContext,
+    ) -> Result<Self, NormalError> {
+        let head = self.head.init_with_rng(weight, &mut rng.fork())?;
+        let linears = self
+            .linears
+            .into_iter()
+            .map(|layer| layer.init_with_rng(weight, bias, &mut rng.fork()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            config: self.config,
+            head,
+            linears,
+        })
+    }
+}
+
+impl<A, K, D> FeedForwardNetwork<A, K, D>
+where
+    A: Float + SampleUniform + ScalarOperand,
+    D: RemoveAxis,
+    K: ParamMode,
+    StandardNormal: Distribution<A>,
+    <A as SampleUniform>::Sampler: Clone,
+{
+    /// Initializes the weights and biases of both layers according to the given strategies.
+    pub fn init_with(self, weight: Init, bias: Init) -> Result<Self, NormalError> {
+        self.init_with_rng(weight, bias, &mut RngContext::from_entropy())
+    }
+    /// Initializes both layers according to the given strategies, giving each its own
+    /// [fork](RngContext::fork) of the context.
+    pub fn init_with_rng(
+        self,
+        weight: Init,
+        bias: Init,
+        rng: &mut RngContext,
+    ) -> Result<Self, NormalError> {
+        Ok(Self {
+            input: self.input.init_with_rng(weight, bias, &mut rng.fork())?,
+            output: self.output.init_with_rng(weight, bias, &mut rng.fork())?,
+            ..self
+        })
+    }
+}
+
+impl<A, S, D> Initialize<A, D> for QkvBase<S, D>
+where
+    D: Dimension,
     S: DataOwned<Elem = A>,
     StandardNormal: Distribution<A>,
 {
@@ -26,7 +139,7 @@ impl<A, S, D> Initialize for QkvBase<S, D> where
         Self {
             q: ArrayBase::rand(dim.clone(), distr.clone()),
             k: ArrayBase::rand(dim.clone(), distr.clone()),
-            v: ArrayBase::rand(dim, distr)
+            v: ArrayBase::rand(dim, distr),
         }
     }
 
@@ -38,9 +151,9 @@ impl<A, S, D> Initialize for QkvBase<S, D> where
     {
         let dim = shape.into_shape().raw_dim().clone();
         Self {
-            q: ArrayBase::rand_with(dim.clone(), distr.clone(), &mut rng),
-            k: ArrayBase::rand_with(dim.clone(), distr.clone(), &mut rng),
-            v: ArrayBase::rand_with(dim, distr, &mut rng)
+            q: ArrayBase::rand_with(dim.clone(), distr.clone(), rng),
+            k: ArrayBase::rand_with(dim.clone(), distr.clone(), rng),
+            v: ArrayBase::rand_with(dim, distr, rng),
         }
     }
 
@@ -49,7 +162,7 @@ impl<A, S, D> Initialize for QkvBase<S, D> where
         Ds: Clone + Distribution<A>,
         Self: Sized,
     {
-        Self::rand(self.dim(), distr)
+        Self::rand(self.q.raw_dim(), distr)
     }
 
     fn init_rand_with<Ds, R>(self, distr: Ds, rng: &mut R) -> Self
@@ -57,8 +170,6 @@ impl<A, S, D> Initialize for QkvBase<S, D> where
         R: Rng + ?Sized,
         Ds: Clone + Distribution<A>,
     {
-        Self::rand_with(self.dim(), distr, rng)
+        Self::rand_with(self.q.raw_dim(), distr, rng)
     }
 }
-
-
@@ -36,7 +36,9 @@ pub mod ops;
 pub mod params;
 
 mod impls {
+    mod impl_autodiff;
     mod impl_head;
+    mod impl_init;
     mod impl_linalg;
     mod impl_params;
 }
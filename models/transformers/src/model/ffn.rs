@@ -2,15 +2,20 @@
     Appellation: model <module>
     Contrib: FL03 <jo3mccain@icloud.com>
 */
-use concision::prelude::{Dropout, Forward, Predict, PredictError, ReLU};
-use linear::{Biased, Linear, ParamMode};
+use concision::func::activate::Activation;
+use concision::nn::optim::Optimizable;
+use concision::nn::{Mode, Training};
+use concision::prelude::{Dropout, Forward, Predict, PredictError};
+use linear::{Biased, Linear, ParamMode, ParamsBase};
 use nd::prelude::*;
-use nd::{RemoveAxis, ScalarOperand};
-use num::traits::Num;
+use nd::{ArrayViewD, ArrayViewMutD, Data, RemoveAxis, ScalarOperand};
+use num::traits::{Float, FloatConst};
 
 // #84: FeedForwardNetwork
-/// A piecewise, feed-forward neural network consisting of two [Linear] layers with a ReLU activation function
-/// optionally (and conditionally) supporting an [Dropout] layer.
+/// A piecewise, feed-forward neural network consisting of two [Linear] layers separated by an
+/// [Activation], ReLU by default, optionally (and conditionally) supporting an [Dropout] layer.
+///
+/// The dropout layer is only applied in [training](Mode::Train) mode; see [Training].
 ///
 /// ### Shape
 ///
@@ -21,9 +26,11 @@ pub struct FeedForwardNetwork<A = f64, K = Biased, D = Ix2>
 where
     D: Dimension,
 {
+    pub(crate) activation: Activation,
     #[cfg(feature = "rand")]
     pub(crate) dropout: Option<Dropout>,
     pub(crate) input: Linear<A, K, D>,
+    pub(crate) mode: Mode,
     pub(crate) output: Linear<A, K, D>,
 }
 
@@ -39,8 +46,10 @@ where
         let input = Linear::from_features(d_model, features);
         let output = Linear::from_features(features, d_model);
         Self {
+            activation: Activation::ReLU,
             dropout,
             input,
+            mode: Mode::Train,
             output,
         }
     }
@@ -51,6 +60,14 @@ where
     D: Dimension,
 {
     concision::getters!(input, output => Linear<A, K, D>);
+    /// Returns the [Activation] applied between the two layers.
+    pub const fn activation(&self) -> Activation {
+        self.activation
+    }
+
+    pub fn with_activation(self, activation: Activation) -> Self {
+        Self { activation, ..self }
+    }
 }
 
 #[cfg(feature = "rand")]
@@ -73,9 +90,44 @@ where
     }
 }
 
+impl<A, D, K> Training for FeedForwardNetwork<A, K, D>
+where
+    D: Dimension,
+{
+    fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+        self.input.set_mode(mode);
+        self.output.set_mode(mode);
+        #[cfg(feature = "rand")]
+        if let Some(dropout) = self.dropout.as_mut() {
+            dropout.set_mode(mode);
+        }
+    }
+}
+
+impl<A, D, K, T> Optimizable<A, (ParamsBase<T, D, K>, ParamsBase<T, D, K>)>
+    for FeedForwardNetwork<A, K, D>
+where
+    D: RemoveAxis,
+    T: Data<Elem = A>,
+{
+    fn zip_grad(
+        &mut self,
+        (input, output): &(ParamsBase<T, D, K>, ParamsBase<T, D, K>),
+        f: &mut dyn FnMut(ArrayViewMutD<'_, A>, ArrayViewD<'_, A>),
+    ) {
+        self.input.zip_grad(input, f);
+        self.output.zip_grad(output, f)
+    }
+}
+
 impl<A, B, D, E, K> Predict<Array<B, E>> for FeedForwardNetwork<A, K, D>
 where
-    B: Num + PartialOrd + ScalarOperand,
+    B: Float + FloatConst + ScalarOperand,
     D: RemoveAxis,
     E: Dimension,
     Linear<A, K, D>: Predict<Array<B, E>, Output = Array<B, E>>,
@@ -83,7 +135,7 @@ where
     type Output = Array<B, E>;
 
     fn predict(&self, input: &Array<B, E>) -> Result<Self::Output, PredictError> {
-        let mut y = self.input().predict(input)?.relu();
+        let mut y = self.activation.apply(&self.input().predict(input)?);
         if let Some(dropout) = self.dropout() {
             y = dropout.forward(&y);
         }
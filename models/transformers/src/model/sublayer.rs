@@ -3,7 +3,7 @@
     Contrib: FL03 <jo3mccain@icloud.com>
 */
 #![cfg(feature = "rand")]
-use concision::nn::Dropout;
+use concision::nn::{DropPath, Dropout, Mode, Training};
 use concision::Forward;
 use linear::{Biased, LayerNorm, ParamMode, Unbiased};
 use nd::prelude::*;
@@ -12,11 +12,15 @@ use num::traits::{Float, FromPrimitive};
 
 /// A residual connection followed by a [layer norm](LayerNorm)
 /// [Transformer](crate::Transformer)
+///
+/// The dropout layer is only applied in [training](Mode::Train) mode; see [Training].
+/// Optionally, the residual branch may be dropped for entire samples using [DropPath].
 pub struct Sublayer<A = f64, K = Biased, D = Ix2>
 where
     D: Dimension,
 {
     pub(crate) dropout: Dropout,
+    pub(crate) drop_path: Option<DropPath>,
     pub(crate) norm: LayerNorm<A, K, D>,
 }
 
@@ -32,17 +36,50 @@ where
     {
         Self {
             dropout: Dropout::new(dropout),
+            drop_path: None,
             norm: LayerNorm::new(shape),
         }
     }
+    /// Enables stochastic depth, dropping the residual branch with the given probability.
+    pub fn with_drop_path(self, p: f64) -> Self {
+        let drop_path = DropPath::new(p).with_mode(self.dropout.mode());
+        Self {
+            drop_path: Some(drop_path),
+            ..self
+        }
+    }
 
     pub fn dropout(&self) -> &Dropout {
         &self.dropout
     }
 
+    pub fn drop_path(&self) -> Option<&DropPath> {
+        self.drop_path.as_ref()
+    }
+
     pub fn norm(&self) -> &LayerNorm<A, K, D> {
         &self.norm
     }
+
+    pub fn norm_mut(&mut self) -> &mut LayerNorm<A, K, D> {
+        &mut self.norm
+    }
+}
+
+impl<A, K, D> Training for Sublayer<A, K, D>
+where
+    D: Dimension,
+{
+    fn mode(&self) -> Mode {
+        self.dropout.mode()
+    }
+
+    fn set_mode(&mut self, mode: Mode) {
+        self.dropout.set_mode(mode);
+        if let Some(drop_path) = self.drop_path.as_mut() {
+            drop_path.set_mode(mode);
+        }
+    }
 }
 
 impl<A, S, D> Forward<ArrayBase<S, D>> for Sublayer<A, Biased, D>
@@ -55,7 +92,11 @@ where
 
     fn forward(&self, input: &ArrayBase<S, D>) -> Self::Output {
         let normal = self.norm().forward(input);
-        input + self.dropout().forward(&normal)
+        let branch = self.dropout().forward(&normal);
+        match self.drop_path() {
+            Some(drop_path) => input + drop_path.forward(&branch),
+            None => input + branch,
+        }
     }
 }
 
@@ -69,6 +110,10 @@ where
 
     fn forward(&self, input: &ArrayBase<S, D>) -> Self::Output {
         let normal = self.norm().forward(input);
-        input + self.dropout().forward(&normal)
+        let branch = self.dropout().forward(&normal);
+        match self.drop_path() {
+            Some(drop_path) => input + drop_path.forward(&branch),
+            None => input + branch,
+        }
     }
 }
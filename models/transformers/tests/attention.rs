@@ -18,5 +18,141 @@ fn attention_head() {
     assert_eq!(head.q(), &Array::ones(shape));
     let exp = Array2::from_elem(shape, 1f64 / 3f64);
     let score = head.attention();
-    assert!(score.attention().abs_diff_eq(&exp, 1e-6));
+    assert!(score.score().abs_diff_eq(&exp, 1e-6));
+}
+
+#[test]
+fn attention_head_row_softmax() {
+    let shape = (3, 3);
+
+    let head = AttentionHead::<f64>::ones(shape);
+    let score = head.attention();
+    // the weights of each query are normalized independently, summing to one
+    let rows = score.score().sum_axis(Axis(1));
+    assert!(rows.abs_diff_eq(&Array1::ones(3), 1e-12));
+    // each query attends uniformly to every key, averaging the (identical) values
+    assert!(score.attention().abs_diff_eq(&Array2::ones(shape), 1e-6));
+}
+
+#[test]
+fn attention_stability() {
+    use transformer::attention::scaled_dot_product_attention;
+
+    let q: Array2<f64> = array![[30.0, -30.0], [40.0, 0.0]];
+    let k = array![[40.0, 0.0], [0.0, 40.0]];
+    let v = array![[1.0, 2.0], [3.0, 4.0]];
+    // the scaled scores exceed the range of the exponential
+    let score = scaled_dot_product_attention(&q, &k, &v, None, None);
+    assert!(score.attention().iter().all(|x| x.is_finite()));
+    assert!(score
+        .attention()
+        .abs_diff_eq(&array![[1.0, 2.0], [1.0, 2.0]], 1e-12));
+}
+
+#[test]
+fn attention_head_trace() {
+    use concision::autodiff::{Tape, Trace};
+    use concision::Backward;
+
+    let shape = (3, 3);
+    let head = AttentionHead::<f64>::ones(shape);
+
+    let tape = Tape::new();
+    let (z, vars) = head.trace(&tape, ());
+    assert!(z
+        .value()
+        .abs_diff_eq(&Array2::ones(shape).into_dyn(), 1e-12));
+
+    let grad = head.grad(&vars, &z.sum().backward());
+    // uniform scores are stationary, leaving only the values with a gradient
+    assert!(grad.q().iter().all(|g| g.abs() < 1e-12));
+    assert!(grad.k().iter().all(|g| g.abs() < 1e-12));
+    assert_eq!(grad.v(), &Array2::from_elem(shape, 1.0));
+}
+
+#[test]
+fn test_multi_head_init_with() {
+    use concision::init::Init;
+    use transformer::MultiHeadAttention;
+
+    let (d_model, heads) = (8, 2);
+    let init = Init::Normal {
+        mean: 0.0,
+        std: 0.02,
+    };
+    let model = MultiHeadAttention::<f64>::std(d_model, heads)
+        .init_with(init, Init::Ones)
+        .unwrap();
+    assert!(model.head().q().iter().any(|&x| x != 0.0));
+    for layer in model.linears() {
+        assert!(layer.weights().iter().any(|&w| w != 0.0));
+        assert_eq!(layer.bias(), &Array1::<f64>::ones(d_model));
+    }
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_attention_head_mode() {
+    use concision::nn::{Dropout, Training};
+
+    let shape = (4, 4);
+    let head = AttentionHead::<f64>::from_elem(shape, 0.5);
+    let exp = head.attention();
+    let mut head = head.with_dropout(Dropout::new(0.5));
+    head.eval_mode();
+    assert_eq!(head.attention().attention(), exp.attention());
+}
+
+#[test]
+fn test_multi_head_mode() {
+    use concision::nn::Training;
+    use transformer::MultiHeadAttention;
+
+    let mut model = MultiHeadAttention::<f64>::std(8, 2);
+    model.eval_mode();
+    assert!(!model.head().is_training());
+    assert!(model.linears().iter().all(|layer| !layer.is_training()));
+    model.train_mode();
+    assert!(model.linears().iter().all(|layer| layer.is_training()));
+}
+
+#[test]
+fn attention_causal_mask() {
+    use concision::autodiff::{Tape, Trace};
+    use concision::nn::mask::Mask;
+
+    let n = 4;
+    let head = AttentionHead::<f64>::from_elem((n, n), 0.5).with_mask(Mask::causal(n));
+    let score = head.attention();
+    for ((i, j), &p) in score.score().indexed_iter() {
+        if j > i {
+            assert_eq!(p, 0.0);
+        } else {
+            assert!((p - (i as f64 + 1.0).recip()).abs() < 1e-12);
+        }
+    }
+    // the traced head agrees with the functional one
+    let tape = Tape::new();
+    let (z, _vars) = head.trace(&tape, ());
+    assert!(z
+        .value()
+        .abs_diff_eq(&score.attention().clone().into_dyn(), 1e-12));
+}
+
+#[test]
+fn attention_head_trace_matches_attention() {
+    use concision::autodiff::{Tape, Trace};
+
+    let shape = (3, 4);
+    let head = AttentionHead::<f64>::builder(shape, |dim| {
+        Array::linspace(-1.0, 1.0, dim.size())
+            .into_shape(dim)
+            .unwrap()
+    });
+    let tape = Tape::new();
+    let (z, _vars) = head.trace(&tape, ());
+    // the traced forward pass agrees with the functional one
+    assert!(z
+        .value()
+        .abs_diff_eq(&head.attention().attention().clone().into_dyn(), 1e-12));
 }
@@ -3,6 +3,7 @@
     Contrib: FL03 <jo3mccain@icloud.com>
 */
 extern crate concision_core as cnc;
+extern crate concision_data as data;
 extern crate concision_linear as linear;
 extern crate concision_transformer as transformer;
 
@@ -23,3 +24,190 @@ fn test_ffn() {
     let pred = model.predict(&data).unwrap();
     assert_eq!(pred.dim(), (samples, d_model));
 }
+
+#[test]
+fn test_ffn_trainer() {
+    use cnc::func::loss::MSE;
+    use cnc::nn::optim::Adam;
+    use cnc::{Compile, Train};
+    use data::{Dataset, Trainer};
+
+    let (samples, d_model, d_ff) = (20, 4, 8);
+    let model = FeedForwardNetwork::<f64, Biased>::std(d_model, d_ff, None);
+    let records = linarr::<f64, Ix2>((samples, d_model)).unwrap() / 100.0;
+    let targets = records.mapv(|x| 2.0 * x + 1.0);
+
+    let mut trainer = Trainer::new(model, Adam::new(0.05), MSE)
+        .with_batch_size(5)
+        .with_epochs(5);
+    trainer.compile(&Dataset::new(records, targets, ()));
+    let history = trainer.train().unwrap();
+    assert_eq!(history.epochs(), 5);
+    assert!(history.loss().last() < history.loss().first());
+}
+
+#[test]
+fn test_ffn_backprop() {
+    use cnc::autodiff::{Tape, Trace, TraceLoss};
+    use cnc::func::loss::{LossGrad, MSE};
+    use cnc::prelude::NdActivate;
+    use cnc::Backward;
+
+    let (samples, d_model, d_ff) = (6, 3, 4);
+    let mut model = FeedForwardNetwork::<f64, Biased>::std(d_model, d_ff, None);
+    let w1 = linarr::<f64, Ix2>((d_ff, d_model)).unwrap();
+    let w2 = linarr::<f64, Ix2>((d_model, d_ff)).unwrap();
+    model
+        .input_mut()
+        .weights_mut()
+        .assign(&(w1.mapv(f64::sin) / 2.0));
+    model
+        .output_mut()
+        .weights_mut()
+        .assign(&(w2.mapv(f64::cos) / 2.0));
+    model.input_mut().bias_mut().fill(-0.1);
+    let x = linarr::<f64, Ix2>((samples, d_model))
+        .unwrap()
+        .mapv(f64::sin);
+    let y = x.mapv(|v| 2.0 * v + 1.0);
+
+    // backpropagate by hand, seeded by the gradient of the loss
+    let (w1, b1) = (model.input().weights(), model.input().bias());
+    let w2 = model.output().weights();
+    let h = x.dot(&w1.t()) + b1;
+    let a = h.relu();
+    let pred = model.predict(&x).unwrap();
+    let dy = MSE.loss_grad(&pred, &y);
+    let dw2 = dy.t().dot(&a);
+    let dh = dy.dot(w2) * h.relu_derivative();
+    let dw1 = dh.t().dot(&x);
+    let db1 = dh.sum_axis(Axis(0));
+
+    let tape = Tape::new();
+    let (out, vars) = model.trace(&tape, tape.var(&x));
+    let loss = MSE.trace_loss(out, &y.view()).unwrap();
+    let (inner, outer) = model.grad(&vars, &loss.backward());
+    fn close<D: Dimension>(a: &Array<f64, D>, b: &Array<f64, D>) -> bool {
+        (a - b).iter().all(|e| e.abs() < 1e-10)
+    }
+    assert!(close(&dw2, outer.weights()));
+    assert!(close(&dw1, inner.weights()));
+    assert!(close(&db1, inner.bias()));
+}
+
+#[test]
+fn test_ffn_activation() {
+    use cnc::autodiff::{Tape, Trace};
+    use cnc::func::activate::Activation;
+
+    let (samples, d_model, d_ff) = (4, 3, 5);
+    let mut model = FeedForwardNetwork::<f64, Biased>::std(d_model, d_ff, None)
+        .with_activation(Activation::GELU);
+    model
+        .input_mut()
+        .weights_mut()
+        .assign(&linarr::<f64, Ix2>((d_ff, d_model)).unwrap().mapv(f64::sin));
+    model.output_mut().weights_mut().fill(0.5);
+    assert_eq!(model.activation(), Activation::GELU);
+
+    let x = linarr::<f64, Ix2>((samples, d_model)).unwrap() - 4.0;
+    let pred = model.predict(&x).unwrap();
+    let relu = model.input().predict(&x).unwrap().mapv(|v| v.max(0.0));
+    assert_ne!(pred, model.output().predict(&relu).unwrap());
+
+    let tape = Tape::new();
+    let (out, _vars) = model.trace(&tape, tape.var(&x));
+    assert!((out.value() - pred.into_dyn())
+        .iter()
+        .all(|d| d.abs() < 1e-12));
+}
+
+#[test]
+fn test_ffn_grad_check() {
+    use cnc::autodiff::{GradCheck, Tape, Trace, TraceLoss};
+    use cnc::func::activate::Activation;
+    use cnc::func::loss::MSE;
+    use cnc::Backward;
+
+    let (samples, d_model, d_ff) = (5, 3, 4);
+    let mut model = FeedForwardNetwork::<f64, Biased>::std(d_model, d_ff, None)
+        .with_activation(Activation::Tanh);
+    model
+        .input_mut()
+        .weights_mut()
+        .assign(&linarr::<f64, Ix2>((d_ff, d_model)).unwrap().mapv(f64::sin));
+    model
+        .output_mut()
+        .weights_mut()
+        .assign(&linarr::<f64, Ix2>((d_model, d_ff)).unwrap().mapv(f64::cos));
+    let x = linarr::<f64, Ix2>((samples, d_model)).unwrap() / 10.0;
+    let y = x.mapv(|v| 2.0 * v - 1.0);
+
+    let tape = Tape::new();
+    let (out, vars) = model.trace(&tape, tape.var(&x));
+    let grad = model.grad(&vars, &MSE.trace_loss(out, &y.view()).unwrap().backward());
+
+    let errs = GradCheck::default().check_loss(&mut model, &grad, &x, &y, &MSE);
+    assert_eq!(errs.len(), 4);
+    assert!(errs.iter().all(|e| e.is_close(1e-8, 1e-6)), "{errs:?}");
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_ffn_init_with() {
+    use cnc::init::Init;
+
+    let (d_model, d_ff) = (8, 16);
+    let model = FeedForwardNetwork::<f64, Biased>::std(d_model, d_ff, None)
+        .init_with(Init::Xavier { uniform: true }, Init::Zeros)
+        .unwrap();
+    let bnd = (6.0 / (d_model + d_ff) as f64).sqrt();
+    for layer in [model.input(), model.output()] {
+        assert!(layer.weights().iter().all(|&w| w.abs() <= bnd));
+        assert!(layer.bias().iter().all(|&b| b == 0.0));
+    }
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_ffn_init_with_rng() {
+    use cnc::init::{Init, RngContext};
+
+    let init = |seed: u64| {
+        FeedForwardNetwork::<f64, Biased>::std(4, 8, None)
+            .init_with_rng(
+                Init::Lecun,
+                Init::Uniform { bound: None },
+                &mut RngContext::new(seed),
+            )
+            .unwrap()
+    };
+    let (a, b) = (init(1), init(1));
+    assert_eq!(a.input().weights(), b.input().weights());
+    assert_eq!(a.output().bias(), b.output().bias());
+    assert_ne!(a.input().weights(), init(2).input().weights());
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_ffn_mode() {
+    use cnc::init::Init;
+    use cnc::nn::{Mode, Training};
+
+    let (samples, d_model, d_ff) = (10, 4, 8);
+    let mut model = FeedForwardNetwork::<f64, Biased>::std(d_model, d_ff, Some(0.5))
+        .init_with(Init::Ones, Init::Ones)
+        .unwrap();
+    let data = linarr::<f64, Ix2>((samples, d_model)).unwrap();
+
+    model.eval_mode();
+    assert_eq!(model.dropout().map(|d| d.mode()), Some(Mode::Eval));
+    assert_eq!(model.input().mode(), Mode::Eval);
+    assert_eq!(model.output().mode(), Mode::Eval);
+    let pred = model.predict(&data).unwrap();
+    assert_eq!(pred, model.predict(&data).unwrap());
+
+    model.train_mode();
+    assert!(model.is_training());
+    assert_ne!(pred, model.predict(&data).unwrap());
+}
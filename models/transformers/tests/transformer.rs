@@ -2,3 +2,52 @@
     Appellation: transformer <test>
     Contrib: FL03 <jo3mccain@icloud.com>
 */
+extern crate concision_core as concision;
+extern crate concision_linear as linear;
+extern crate concision_transformer as transformer;
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_sublayer_drop_path() {
+    use concision::nn::{Mode, Training};
+    use transformer::model::sublayer::Sublayer;
+
+    let mut sublayer = Sublayer::<f64>::new((4, 8), 0.1).with_drop_path(0.2);
+    assert_eq!(sublayer.drop_path().map(|d| d.p()), Some(0.2));
+    assert!(sublayer.drop_path().unwrap().is_training());
+
+    sublayer.eval_mode();
+    assert_eq!(sublayer.mode(), Mode::Eval);
+    assert_eq!(sublayer.drop_path().map(|d| d.mode()), Some(Mode::Eval));
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn test_sublayer_drop_path_forward() {
+    use concision::nn::Training;
+    use concision::prelude::linarr;
+    use concision::Forward;
+    use linear::LayerNorm;
+    use ndarray::prelude::*;
+    use transformer::model::sublayer::Sublayer;
+
+    let shape = (4, 4);
+    let x = linarr::<f64, Ix2>(shape).unwrap();
+    let sublayer = |p: f64| {
+        let mut sublayer = Sublayer::<f64>::new(shape, 0.0).with_drop_path(p);
+        *sublayer.norm_mut() = LayerNorm::ones(shape);
+        sublayer
+    };
+    let normal = sublayer(0.0).norm().forward(&x);
+    // each sample either skips the branch or scales it by 1 / (1 - p)
+    let y = sublayer(0.5).forward(&x);
+    for ((y, x), normal) in y.rows().into_iter().zip(x.rows()).zip(normal.rows()) {
+        assert!(y == x || y == &x + &(&normal * 2.0));
+    }
+    // every sample skips the branch
+    assert_eq!(sublayer(1.0).forward(&x), x);
+    // the branch is kept as is during evaluation
+    let mut model = sublayer(1.0);
+    model.eval_mode();
+    assert_eq!(model.forward(&x), &x + &normal);
+}